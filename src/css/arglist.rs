@@ -0,0 +1,53 @@
+use super::{CallArgs, Value};
+use crate::ordermap::OrderMap;
+use crate::value::{ListSeparator, Quotes};
+
+/// The value of a rest argument, as `$args` in `@mixin foo($args...)`.
+///
+/// An argument list is a comma-separated list of the positional
+/// arguments, that also knows any named arguments that were not
+/// matched by other arguments.  The named arguments are available by
+/// the `keywords` function.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ArgList {
+    pub positional: Vec<Value>,
+    /// Named arguments, with names as in the source (but with `_`
+    /// for `-`).
+    pub named: Vec<(String, Value)>,
+}
+
+impl ArgList {
+    pub fn new(positional: Vec<Value>, named: Vec<(String, Value)>) -> Self {
+        ArgList { positional, named }
+    }
+
+    /// Get the named arguments as a map from unquoted names (without
+    /// the `$`) to values.
+    pub fn keywords(&self) -> OrderMap<Value, Value> {
+        let mut map = OrderMap::new();
+        for (name, value) in &self.named {
+            map.insert(
+                Value::Literal(name.replace('_', "-"), Quotes::None),
+                value.clone(),
+            );
+        }
+        map
+    }
+
+    /// Get the positional arguments as a plain list.
+    pub fn to_list(&self) -> Value {
+        Value::List(self.positional.clone(), ListSeparator::Comma, false)
+    }
+}
+
+impl From<ArgList> for CallArgs {
+    fn from(args: ArgList) -> CallArgs {
+        CallArgs(
+            args.positional
+                .into_iter()
+                .map(|v| (None, v))
+                .chain(args.named.into_iter().map(|(k, v)| (Some(k), v)))
+                .collect(),
+        )
+    }
+}
@@ -19,6 +19,7 @@ impl CallArgs {
             Value::List(v, _, false) => {
                 CallArgs(v.into_iter().map(|v| (None, v)).collect())
             }
+            Value::ArgList(args) => args.into(),
             v => CallArgs(vec![(None, v)]),
         }
     }
@@ -0,0 +1,434 @@
+//! Media queries, as used in `@media` rules.
+//!
+//! The queries of nested `@media` rules are merged, so that the
+//! output contains the query that matches what both of them matches.
+use std::fmt;
+
+/// A single media query, like `not screen and (color)`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct MediaQuery {
+    /// The modifier `not` or `only`, if any.
+    modifier: Option<String>,
+    /// The media type, like `screen`, if any.
+    media_type: Option<String>,
+    /// The conditions, like `(color)` or `(min-width: 20em)`.
+    conditions: Vec<String>,
+    /// True if all conditions must match, false if any of them may.
+    conjunction: bool,
+}
+
+/// The result of merging two media queries.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) enum MergeResult {
+    /// No media can match both queries.
+    Empty,
+    /// The queries overlap, but there is no way to write a single
+    /// query for that.
+    Unrepresentable,
+    /// A query matching exactly what both queries matches.
+    Query(MediaQuery),
+}
+
+impl MediaQuery {
+    /// Parse a comma-separated list of media queries.
+    ///
+    /// Returns `None` if the text is not a list of queries that this
+    /// module understands.
+    pub(crate) fn parse_list(text: &str) -> Option<Vec<MediaQuery>> {
+        split_top_level(text, ',')
+            .iter()
+            .map(|q| MediaQuery::parse(q))
+            .collect()
+    }
+
+    fn parse(text: &str) -> Option<MediaQuery> {
+        let tokens = tokenize(text)?;
+        let first = tokens.first()?;
+        if first.starts_with('(')
+            || (first.eq_ignore_ascii_case("not")
+                && tokens.get(1).map_or(false, |t| t.starts_with('(')))
+        {
+            let (conditions, conjunction) = parse_conditions(&tokens)?;
+            return Some(MediaQuery {
+                modifier: None,
+                media_type: None,
+                conditions,
+                conjunction,
+            });
+        }
+        let mut tokens = tokens.into_iter().peekable();
+        let mut modifier = None;
+        let mut media_type = tokens.next()?;
+        if media_type.eq_ignore_ascii_case("not")
+            || media_type.eq_ignore_ascii_case("only")
+        {
+            modifier = Some(media_type);
+            media_type = tokens.next()?;
+        }
+        if media_type.starts_with('(') || is_keyword(&media_type) {
+            return None;
+        }
+        let mut conditions = Vec::new();
+        while let Some(and) = tokens.next() {
+            let condition = tokens.next()?;
+            if !and.eq_ignore_ascii_case("and") || !condition.starts_with('(')
+            {
+                return None;
+            }
+            conditions.push(condition);
+        }
+        Some(MediaQuery {
+            modifier,
+            media_type: Some(media_type),
+            conditions,
+            conjunction: true,
+        })
+    }
+
+    fn lower_modifier(&self) -> Option<String> {
+        self.modifier.as_ref().map(|m| m.to_lowercase())
+    }
+    fn lower_type(&self) -> Option<String> {
+        self.media_type.as_ref().map(|t| t.to_lowercase())
+    }
+    fn is_not(&self) -> bool {
+        self.lower_modifier().map_or(false, |m| m == "not")
+    }
+    fn matches_all_types(&self) -> bool {
+        self.lower_type().map_or(true, |t| t == "all")
+    }
+
+    /// Merge this query with another, to a query that matches only
+    /// what both queries matches.
+    pub(crate) fn merge(&self, other: &MediaQuery) -> MergeResult {
+        if !self.conjunction || !other.conjunction {
+            return MergeResult::Unrepresentable;
+        }
+        let our_modifier = self.lower_modifier();
+        let our_type = self.lower_type();
+        let their_modifier = other.lower_modifier();
+        let their_type = other.lower_type();
+
+        if our_type.is_none() && their_type.is_none() {
+            return MergeResult::Query(MediaQuery {
+                modifier: None,
+                media_type: None,
+                conditions: concat(&self.conditions, &other.conditions),
+                conjunction: true,
+            });
+        }
+
+        let (modifier, media_type, conditions) = if self.is_not()
+            != other.is_not()
+        {
+            if our_type == their_type {
+                let (negative, positive) = if self.is_not() {
+                    (&self.conditions, &other.conditions)
+                } else {
+                    (&other.conditions, &self.conditions)
+                };
+                // `not screen and (color)` means `not (screen and
+                // (color))`, so it overlaps `screen and (grid)`.
+                return if negative.iter().all(|c| positive.contains(c)) {
+                    MergeResult::Empty
+                } else {
+                    MergeResult::Unrepresentable
+                };
+            } else if self.matches_all_types() || other.matches_all_types() {
+                return MergeResult::Unrepresentable;
+            }
+            if self.is_not() {
+                (their_modifier, their_type, other.conditions.clone())
+            } else {
+                (
+                    our_modifier.clone(),
+                    our_type.clone(),
+                    self.conditions.clone(),
+                )
+            }
+        } else if self.is_not() {
+            // There is no way to write "neither screen nor print".
+            if our_type != their_type {
+                return MergeResult::Unrepresentable;
+            }
+            let (more, fewer) =
+                if self.conditions.len() > other.conditions.len() {
+                    (&self.conditions, &other.conditions)
+                } else {
+                    (&other.conditions, &self.conditions)
+                };
+            // If one set of conditions contains the other, it is the
+            // narrower one.
+            if fewer.iter().all(|c| more.contains(c)) {
+                (our_modifier.clone(), our_type.clone(), more.clone())
+            } else {
+                return MergeResult::Unrepresentable;
+            }
+        } else if self.matches_all_types() {
+            // Omit the type if either query did.
+            let media_type =
+                if other.matches_all_types() && our_type.is_none() {
+                    None
+                } else {
+                    their_type
+                };
+            (
+                their_modifier,
+                media_type,
+                concat(&self.conditions, &other.conditions),
+            )
+        } else if other.matches_all_types() {
+            (
+                our_modifier.clone(),
+                our_type.clone(),
+                concat(&self.conditions, &other.conditions),
+            )
+        } else if our_type != their_type {
+            return MergeResult::Empty;
+        } else {
+            (
+                our_modifier.clone().or(their_modifier),
+                our_type.clone(),
+                concat(&self.conditions, &other.conditions),
+            )
+        };
+
+        MergeResult::Query(MediaQuery {
+            modifier: if modifier == our_modifier {
+                self.modifier.clone()
+            } else {
+                other.modifier.clone()
+            },
+            media_type: if media_type == our_type {
+                self.media_type.clone()
+            } else {
+                other.media_type.clone()
+            },
+            conditions,
+            conjunction: true,
+        })
+    }
+}
+
+/// Merge two lists of media queries, to the list of queries that
+/// matches what both lists matches.
+///
+/// Returns `None` if any pair of queries can't be merged.  An empty
+/// result means that no media can match both lists.
+pub(crate) fn merge_lists(
+    list1: &[MediaQuery],
+    list2: &[MediaQuery],
+) -> Option<Vec<MediaQuery>> {
+    let mut result = Vec::new();
+    for query1 in list1 {
+        for query2 in list2 {
+            match query1.merge(query2) {
+                MergeResult::Empty => (),
+                MergeResult::Unrepresentable => return None,
+                MergeResult::Query(query) => result.push(query),
+            }
+        }
+    }
+    Some(result)
+}
+
+impl fmt::Display for MediaQuery {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        let mut sep = "";
+        if let Some(ref modifier) = self.modifier {
+            write!(out, "{} ", modifier)?;
+        }
+        if let Some(ref media_type) = self.media_type {
+            out.write_str(media_type)?;
+            sep = " and ";
+        }
+        for condition in &self.conditions {
+            write!(out, "{}{}", sep, condition)?;
+            sep = if self.conjunction { " and " } else { " or " };
+        }
+        Ok(())
+    }
+}
+
+/// Conditions separated by either `and` or `or`.
+fn parse_conditions(tokens: &[String]) -> Option<(Vec<String>, bool)> {
+    let mut conditions = Vec::new();
+    let mut conjunction = None;
+    let mut tokens = tokens.iter();
+    loop {
+        let token = tokens.next()?;
+        if token.eq_ignore_ascii_case("not") {
+            conditions.push(format!("{} {}", token, tokens.next()?));
+        } else if token.starts_with('(') {
+            conditions.push(token.clone());
+        } else {
+            return None;
+        }
+        match tokens.next() {
+            None => break,
+            Some(op) => {
+                let and = if op.eq_ignore_ascii_case("and") {
+                    true
+                } else if op.eq_ignore_ascii_case("or") {
+                    false
+                } else {
+                    return None;
+                };
+                if *conjunction.get_or_insert(and) != and {
+                    return None;
+                }
+            }
+        }
+    }
+    Some((conditions, conjunction.unwrap_or(true)))
+}
+
+fn is_keyword(word: &str) -> bool {
+    ["and", "not", "only", "or"]
+        .iter()
+        .any(|k| word.eq_ignore_ascii_case(k))
+}
+
+fn concat(a: &[String], b: &[String]) -> Vec<String> {
+    a.iter().chain(b).cloned().collect()
+}
+
+/// Split a query into words and parenthesized groups.
+fn tokenize(text: &str) -> Option<Vec<String>> {
+    let mut result = Vec::new();
+    let mut chars = text.trim().chars().peekable();
+    while let Some(c) = chars.next() {
+        if c.is_whitespace() {
+            continue;
+        }
+        let mut token = c.to_string();
+        if c == '(' {
+            let mut depth = 1;
+            while depth > 0 {
+                let c = chars.next()?;
+                match c {
+                    '(' => depth += 1,
+                    ')' => depth -= 1,
+                    _ => (),
+                }
+                token.push(c);
+            }
+        } else if c == ')' {
+            return None;
+        } else {
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() || c == '(' || c == ')' {
+                    break;
+                }
+                token.push(c);
+                chars.next();
+            }
+        }
+        result.push(token);
+    }
+    Some(result)
+}
+
+/// Split `text` at each `sep` that is not inside parenthesis.
+fn split_top_level(text: &str, sep: char) -> Vec<&str> {
+    let mut result = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in text.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            c if c == sep && depth == 0 => {
+                result.push(&text[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => (),
+        }
+    }
+    result.push(&text[start..]);
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::{merge_lists, MediaQuery};
+
+    fn merged(outer: &str, inner: &str) -> Option<String> {
+        let outer = MediaQuery::parse_list(outer).unwrap();
+        let inner = MediaQuery::parse_list(inner).unwrap();
+        merge_lists(&outer, &inner).map(|queries| {
+            queries
+                .iter()
+                .map(|q| q.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        })
+    }
+
+    #[test]
+    fn type_and_feature() {
+        assert_eq!(
+            merged("screen", "(color)").unwrap(),
+            "screen and (color)"
+        );
+        assert_eq!(
+            merged("(color)", "screen").unwrap(),
+            "screen and (color)"
+        );
+    }
+    #[test]
+    fn features() {
+        assert_eq!(
+            merged("(max-width: 300px)", "all and (min-width: 200px)")
+                .unwrap(),
+            "(max-width: 300px) and (min-width: 200px)"
+        );
+        assert_eq!(
+            merged("all and (max-width: 300px)", "all and (min-width: 2px)")
+                .unwrap(),
+            "all and (max-width: 300px) and (min-width: 2px)"
+        );
+    }
+    #[test]
+    fn only_is_kept() {
+        assert_eq!(
+            merged("only screen", "all and (color)").unwrap(),
+            "only screen and (color)"
+        );
+    }
+    #[test]
+    fn not_and_other_type() {
+        assert_eq!(merged("not screen", "print").unwrap(), "print");
+    }
+    #[test]
+    fn narrower_not() {
+        assert_eq!(
+            merged("not screen", "not screen and (color)").unwrap(),
+            "not screen and (color)"
+        );
+    }
+    #[test]
+    fn empty() {
+        assert_eq!(merged("screen", "print").unwrap(), "");
+        assert_eq!(merged("screen and (color)", "not screen").unwrap(), "");
+    }
+    #[test]
+    fn cross_product() {
+        assert_eq!(
+            merged("screen, print", "speech, (grid)").unwrap(),
+            "screen and (grid), print and (grid)"
+        );
+    }
+    #[test]
+    fn unrepresentable() {
+        assert_eq!(merged("not screen", "(color)"), None);
+        assert_eq!(merged("not screen", "not print"), None);
+        assert_eq!(merged("screen, not screen", "(color)"), None);
+        assert_eq!(merged("(a) or (b)", "(c)"), None);
+    }
+    #[test]
+    fn unparsable() {
+        assert_eq!(MediaQuery::parse_list("screen (color)"), None);
+        assert_eq!(MediaQuery::parse_list("screen and"), None);
+    }
+}
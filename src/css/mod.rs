@@ -1,6 +1,11 @@
+mod arglist;
 mod call_args;
+mod media;
 mod value;
 mod valueformat;
 
+pub use self::arglist::ArgList;
 pub use self::call_args::CallArgs;
+pub(crate) use self::media::{merge_lists, MediaQuery};
 pub use self::value::Value;
+pub(crate) use self::valueformat::CalcArg;
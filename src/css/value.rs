@@ -1,11 +1,16 @@
-use crate::css::CallArgs;
+use crate::css::{ArgList, CallArgs};
 use crate::error::Error;
 use crate::functions::SassFunction;
-use crate::ordermap::OrderMap;
+use crate::ordermap::{MapKey, OrderMap};
 use crate::output::{Format, Formatted};
-use crate::value::{ListSeparator, Number, Operator, Quotes, Rgba, Unit};
+use crate::value::{
+    equal_values, CalcName, ListSeparator, Number, Operator, Quotes, Rgba,
+    Unit,
+};
 use num_rational::Rational;
 use std::convert::TryFrom;
+use std::hash::{Hash, Hasher};
+use std::mem::discriminant;
 
 /// A css value.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
@@ -14,11 +19,18 @@ pub enum Value {
     Bang(String),
     /// An function call that was not evaluated.
     Call(String, CallArgs),
+    /// A css calculation that could not be simplified to a number.
+    ///
+    /// The arguments are numbers, strings, nested calculations, or
+    /// binary operations on those.
+    Calc(CalcName, Vec<Value>),
     /// A (callable?) function.
     Function(String, Option<SassFunction>),
     Literal(String, Quotes),
     /// A comma- or space separated list of values, with or without brackets.
     List(Vec<Value>, ListSeparator, bool),
+    /// The rest argument of a function or mixin.
+    ArgList(ArgList),
     /// A Numeric value is a rational value with a Unit (which may be
     /// Unit::None) and flags.
     ///
@@ -40,6 +52,57 @@ pub enum Value {
     Paren(Box<Value>),
 }
 
+/// Map keys are compared by sass equality, so e.g. `1in` and `96px`,
+/// or `"a"` and `a`, are the same key.
+impl MapKey for Value {
+    fn hash_key<H: Hasher>(&self, state: &mut H) {
+        discriminant(self).hash(state);
+        match *self {
+            Value::Literal(ref s, _) => s.hash(state),
+            Value::Numeric(ref num, ref unit, _) => {
+                // Numbers in absolute units are equal if they are the
+                // same in the base unit of their dimension.
+                if unit.is_absolute() {
+                    unit.dimension().hash(state);
+                    exact_product(&num.value, &unit.scale_factor())
+                        .hash(state);
+                } else {
+                    unit.hash(state);
+                    num.value.hash(state);
+                }
+            }
+            Value::Color(ref rgba, _) => rgba.hash(state),
+            Value::List(ref v, ref sep, bracketed) => {
+                sep.hash(state);
+                bracketed.hash(state);
+                for item in v {
+                    item.hash_key(state);
+                }
+            }
+            // Other values are rarely used as keys, so colliding is ok.
+            _ => (),
+        }
+    }
+    fn eq_key(&self, other: &Self) -> bool {
+        equal_values(self, other)
+    }
+}
+
+/// Multiply two rationals without overflow, as a reduced fraction.
+fn exact_product(a: &Rational, b: &Rational) -> (i128, i128) {
+    fn gcd(a: i128, b: i128) -> i128 {
+        if b == 0 {
+            a.abs()
+        } else {
+            gcd(b, a % b)
+        }
+    }
+    let numer = *a.numer() as i128 * *b.numer() as i128;
+    let denom = *a.denom() as i128 * *b.denom() as i128;
+    let d = gcd(numer, denom);
+    (numer / d, denom / d)
+}
+
 impl Value {
     pub fn scalar<T: Into<Number>>(v: T) -> Self {
         Value::Numeric(v.into(), Unit::None, false)
@@ -60,6 +123,9 @@ impl Value {
     pub fn hsla(h: Rational, s: Rational, l: Rational, a: Rational) -> Self {
         Value::Color(Rgba::from_hsla(h / 360, s, l, a), None)
     }
+    pub fn hwba(h: Rational, w: Rational, b: Rational, a: Rational) -> Self {
+        Value::Color(Rgba::from_hwba(h / 360, w, b, a), None)
+    }
 
     pub fn type_name(&self) -> &'static str {
         match *self {
@@ -67,7 +133,9 @@ impl Value {
             Value::Literal(..) => "string",
             Value::Numeric(..) => "number",
             Value::List(..) => "list",
+            Value::ArgList(..) => "arglist",
             Value::Function(..) => "function",
+            Value::Calc(..) => "calculation",
             Value::True | Value::False => "bool",
             Value::Null => "null",
             _ => "unknown",
@@ -109,6 +177,7 @@ impl Value {
                 list.iter().all(|v| v.is_null())
             }
             Value::Literal(ref s, Quotes::None) if s.is_empty() => true,
+            Value::ArgList(ref args) => args.to_list().is_null(),
             Value::Paren(ref v) => v.is_null(),
             _ => false,
         }
@@ -117,7 +186,7 @@ impl Value {
     pub fn integer_value(&self) -> Result<isize, Error> {
         match self {
             &Value::Numeric(ref num, ..) if num.is_integer() => {
-                Ok(num.to_integer())
+                Ok(num.value.round().to_integer())
             }
             v => Err(Error::bad_value("integer", v)),
         }
@@ -298,6 +367,7 @@ impl Value {
     pub fn iter_items(self) -> Vec<Value> {
         match self {
             Value::List(v, _, _) => v,
+            Value::ArgList(args) => args.positional,
             Value::Map(map) => map
                 .iter()
                 .map(|&(ref k, ref v)| {
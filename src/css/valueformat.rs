@@ -1,5 +1,5 @@
 use super::Value;
-use crate::output::Formatted;
+use crate::output::{ColorFormat, Format, Formatted};
 use crate::value::{ListSeparator, Operator, Quotes};
 use std::fmt::{self, Display, Write};
 
@@ -44,7 +44,9 @@ impl<'a> Display for Formatted<'a, Value> {
                 write!(out, "{}{}", num.format(self.format), unit)
             }
             Value::Color(ref rgba, ref name) => {
-                if let Some(ref name) = *name {
+                if let (Some(ref name), ColorFormat::Auto) =
+                    (name, self.format.colors())
+                {
                     name.fmt(out)
                 } else {
                     rgba.format(self.format).fmt(out)
@@ -80,6 +82,7 @@ impl<'a> Display for Formatted<'a, Value> {
                             }
                         }
                         ListSeparator::Space => " ",
+                        ListSeparator::Slash => "/",
                     })
                 };
                 if brackets {
@@ -91,9 +94,28 @@ impl<'a> Display for Formatted<'a, Value> {
                 }
                 Ok(())
             }
+            Value::ArgList(ref args) => {
+                // A single argument is written as itself, the `(2,)`
+                // form is only used by `inspect()`.
+                if let [ref single] = args.positional[..] {
+                    single.format(self.format).fmt(out)
+                } else {
+                    args.to_list().format(self.format).fmt(out)
+                }
+            }
             Value::Call(ref name, ref arg) => {
                 write!(out, "{}({})", name, arg)
             }
+            Value::Calc(ref name, ref args) => {
+                write!(out, "{}(", name)?;
+                for (i, arg) in args.iter().enumerate() {
+                    if i > 0 {
+                        out.write_str(", ")?;
+                    }
+                    write_calc_arg(out, arg, self.format)?;
+                }
+                out.write_char(')')
+            }
             Value::BinOp(ref a, _, Operator::Plus, _, ref b) => {
                 // The plus operator is also a concat operator
                 a.format(self.format).fmt(out)?;
@@ -141,3 +163,37 @@ impl<'a> Display for Formatted<'a, Value> {
         }
     }
 }
+
+/// A value displayed as an argument of a calculation, e.g. for
+/// `meta.calc-args`.
+pub(crate) struct CalcArg<'a>(pub &'a Value);
+
+impl Display for CalcArg<'_> {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        write_calc_arg(out, self.0, Default::default())
+    }
+}
+
+/// Write an argument of a css calculation.
+///
+/// Operators inside a calculation are always surrounded by
+/// whitespace, as required for `+` and `-` in css.
+fn write_calc_arg(
+    out: &mut fmt::Formatter,
+    arg: &Value,
+    format: Format,
+) -> fmt::Result {
+    match *arg {
+        Value::BinOp(ref a, _, ref op, _, ref b) => {
+            write_calc_arg(out, a, format)?;
+            write!(out, " {} ", op)?;
+            write_calc_arg(out, b, format)
+        }
+        Value::Paren(ref v) => {
+            out.write_char('(')?;
+            write_calc_arg(out, v, format)?;
+            out.write_char(')')
+        }
+        ref v => v.format(format).fmt(out),
+    }
+}
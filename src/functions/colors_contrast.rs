@@ -0,0 +1,249 @@
+//! Accessibility helpers, based on the WCAG 2 definitions of relative
+//! luminance and contrast ratio.
+//!
+//! See <https://www.w3.org/TR/WCAG21/#dfn-relative-luminance>.
+//! The alpha channel of colors is ignored.
+//!
+//! These functions are rsass extensions, they are not defined by the
+//! sass language.  To keep the builtin modules compatible with other
+//! sass implementations, they are only available as global functions,
+//! not in the `sass:color` module.
+use super::colors_space::{get_color, number};
+use super::{Error, SassFunction};
+use crate::css::{ArgList, Value};
+use crate::value::{from_f64, to_f64, ColorSpace, Rgba, SpaceColor, Unit};
+use num_rational::Rational;
+use num_traits::Zero;
+use std::collections::BTreeMap;
+
+pub fn register(f: &mut BTreeMap<&'static str, SassFunction>) {
+    def!(f, luminance(color), |s| {
+        let color = get_color(s.get("color")?)?;
+        Ok(number(from_f64(luminance(&color)), Unit::None))
+    });
+    def!(f, contrast(color1, color2), |s| {
+        let a = get_color(s.get("color1")?)?;
+        let b = get_color(s.get("color2")?)?;
+        Ok(number(from_f64(contrast(&a, &b)), Unit::None))
+    });
+    def_va!(f, best_contrast(background, candidates), |s| {
+        let background = get_color(s.get("background")?)?;
+        let candidates = match s.get("candidates")? {
+            Value::ArgList(ref args) if args.positional.is_empty() => {
+                vec![
+                    Value::black(),
+                    Value::Color(Rgba::from_rgb(255, 255, 255), None),
+                ]
+            }
+            // A single list of candidates is also accepted.
+            Value::ArgList(mut args) if args.positional.len() == 1 => {
+                args.positional.remove(0).iter_items()
+            }
+            Value::ArgList(ArgList { positional: v, .. }) => v,
+            v => vec![v],
+        };
+        let mut best: Option<(f64, Value)> = None;
+        for candidate in candidates {
+            let ratio = contrast(&background, &get_color(candidate.clone())?);
+            if best.as_ref().map(|b| ratio > b.0).unwrap_or(true) {
+                best = Some((ratio, candidate));
+            }
+        }
+        best.map(|b| b.1).ok_or_else(|| {
+            Error::BadArguments("$candidates: No colors given.".into())
+        })
+    });
+    def!(f, ensure_contrast(color, background, ratio = b"4.5"), |s| {
+        let value = s.get("color")?;
+        let color = get_color(value.clone())?;
+        let background = get_color(s.get("background")?)?;
+        let ratio = match s.get("ratio")? {
+            Value::Numeric(ref v, Unit::None, _) => to_f64(v.value),
+            v => return Err(Error::badarg("number", &v)),
+        };
+        if contrast(&color, &background) >= ratio {
+            return Ok(value);
+        }
+        Ok(Value::Color(
+            ensure_contrast(
+                rounded(Rgba::from_space(color)),
+                &background,
+                ratio,
+            ),
+            None,
+        ))
+    });
+}
+
+/// Adjust the lightness of `color` as little as possible to get at
+/// least `ratio` contrast against `background`.
+///
+/// Both darker and lighter variants are considered.  If neither can
+/// reach the ratio, the extreme with the best contrast is returned.
+fn ensure_contrast(color: Rgba, background: &SpaceColor, ratio: f64) -> Rgba {
+    let (h, s, l, a) = color.to_hsla();
+    let with_lightness =
+        |l: Rational| rounded(Rgba::from_hsla(h / 360, s, l, a));
+    let bg = luminance(background);
+    let lum = |c: &Rgba| luminance(&c.to_space_color());
+    let darker = |c: &Rgba| (bg + 0.05) / (lum(c) + 0.05) >= ratio;
+    let lighter = |c: &Rgba| (lum(c) + 0.05) / (bg + 0.05) >= ratio;
+
+    let black = with_lightness(Rational::zero());
+    let white = with_lightness(Rational::from_integer(1));
+    let dark = if darker(&black) {
+        Some(bisect(Rational::zero(), l, |l| darker(&with_lightness(l))))
+    } else {
+        None
+    };
+    let light = if lighter(&white) {
+        let one = Rational::from_integer(1);
+        Some(bisect(one, l, |l| lighter(&with_lightness(l))))
+    } else {
+        None
+    };
+    match (dark, light) {
+        (Some(d), Some(li)) if (l - d) <= (li - l) => with_lightness(d),
+        (_, Some(li)) => with_lightness(li),
+        (Some(d), None) => with_lightness(d),
+        (None, None) => {
+            let contrast =
+                |c: &Rgba| contrast(&c.to_space_color(), background);
+            if contrast(&black) >= contrast(&white) {
+                black
+            } else {
+                white
+            }
+        }
+    }
+}
+
+/// Find the lightness closest to `fail` that is `ok`, given that
+/// `ok(good)` holds and the predicate is monotone between the two.
+fn bisect<F>(mut good: Rational, mut fail: Rational, ok: F) -> Rational
+where
+    F: Fn(Rational) -> bool,
+{
+    for _ in 0..16 {
+        let mid = (good + fail) / 2;
+        if ok(mid) {
+            good = mid;
+        } else {
+            fail = mid;
+        }
+    }
+    good
+}
+
+/// The WCAG relative luminance of a color, from 0 (black) to 1 (white).
+///
+/// Colors outside the srgb gamut are clipped to it first.
+fn luminance(color: &SpaceColor) -> f64 {
+    let c = color
+        .to_space(ColorSpace::Srgb)
+        .clip()
+        .to_space(ColorSpace::SrgbLinear)
+        .channels_f64();
+    0.2126 * c[0] + 0.7152 * c[1] + 0.0722 * c[2]
+}
+
+/// The WCAG contrast ratio of two colors, from 1 to 21.
+fn contrast(a: &SpaceColor, b: &SpaceColor) -> f64 {
+    let (a, b) = (luminance(a), luminance(b));
+    let (light, dark) = if a > b { (a, b) } else { (b, a) };
+    (light + 0.05) / (dark + 0.05)
+}
+
+/// Round the rgb channels the same way they are rounded in output,
+/// so a contrast that is met here is also met in the generated css.
+fn rounded(color: Rgba) -> Rgba {
+    Rgba::new(
+        color.red.round(),
+        color.green.round(),
+        color.blue.round(),
+        color.alpha,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use crate::variablescope::test::do_evaluate;
+
+    #[test]
+    fn luminance_white() {
+        assert_eq!(do_evaluate(&[], b"luminance(white);"), "1")
+    }
+    #[test]
+    fn luminance_red() {
+        assert_eq!(do_evaluate(&[], b"luminance(red);"), "0.2126")
+    }
+    #[test]
+    fn contrast_black_white() {
+        assert_eq!(do_evaluate(&[], b"contrast(black, white);"), "21")
+    }
+    #[test]
+    fn contrast_symmetric() {
+        assert_eq!(
+            do_evaluate(&[], b"contrast(white, #777);"),
+            do_evaluate(&[], b"contrast(#777, white);"),
+        )
+    }
+    #[test]
+    fn best_contrast_default() {
+        assert_eq!(do_evaluate(&[], b"best-contrast(#ffd700);"), "black")
+    }
+    #[test]
+    fn best_contrast_dark_background() {
+        assert_eq!(
+            do_evaluate(&[], b"best-contrast(navy, #333, #eee, red);"),
+            "#eee"
+        )
+    }
+    #[test]
+    fn best_contrast_list() {
+        assert_eq!(
+            do_evaluate(&[], b"best-contrast(navy, (#333 red));"),
+            "red"
+        )
+    }
+    #[test]
+    fn ensure_contrast_already_met() {
+        assert_eq!(do_evaluate(&[], b"ensure-contrast(#333, white);"), "#333")
+    }
+    #[test]
+    fn ensure_contrast_darkens() {
+        assert_eq!(
+            do_evaluate(
+                &[],
+                b"contrast(ensure-contrast(#999, white), white) \
+                  >= 4.5;"
+            ),
+            "true"
+        )
+    }
+    #[test]
+    fn ensure_contrast_lightens() {
+        assert_eq!(
+            do_evaluate(
+                &[],
+                b"contrast(ensure-contrast(#345, #234, 7), #234) \
+                  >= 7;"
+            ),
+            "true"
+        )
+    }
+    #[test]
+    fn not_in_color_module() {
+        assert_eq!(
+            do_evaluate(&[], b"color.luminance(white);"),
+            "color.luminance(white)"
+        )
+    }
+    #[test]
+    fn ensure_contrast_keeps_hue() {
+        assert_eq!(
+            do_evaluate(&[], b"color.hue(ensure-contrast(#f66, white));"),
+            "0deg"
+        )
+    }
+}
@@ -1,6 +1,6 @@
-use super::{make_call, Error, SassFunction};
+use super::{colors_space, expose, make_call, Error, Module, SassFunction};
 use crate::css::Value;
-use crate::value::{Quotes, Unit};
+use crate::value::{ListSeparator, Number, Quotes, Unit};
 use crate::variablescope::Scope;
 use num_rational::Rational;
 use num_traits::{One, Signed, Zero};
@@ -10,7 +10,8 @@ pub fn register(f: &mut BTreeMap<&'static str, SassFunction>) {
     def!(
         f,
         adjust_color(
-            color, red, green, blue, hue, saturation, lightness, alpha
+            color, red, green, blue, hue, saturation, lightness, alpha,
+            whiteness, blackness
         ),
         |s: &dyn Scope| match &s.get("color")? {
             &Value::Color(ref rgba, _) => {
@@ -18,10 +19,30 @@ pub fn register(f: &mut BTreeMap<&'static str, SassFunction>) {
                     Value::Null => Ok(orig),
                     x => to_rational(x).map(|x| orig + x),
                 };
+                let sl_add = |orig: Rational, x: Value| match x {
+                    Value::Null => Ok(orig),
+                    x => to_rational_percent(x).map(|x| orig + x),
+                };
                 let h_adj = s.get("hue")?;
                 let s_adj = s.get("saturation")?;
                 let l_adj = s.get("lightness")?;
-                if h_adj.is_null() && s_adj.is_null() && l_adj.is_null() {
+                let w_adj = s.get("whiteness")?;
+                let b_adj = s.get("blackness")?;
+                if is_hwb(s)? {
+                    let (h, w, b, alpha) = rgba.to_hwba();
+                    Ok(Value::hwba(
+                        match h_adj {
+                            Value::Null => h,
+                            x => h + to_degrees(x)?,
+                        },
+                        sl_add(w, w_adj)?,
+                        sl_add(b, b_adj)?,
+                        c_add(alpha, "alpha")?,
+                    ))
+                } else if h_adj.is_null()
+                    && s_adj.is_null()
+                    && l_adj.is_null()
+                {
                     Ok(Value::rgba(
                         c_add(rgba.red, "red")?,
                         c_add(rgba.green, "green")?,
@@ -30,10 +51,6 @@ pub fn register(f: &mut BTreeMap<&'static str, SassFunction>) {
                     ))
                 } else {
                     let (h, s, l, alpha) = rgba.to_hsla();
-                    let sl_add = |orig: Rational, x: Value| match x {
-                        Value::Null => Ok(orig),
-                        x => to_rational_percent(x).map(|x| orig + x),
-                    };
                     Ok(Value::hsla(
                         c_add(h, "hue")?,
                         sl_add(s, s_adj)?,
@@ -48,7 +65,8 @@ pub fn register(f: &mut BTreeMap<&'static str, SassFunction>) {
     def!(
         f,
         scale_color(
-            color, red, green, blue, hue, saturation, lightness, alpha
+            color, red, green, blue, hue, saturation, lightness, alpha,
+            whiteness, blackness
         ),
         |s: &dyn Scope| match &s.get("color")? {
             &Value::Color(ref rgba, _) => {
@@ -56,6 +74,8 @@ pub fn register(f: &mut BTreeMap<&'static str, SassFunction>) {
                 let s_adj = s.get("saturation")?;
                 let l_adj = s.get("lightness")?;
                 let a_adj = s.get("alpha")?;
+                let w_adj = s.get("whiteness")?;
+                let b_adj = s.get("blackness")?;
 
                 let comb = |orig: Rational, x: Value, max: Rational| match x {
                     Value::Null => Ok(orig),
@@ -69,7 +89,18 @@ pub fn register(f: &mut BTreeMap<&'static str, SassFunction>) {
                 };
                 let one = Rational::one();
                 let ff = Rational::from_integer(255);
-                if h_adj.is_null() && s_adj.is_null() && l_adj.is_null() {
+                if is_hwb(s)? {
+                    let (h, w, b, alpha) = rgba.to_hwba();
+                    Ok(Value::hwba(
+                        h,
+                        comb(w, w_adj, one)?,
+                        comb(b, b_adj, one)?,
+                        comb(alpha, a_adj, one)?,
+                    ))
+                } else if h_adj.is_null()
+                    && s_adj.is_null()
+                    && l_adj.is_null()
+                {
                     Ok(Value::rgba(
                         comb(rgba.red, s.get("red")?, ff)?,
                         comb(rgba.green, s.get("green")?, ff)?,
@@ -102,8 +133,7 @@ pub fn register(f: &mut BTreeMap<&'static str, SassFunction>) {
     fn fade_in(color: Value, amount: Value) -> Result<Value, Error> {
         match (color, amount) {
             (Value::Color(rgba, _), Value::Numeric(v, ..)) => {
-                let a = rgba.alpha + v.value;
-                Ok(Value::rgba(rgba.red, rgba.green, rgba.blue, a))
+                Ok(Value::Color(rgba.with_alpha(rgba.alpha + v.value), None))
             }
             (c, v) => Err(Error::badargs(&["color", "number"], &[&c, &v])),
         }
@@ -114,8 +144,7 @@ pub fn register(f: &mut BTreeMap<&'static str, SassFunction>) {
     fn fade_out(color: Value, amount: Value) -> Result<Value, Error> {
         match (color, amount) {
             (Value::Color(rgba, _), Value::Numeric(v, ..)) => {
-                let a = rgba.alpha - v.value;
-                Ok(Value::rgba(rgba.red, rgba.green, rgba.blue, a))
+                Ok(Value::Color(rgba.with_alpha(rgba.alpha - v.value), None))
             }
             (c, v) => Err(Error::badargs(&["color", "number"], &[&c, &v])),
         }
@@ -126,13 +155,16 @@ pub fn register(f: &mut BTreeMap<&'static str, SassFunction>) {
     def!(
         f,
         change_color(
-            color, red, green, blue, hue, saturation, lightness, alpha
+            color, red, green, blue, hue, saturation, lightness, alpha,
+            whiteness, blackness
         ),
         |s: &dyn Scope| match s.get("color")? {
             Value::Color(rgba, _) => {
                 let h_adj = s.get("hue")?;
                 let s_adj = s.get("saturation")?;
                 let l_adj = s.get("lightness")?;
+                let w_adj = s.get("whiteness")?;
+                let b_adj = s.get("blackness")?;
 
                 let c_or = |name: &str, orig: Rational| match s.get(name)? {
                     Value::Null => Ok(orig),
@@ -146,12 +178,37 @@ pub fn register(f: &mut BTreeMap<&'static str, SassFunction>) {
                     Value::Null => Ok(orig),
                     x => to_rational_percent(x),
                 };
-                if h_adj.is_null() && s_adj.is_null() && l_adj.is_null() {
+                if is_hwb(s)? {
+                    let (h, w, b, alpha) = rgba.to_hwba();
+                    Ok(Value::hwba(
+                        match h_adj {
+                            Value::Null => h,
+                            x => to_degrees(x)?,
+                        },
+                        sl_or(w_adj, w)?,
+                        sl_or(b_adj, b)?,
+                        a_or("alpha", alpha)?,
+                    ))
+                } else if h_adj.is_null()
+                    && s_adj.is_null()
+                    && l_adj.is_null()
+                {
+                    let alpha = a_or("alpha", rgba.alpha)?;
+                    if s.get("red")?.is_null()
+                        && s.get("green")?.is_null()
+                        && s.get("blue")?.is_null()
+                    {
+                        // Only alpha is changed, keep the color space.
+                        return Ok(Value::Color(
+                            rgba.with_alpha(alpha),
+                            None,
+                        ));
+                    }
                     Ok(Value::rgba(
                         c_or("red", rgba.red)?,
                         c_or("green", rgba.green)?,
                         c_or("blue", rgba.blue)?,
-                        a_or("alpha", rgba.alpha)?,
+                        alpha,
                     ))
                 } else {
                     let (h, s, l, alpha) = rgba.to_hsla();
@@ -166,6 +223,22 @@ pub fn register(f: &mut BTreeMap<&'static str, SassFunction>) {
             v => Err(Error::badarg("color", &v)),
         }
     );
+    def!(f, hwb(hue, whiteness, blackness, alpha = b"1"), |s| {
+        let hue = s.get("hue")?;
+        let (hue, white, black) = match (hue, s.get("whiteness")?) {
+            (
+                Value::List(ref v, ListSeparator::Space, false),
+                Value::Null,
+            ) if v.len() == 3 => (v[0].clone(), v[1].clone(), v[2].clone()),
+            (hue, white) => (hue, white, s.get("blackness")?),
+        };
+        Ok(Value::hwba(
+            to_degrees(hue)?,
+            to_percentage(white, "whiteness")?,
+            to_percentage(black, "blackness")?,
+            to_rational(s.get("alpha")?)?,
+        ))
+    });
     def!(f, ie_hex_str(color), |s| match s.get("color")? {
         Value::Color(rgba, _) => {
             let (r, g, b, a) = rgba.to_bytes();
@@ -178,6 +251,47 @@ pub fn register(f: &mut BTreeMap<&'static str, SassFunction>) {
     });
 }
 
+pub fn create_module() -> Module {
+    let mut f = BTreeMap::new();
+    for &name in &[
+        "alpha",
+        "blue",
+        "complement",
+        "grayscale",
+        "green",
+        "hue",
+        "ie_hex_str",
+        "invert",
+        "lightness",
+        "opacity",
+        "red",
+        "saturation",
+    ] {
+        expose(&mut f, name, name);
+    }
+    expose(&mut f, "hwb", "hwb");
+    expose(&mut f, "adjust_color", "adjust");
+    expose(&mut f, "change_color", "change");
+    expose(&mut f, "scale_color", "scale");
+    colors_space::add_to_module(&mut f);
+
+    def!(f, whiteness(color), |s| match s.get("color")? {
+        Value::Color(ref rgba, _) => {
+            let (_h, w, _b, _a) = rgba.to_hwba();
+            Ok(Value::Numeric(Number::from(w * 100), Unit::Percent, true))
+        }
+        v => Err(Error::badarg("color", &v)),
+    });
+    def!(f, blackness(color), |s| match s.get("color")? {
+        Value::Color(ref rgba, _) => {
+            let (_h, _w, b, _a) = rgba.to_hwba();
+            Ok(Value::Numeric(Number::from(b * 100), Unit::Percent, true))
+        }
+        v => Err(Error::badarg("color", &v)),
+    });
+    f
+}
+
 fn to_rational(v: Value) -> Result<Rational, Error> {
     match v {
         Value::Numeric(v, ..) => Ok(v.value),
@@ -185,6 +299,59 @@ fn to_rational(v: Value) -> Result<Rational, Error> {
     }
 }
 
+/// Check if `$whiteness` or `$blackness` is given to adjust, change
+/// or scale a color.
+///
+/// Those can't be combined with rgb or hsl parameters.
+fn is_hwb(s: &dyn Scope) -> Result<bool, Error> {
+    if s.get("whiteness")?.is_null() && s.get("blackness")?.is_null() {
+        return Ok(false);
+    }
+    for &(space, names) in &[
+        ("RGB", &["red", "green", "blue"][..]),
+        ("HSL", &["saturation", "lightness"][..]),
+    ] {
+        for name in names {
+            if !s.get(name)?.is_null() {
+                return Err(Error::BadArguments(format!(
+                    "{} parameters may not be passed along with HWB \
+                     parameters.",
+                    space
+                )));
+            }
+        }
+    }
+    Ok(true)
+}
+
+/// Get a hue in degrees.
+///
+/// A number with an angle unit, like `0.5turn`, is converted to
+/// degrees.  Other numbers are taken as degrees.
+fn to_degrees(v: Value) -> Result<Rational, Error> {
+    match v {
+        Value::Numeric(v, unit, _) => Ok(match unit.scale_to(&Unit::Deg) {
+            Some(scale) => v.value * scale,
+            None => v.value,
+        }),
+        v => Err(Error::badarg("number", &v)),
+    }
+}
+
+/// Get a percentage argument as part (e.g. 50% => 0.5).
+///
+/// Unlike `to_rational_percent`, the unit is required.
+fn to_percentage(v: Value, name: &str) -> Result<Rational, Error> {
+    match v {
+        Value::Numeric(v, Unit::Percent, _) => Ok(v.value / 100),
+        v => Err(Error::BadArguments(format!(
+            "${}: Expected {} to have unit \"%\".",
+            name,
+            v.format(Default::default()),
+        ))),
+    }
+}
+
 /// Try to get percentage part (i.e. a number -1 .. 1) from a value.
 ///
 /// If the value is a percentage, get it as part (e.g. 50% => 0.5).
@@ -205,8 +372,83 @@ fn to_rational_percent(v: Value) -> Result<Rational, Error> {
 
 #[cfg(test)]
 mod test {
-    use crate::variablescope::test::do_evaluate;
+    use crate::variablescope::test::{
+        check_err, do_evaluate, do_evaluate_or_error,
+    };
 
+    #[test]
+    fn hwb() {
+        assert_eq!(do_evaluate(&[], b"color.hwb(210, 0%, 60%);"), "#003366")
+    }
+    #[test]
+    fn hwb_global() {
+        assert_eq!(do_evaluate(&[], b"hwb(210, 0%, 60%);"), "#003366")
+    }
+    #[test]
+    fn hwb_hue_unit() {
+        assert_eq!(do_evaluate(&[], b"hwb(0.5turn, 0%, 60%);"), "#006666")
+    }
+    #[test]
+    fn hwb_alpha() {
+        assert_eq!(
+            do_evaluate(&[], b"color.hwb(210, 0%, 60%, 0.5);"),
+            "rgba(0, 51, 102, 0.5)"
+        )
+    }
+    #[test]
+    fn hwb_space_list() {
+        assert_eq!(do_evaluate(&[], b"color.hwb(210 0% 60%);"), "#003366")
+    }
+    #[test]
+    fn hwb_unitless_whiteness() {
+        assert!(do_evaluate_or_error(&[], b"color.hwb(210, 0, 60%);").is_err())
+    }
+    #[test]
+    fn whiteness() {
+        assert_eq!(
+            do_evaluate(&[], b"color.whiteness(#e1d7d2);"),
+            "82.352941%"
+        )
+    }
+    #[test]
+    fn blackness() {
+        assert_eq!(do_evaluate(&[], b"color.blackness(#003366);"), "60%")
+    }
+    #[test]
+    fn adjust_whiteness() {
+        assert_eq!(
+            do_evaluate(&[], b"adjust-color(#003366, $whiteness: 20%);"),
+            "#334d66"
+        )
+    }
+    #[test]
+    fn change_blackness() {
+        assert_eq!(
+            do_evaluate(&[], b"color.change(#003366, $blackness: 20%);"),
+            "#0066cc"
+        )
+    }
+    #[test]
+    fn adjust_whiteness_and_red() {
+        check_err(
+            "adjust-color(#003366, $whiteness: 20%, $red: 10);",
+            "RGB parameters may not be passed along with HWB parameters.",
+        )
+    }
+    #[test]
+    fn change_blackness_and_saturation() {
+        check_err(
+            "color.change(#003366, $blackness: 20%, $saturation: 10%);",
+            "HSL parameters may not be passed along with HWB parameters.",
+        )
+    }
+    #[test]
+    fn scale_whiteness() {
+        assert_eq!(
+            do_evaluate(&[], b"scale-color(#003366, $blackness: -50%);"),
+            "#0059b3"
+        )
+    }
     #[test]
     fn ie_hex_str_a() {
         assert_eq!(do_evaluate(&[], b"ie-hex-str(#abc);"), "#FFAABBCC")
@@ -1,11 +1,32 @@
 use super::{make_call, Error, SassFunction};
 use crate::css::{CallArgs, Value};
-use crate::value::{Number, Quotes, Unit};
+use crate::value::{Number, Quotes, Rgba, Unit};
 use crate::variablescope::Scope;
 use num_rational::Rational;
 use num_traits::{One, Zero};
 use std::collections::BTreeMap;
 
+/// Mix two colors in the legacy rgb way.
+///
+/// The weight `p` is the part of `a` in the result, 0 .. 1.
+pub fn mix(a: &Rgba, b: &Rgba, p: Rational) -> Value {
+    let one = Rational::one();
+    let w = p * 2 - one;
+    let wa = a.alpha - b.alpha;
+
+    let divis = w * wa + 1;
+    let w1 = (if divis.is_zero() { w } else { (w + wa) / divis } + 1) / 2;
+    let w2 = one - w1;
+
+    let m_c = |c1, c2| w1 * c1 + w2 * c2;
+    Value::rgba(
+        m_c(a.red, b.red),
+        m_c(a.green, b.green),
+        m_c(a.blue, b.blue),
+        a.alpha * p + b.alpha * (one - p),
+    )
+}
+
 fn do_rgba(fn_name: &str, s: &dyn Scope) -> Result<Value, Error> {
     let a = s.get("alpha")?;
     let red = s.get("red")?;
@@ -14,7 +35,7 @@ fn do_rgba(fn_name: &str, s: &dyn Scope) -> Result<Value, Error> {
         let a = if a.is_null() { s.get("green")? } else { a };
         match a {
             Value::Numeric(a, ..) => {
-                Ok(Value::rgba(rgba.red, rgba.green, rgba.blue, a.value))
+                Ok(Value::Color(rgba.with_alpha(a.value), None))
             }
             _ => Ok(make_call(
                 fn_name,
@@ -108,22 +129,7 @@ pub fn register(f: &mut BTreeMap<&'static str, SassFunction>) {
             } else {
                 w.value
             };
-            let one = Rational::one();
-            let w = p * 2 - one;
-            let wa = a.alpha - b.alpha;
-
-            let divis = w * wa + 1;
-            let w1 =
-                (if divis.is_zero() { w } else { (w + wa) / divis } + 1) / 2;
-            let w2 = one - w1;
-
-            let m_c = |c1, c2| w1 * c1 + w2 * c2;
-            Ok(Value::rgba(
-                m_c(a.red, b.red),
-                m_c(a.green, b.green),
-                m_c(a.blue, b.blue),
-                a.alpha * p + b.alpha * (one - p),
-            ))
+            Ok(mix(&a, &b, p))
         }
         (color1, color2, weight) => Err(Error::badargs(
             &["color", "color", "number"],
@@ -0,0 +1,441 @@
+use super::colors_rgb::mix;
+use super::{make_call, Error, Module, SassFunction};
+use crate::css::Value;
+use crate::value::{
+    to_f64, ColorSpace, ListSeparator, Number, Quotes, Rgba, SpaceColor, Unit,
+};
+use num_rational::Rational;
+use num_traits::One;
+use std::collections::BTreeMap;
+
+pub fn register(f: &mut BTreeMap<&'static str, SassFunction>) {
+    def!(f, lab(channels), |s| {
+        space_color(ColorSpace::Lab, "lab", s.get("channels")?)
+    });
+    def!(f, lch(channels), |s| {
+        space_color(ColorSpace::Lch, "lch", s.get("channels")?)
+    });
+    def!(f, oklab(channels), |s| {
+        space_color(ColorSpace::Oklab, "oklab", s.get("channels")?)
+    });
+    def!(f, oklch(channels), |s| {
+        space_color(ColorSpace::Oklch, "oklch", s.get("channels")?)
+    });
+    def!(f, color(description), |s| {
+        let description = s.get("description")?;
+        let (channels, alpha) = split_alpha(description.clone());
+        match channels {
+            Value::List(mut v, ListSeparator::Space, false)
+                if v.len() == 4 =>
+            {
+                let space = match v.remove(0) {
+                    Value::Literal(ref name, Quotes::None) => {
+                        ColorSpace::from_name(name)
+                    }
+                    _ => None,
+                };
+                match space {
+                    Some(space) if space.is_bounded() => {
+                        let channels =
+                            Value::List(v, ListSeparator::Space, false);
+                        let channels = match alpha {
+                            Some(alpha) => Value::List(
+                                vec![channels, alpha],
+                                ListSeparator::Slash,
+                                false,
+                            ),
+                            None => channels,
+                        };
+                        space_color(space, "color", channels)
+                    }
+                    _ => Ok(make_call("color", vec![description])),
+                }
+            }
+            _ => Ok(make_call("color", vec![description])),
+        }
+    });
+}
+
+/// Add the color space functions of the `sass:color` module.
+pub fn add_to_module(f: &mut Module) {
+    def!(f, space(color), |s| {
+        let color = get_color(s.get("color")?)?;
+        Ok(Value::Literal(color.space.name().into(), Quotes::None))
+    });
+    def!(f, to_space(color, space), |s| {
+        let color = get_color(s.get("color")?)?;
+        let space = get_space(s.get("space")?)?;
+        Ok(color_value(color.to_space(space)))
+    });
+    def!(f, channel(color, channel, space), |s| {
+        let color = get_color(s.get("color")?)?;
+        let color = match s.get("space")? {
+            Value::Null => color,
+            space => color.to_space(get_space(space)?),
+        };
+        let name = match s.get("channel")? {
+            Value::Literal(name, _) => name,
+            v => return Err(Error::badarg("string", &v)),
+        };
+        if name == "alpha" {
+            return Ok(Value::scalar(color.alpha));
+        }
+        let space = color.space;
+        match space.channel_names().iter().position(|n| *n == name) {
+            Some(0)
+                if space == ColorSpace::Oklab
+                    || space == ColorSpace::Oklch =>
+            {
+                Ok(number(color.channels[0] * 100, Unit::Percent))
+            }
+            Some(0)
+                if space == ColorSpace::Lab || space == ColorSpace::Lch =>
+            {
+                Ok(number(color.channels[0], Unit::Percent))
+            }
+            Some(2) if space.is_polar() => {
+                Ok(number(color.channels[2], Unit::Deg))
+            }
+            Some(i) => Ok(number(color.channels[i], Unit::None)),
+            None => Err(Error::BadArguments(format!(
+                "$channel: Color {} has no channel named {}.",
+                space, name
+            ))),
+        }
+    });
+    def!(f, is_in_gamut(color, space), |s| {
+        let color = get_color(s.get("color")?)?;
+        let color = match s.get("space")? {
+            Value::Null => color,
+            space => color.to_space(get_space(space)?),
+        };
+        Ok(Value::bool(color.is_in_gamut()))
+    });
+    def!(f, to_gamut(color, space, method), |s| {
+        let color = get_color(s.get("color")?)?;
+        let space = match s.get("space")? {
+            Value::Null => color.space,
+            space => get_space(space)?,
+        };
+        let in_space = color.to_space(space);
+        let mapped = match s.get("method")? {
+            Value::Null => in_space.to_gamut(),
+            Value::Literal(ref m, _) if m == "local-minde" => {
+                in_space.to_gamut()
+            }
+            Value::Literal(ref m, _) if m == "clip" => in_space.clip(),
+            v => {
+                return Err(Error::BadArguments(format!(
+                    "$method: Unknown gamut map method {}.",
+                    v.format(Default::default())
+                )))
+            }
+        };
+        Ok(color_value(mapped.to_space(color.space)))
+    });
+    def!(f, mix(color1, color2, weight = b"50%", method), |s| match (
+        s.get("color1")?,
+        s.get("color2")?,
+        s.get("weight")?
+    ) {
+        (
+            Value::Color(a, _),
+            Value::Color(b, _),
+            Value::Numeric(w, wu, _),
+        ) => {
+            let p = if wu == Unit::Percent {
+                w.value / 100
+            } else {
+                w.value
+            };
+            match s.get("method")? {
+                Value::Null => Ok(mix(&a, &b, p)),
+                method => {
+                    let space = get_space(method)?;
+                    let (a, b) = (a.to_space_color(), b.to_space_color());
+                    let mixed = a.mix(&b, to_f64(p), space);
+                    Ok(color_value(mixed.to_space(a.space)))
+                }
+            }
+        }
+        (color1, color2, weight) => Err(Error::badargs(
+            &["color", "color", "number"],
+            &[&color1, &color2, &weight],
+        )),
+    });
+}
+
+/// Create a color in a given space from a list of channels and an
+/// optional alpha.
+///
+/// If any channel is not a number (e.g. a `var()`), the function call
+/// is kept as is.
+fn space_color(
+    space: ColorSpace,
+    name: &str,
+    args: Value,
+) -> Result<Value, Error> {
+    let (channels, alpha) = split_alpha(args.clone());
+    let keep = || Ok(make_call(name, vec![args.clone()]));
+    let channels = match channels {
+        Value::List(ref v, ListSeparator::Space, false) if v.len() == 3 => v,
+        _ => return keep(),
+    };
+    let refs = space.percent_reference();
+    let mut values = [0.; 3];
+    for (i, channel) in channels.iter().enumerate() {
+        values[i] = match *channel {
+            Value::Numeric(ref v, Unit::Percent, _) => {
+                to_f64(v.value) / 100. * refs[i]
+            }
+            Value::Numeric(ref v, Unit::None, _) => to_f64(v.value),
+            Value::Numeric(ref v, ref unit, _)
+                if space.is_polar() && i == 2 =>
+            {
+                match unit.scale_to(&Unit::Deg) {
+                    Some(scale) => to_f64(v.value * scale),
+                    None => return Err(Error::badarg("angle", channel)),
+                }
+            }
+            Value::Numeric(..) => {
+                return Err(Error::badarg("number", channel))
+            }
+            _ => return keep(),
+        };
+    }
+    let alpha = match alpha {
+        None => Rational::one(),
+        Some(Value::Numeric(v, Unit::Percent, _)) => v.value / 100,
+        Some(Value::Numeric(v, Unit::None, _)) => v.value,
+        Some(Value::Numeric(..)) => {
+            return Err(Error::S(
+                "Alpha must be a number or percentage".into(),
+            ))
+        }
+        Some(_) => return keep(),
+    };
+    Ok(color_value(SpaceColor::new(space, values, alpha)))
+}
+
+/// Split a channels argument like `60% 0.1 250 / 0.5` into the
+/// channels and the alpha.
+fn split_alpha(args: Value) -> (Value, Option<Value>) {
+    match args {
+        Value::List(mut v, ListSeparator::Slash, false) if v.len() == 2 => {
+            let alpha = v.pop();
+            (v.pop().unwrap(), alpha)
+        }
+        Value::List(mut v, ListSeparator::Space, false) => match v.pop() {
+            Some(Value::List(mut last, ListSeparator::Slash, false))
+                if last.len() == 2 =>
+            {
+                let alpha = last.pop();
+                v.extend(last);
+                (Value::List(v, ListSeparator::Space, false), alpha)
+            }
+            Some(last) => {
+                v.push(last);
+                (Value::List(v, ListSeparator::Space, false), None)
+            }
+            None => (Value::List(v, ListSeparator::Space, false), None),
+        },
+        args => (args, None),
+    }
+}
+
+fn color_value(color: SpaceColor) -> Value {
+    Value::Color(Rgba::from_space(color), None)
+}
+
+pub fn get_color(v: Value) -> Result<SpaceColor, Error> {
+    match v {
+        Value::Color(rgba, _) => Ok(rgba.to_space_color()),
+        v => Err(Error::badarg("color", &v)),
+    }
+}
+
+fn get_space(v: Value) -> Result<ColorSpace, Error> {
+    match v {
+        Value::Literal(ref name, _) => ColorSpace::from_name(name)
+            .ok_or_else(|| {
+                Error::BadArguments(format!(
+                    "$space: Unknown color space \"{}\".",
+                    name
+                ))
+            }),
+        v => Err(Error::badarg("string", &v)),
+    }
+}
+
+pub fn number(v: Rational, unit: Unit) -> Value {
+    Value::Numeric(Number::from(v), unit, true)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::variablescope::test::do_evaluate;
+
+    #[test]
+    fn oklch_in_srgb_gamut() {
+        assert_eq!(do_evaluate(&[], b"oklch(62.796% 0.25768 29.234);"), "red")
+    }
+    #[test]
+    fn space_color_not_equal_to_rgb() {
+        assert_eq!(do_evaluate(&[], b"color(srgb 1 0 0) == red;"), "false")
+    }
+    #[test]
+    fn equal_out_of_gamut() {
+        assert_eq!(
+            do_evaluate(&[], b"oklch(70% 0.3 150) == oklch(70% 0.3 150);"),
+            "true"
+        )
+    }
+    #[test]
+    fn unequal_out_of_gamut() {
+        // Out of gamut colors differ even if they clip to the same rgb.
+        assert_eq!(
+            do_evaluate(&[], b"oklch(70% 0.3 150) == oklch(70% 0.31 150);"),
+            "false"
+        )
+    }
+    #[test]
+    fn space_color_as_map_key() {
+        assert_eq!(
+            do_evaluate(
+                &[],
+                b"map-get((color(srgb 1 0 0): a), color(srgb 1 0 0));"
+            ),
+            "a"
+        )
+    }
+    #[test]
+    fn rgba_keeps_space() {
+        assert_eq!(
+            do_evaluate(&[], b"color.space(rgba(oklch(70% 0.3 150), 0.5));"),
+            "oklch"
+        )
+    }
+    #[test]
+    fn transparentize_keeps_space() {
+        assert_eq!(
+            do_evaluate(&[], b"transparentize(oklch(70% 0.3 150), 0.5);"),
+            "oklch(70% 0.3 150deg / 0.5)"
+        )
+    }
+    #[test]
+    fn change_alpha_keeps_space() {
+        assert_eq!(
+            do_evaluate(
+                &[],
+                b"color.change(oklch(70% 0.3 150), $alpha: 0.5);"
+            ),
+            "oklch(70% 0.3 150deg / 0.5)"
+        )
+    }
+    #[test]
+    fn oklch_out_of_srgb_gamut() {
+        assert_eq!(
+            do_evaluate(&[], b"oklch(70% 0.3 150);"),
+            "oklch(70% 0.3 150deg)"
+        )
+    }
+    #[test]
+    fn oklch_alpha() {
+        assert_eq!(
+            do_evaluate(&[], b"oklch(70% 0.3 150 / 0.5);"),
+            "oklch(70% 0.3 150deg / 0.5)"
+        )
+    }
+    #[test]
+    fn oklch_var() {
+        assert_eq!(
+            do_evaluate(&[], b"oklch(var(--l) 0.1 150);"),
+            "oklch(var(--l) 0.1 150)"
+        )
+    }
+    #[test]
+    fn display_p3() {
+        assert_eq!(
+            do_evaluate(&[], b"color(display-p3 1 0 0);"),
+            "color(display-p3 1 0 0)"
+        )
+    }
+    #[test]
+    fn display_p3_in_srgb_gamut() {
+        assert_eq!(
+            do_evaluate(&[], b"color(display-p3 0.5 0.5 0.5);"),
+            "gray"
+        )
+    }
+    #[test]
+    fn lab() {
+        assert_eq!(do_evaluate(&[], b"lab(50% 0 0);"), "#777777")
+    }
+    #[test]
+    fn to_space() {
+        assert_eq!(
+            do_evaluate(
+                &[],
+                b"color.channel(color.to-space(red, oklch), hue);"
+            ),
+            "29.23388deg"
+        )
+    }
+    #[test]
+    fn channel_in_space() {
+        assert_eq!(
+            do_evaluate(&[], b"color.channel(red, lightness, $space: lab);"),
+            "54.290543%"
+        )
+    }
+    #[test]
+    fn space() {
+        assert_eq!(
+            do_evaluate(&[], b"color.space(oklch(70% 0.3 150));"),
+            "oklch"
+        )
+    }
+    #[test]
+    fn is_in_gamut() {
+        assert_eq!(
+            do_evaluate(
+                &[],
+                b"color.is-in-gamut(color(display-p3 1 0 0), $space: srgb);"
+            ),
+            "false"
+        )
+    }
+    #[test]
+    fn to_gamut() {
+        assert_eq!(
+            do_evaluate(
+                &[],
+                b"color.is-in-gamut(color.to-gamut(oklch(70% 0.3 150), \
+                  $space: srgb), $space: srgb);"
+            ),
+            "true"
+        )
+    }
+    #[test]
+    fn to_gamut_clip() {
+        assert_eq!(
+            do_evaluate(
+                &[],
+                b"color.to-space(color.to-gamut(color(display-p3 1 0 0), \
+                  $space: srgb, $method: clip), rgb);"
+            ),
+            "red"
+        )
+    }
+    #[test]
+    fn mix_oklch() {
+        assert_eq!(
+            do_evaluate(&[], b"color.mix(red, blue, $method: oklch);"),
+            "#ba00c2"
+        )
+    }
+    #[test]
+    fn mix_legacy() {
+        assert_eq!(do_evaluate(&[], b"color.mix(red, blue);"), "purple")
+    }
+}
@@ -1,5 +1,8 @@
-use super::{Error, SassFunction};
-use crate::css::{CallArgs, Value};
+use super::{
+    expose, get_builtin_module, get_builtin_module_variables, Error, Module,
+    SassFunction,
+};
+use crate::css::{CalcArg, CallArgs, Value};
 use crate::value::{ListSeparator, Quotes, Unit};
 use crate::variablescope::Scope;
 use std::collections::BTreeMap;
@@ -21,6 +24,10 @@ static IMPLEMENTED_FEATURES: &[&str] = &[
 ];
 
 pub fn register(f: &mut BTreeMap<&'static str, SassFunction>) {
+    def!(f, keywords(args), |s| match s.get("args")? {
+        Value::ArgList(args) => Ok(Value::Map(args.keywords())),
+        v => Err(Error::badarg("arglist", &v)),
+    });
     def!(f, feature_exists(feature), |s| match &s.get("feature")? {
         &Value::Literal(ref v, _) => {
             Ok(Value::bool(IMPLEMENTED_FEATURES.iter().any(|s| s == v)))
@@ -74,7 +81,10 @@ pub fn register(f: &mut BTreeMap<&'static str, SassFunction>) {
         ))
     });
     def!(f, inspect(value), |s| Ok(Value::Literal(
-        match s.get("value")? {
+        match s.get("value").map(|v| match v {
+            Value::ArgList(args) => args.to_list(),
+            v => v,
+        })? {
             Value::Null => "null".to_string(),
             Value::List(ref v, ref sep, brackets) => {
                 // TODO Try to unify this with the Display formatting?
@@ -103,6 +113,7 @@ pub fn register(f: &mut BTreeMap<&'static str, SassFunction>) {
                     t.join(match *sep {
                         ListSeparator::Comma => ", ",
                         ListSeparator::Space => " ",
+                        ListSeparator::Slash => "/",
                     })
                 };
                 if brackets {
@@ -135,12 +146,11 @@ pub fn register(f: &mut BTreeMap<&'static str, SassFunction>) {
             (
                 &Value::Numeric(_, ref u1, ..),
                 &Value::Numeric(_, ref u2, ..),
-            ) => {
-                // TODO e.g. cm and mm are comparable too!
-                // Simply comparig the dimension is not enough, though,
-                // as e.g em and vh is not comparable.
-                Ok(Value::bool(u1 == u2))
-            }
+            ) => Ok(Value::bool(
+                *u1 == Unit::None
+                    || *u2 == Unit::None
+                    || u1.scale_to(u2).is_some(),
+            )),
             (v1, v2) => Err(Error::badargs(&["number", "number"], &[v1, v2])),
         }
     });
@@ -167,6 +177,104 @@ pub fn register(f: &mut BTreeMap<&'static str, SassFunction>) {
     });
 }
 
+/// Create the `sass:meta` module.
+///
+/// The `load-css` mixin is handled by the css output, see
+/// `output::style`.
+pub fn create_module() -> Module {
+    let mut f = BTreeMap::new();
+    for &name in &[
+        "call",
+        "content_exists",
+        "feature_exists",
+        "function_exists",
+        "get_function",
+        "global_variable_exists",
+        "inspect",
+        "keywords",
+        "mixin_exists",
+        "type_of",
+        "variable_exists",
+    ] {
+        expose(&mut f, name, name);
+    }
+
+    def!(f, module_variables(module), |s| {
+        let name = module_name(s.get("module")?)?;
+        Ok(Value::Map(
+            get_builtin_module_variables(&name)
+                .into_iter()
+                .map(|(name, value)| (member_name(name), value))
+                .collect(),
+        ))
+    });
+    def!(f, module_functions(module), |s| {
+        let name = module_name(s.get("module")?)?;
+        Ok(Value::Map(
+            get_builtin_module(&name)
+                .into_iter()
+                .flatten()
+                .map(|(local, function)| {
+                    let qualified = format!("{}.{}", name, local);
+                    (
+                        member_name(local),
+                        Value::Function(qualified, Some(function.clone())),
+                    )
+                })
+                .collect(),
+        ))
+    });
+    def!(f, calc_name(calc), |s| match s.get("calc")? {
+        Value::Calc(name, _) => {
+            Ok(Value::Literal(name.to_string(), Quotes::Double))
+        }
+        v => Err(Error::badarg("calculation", &v)),
+    });
+    def!(f, calc_args(calc), |s| match s.get("calc")? {
+        Value::Calc(_, args) => Ok(Value::List(
+            args.into_iter()
+                .map(|arg| match arg {
+                    arg @ Value::Numeric(..) => arg,
+                    arg => Value::Literal(
+                        CalcArg(&arg).to_string(),
+                        Quotes::None,
+                    ),
+                })
+                .collect(),
+            ListSeparator::Comma,
+            false,
+        )),
+        v => Err(Error::badarg("calculation", &v)),
+    });
+    f
+}
+
+/// Get the name of a module that is used, as given to the
+/// `module-variables` and `module-functions` functions.
+///
+/// Only the builtin modules are supported so far.  They are used by
+/// their default namespace, e.g. `math` for `sass:math`.
+fn module_name(v: Value) -> Result<String, Error> {
+    match v {
+        Value::Literal(name, _) => {
+            if get_builtin_module(&name).is_some() {
+                Ok(name)
+            } else {
+                Err(Error::BadArguments(format!(
+                    "$module: There is no module with namespace \"{}\".",
+                    name
+                )))
+            }
+        }
+        v => Err(Error::badarg("string", &v)),
+    }
+}
+
+/// A module member name as a map key, e.g. `"to-upper-case"`.
+fn member_name(local: &str) -> Value {
+    Value::Literal(local.replace('_', "-"), Quotes::Double)
+}
+
 #[cfg(test)]
 mod test {
     use super::super::super::variablescope::test::do_evaluate;
@@ -220,6 +328,48 @@ mod test {
         assert_eq!("false", do_evaluate(&[], b"unitless(100px);"))
     }
 
+    #[test]
+    fn module_variables() {
+        assert_eq!(
+            do_evaluate(&[], b"map-keys(meta.module-variables(math));"),
+            "\"e\", \"pi\""
+        )
+    }
+    #[test]
+    fn module_functions() {
+        assert_eq!(
+            do_evaluate(
+                &[],
+                b"meta.call(map-get(meta.module-functions(math), abs), -2);"
+            ),
+            "2"
+        )
+    }
+    #[test]
+    fn module_functions_dashed_names() {
+        assert_eq!(
+            do_evaluate(
+                &[],
+                b"map-has-key(meta.module-functions(string), \"to-upper-case\");"
+            ),
+            "true"
+        )
+    }
+    #[test]
+    fn calc_name() {
+        assert_eq!(
+            do_evaluate(&[], b"meta.calc-name(calc(1px + 10%));"),
+            "\"calc\""
+        )
+    }
+    #[test]
+    fn calc_args() {
+        assert_eq!(
+            do_evaluate(&[], b"meta.calc-args(clamp(1px, 2vw + 1rem, 3px));"),
+            "1px, 2vw + 1rem, 3px"
+        )
+    }
+
     /// From `sass-spec/spec/types-4.0`
     mod types_4_0 {
         use super::do_evaluate;
@@ -1,11 +1,14 @@
-use super::{Error, SassFunction};
-use crate::css::Value;
-use crate::value::{ListSeparator, Quotes};
+use super::{expose, Error, Module, SassFunction};
+use crate::css::{ArgList, Value};
+use crate::value::{equal_values, ListSeparator, Quotes};
 use std::collections::BTreeMap;
 
 pub fn register(f: &mut BTreeMap<&'static str, SassFunction>) {
     def!(f, length(list), |s| match s.get("list")? {
-        Value::List(v, _, _) => Ok(Value::scalar(v.len() as isize)),
+        Value::List(v, _, _)
+        | Value::ArgList(ArgList { positional: v, .. }) => {
+            Ok(Value::scalar(v.len() as isize))
+        }
         Value::Map(m) => Ok(Value::scalar(m.len() as isize)),
         // A null value is considered eqivalent to an empty list
         Value::Null => Ok(Value::scalar(0)),
@@ -15,9 +18,10 @@ pub fn register(f: &mut BTreeMap<&'static str, SassFunction>) {
     def!(f, nth(list, n), |s| {
         let n = s.get("n")?.integer_value()?;
         match s.get("list")? {
-            Value::List(list, _, _) => {
-                Ok(list[list_index(n, &list)?].clone())
-            }
+            Value::List(list, _, _)
+            | Value::ArgList(ArgList {
+                positional: list, ..
+            }) => Ok(list[list_index(n, &list)?].clone()),
             Value::Map(map) => {
                 let n = rust_index(n, map.len())?;
                 if let Some(&(ref k, ref v)) = map.get_item(n) {
@@ -30,7 +34,10 @@ pub fn register(f: &mut BTreeMap<&'static str, SassFunction>) {
                     Ok(Value::Null)
                 }
             }
-            v => Ok(if n == 1 { v } else { Value::Null }),
+            v => {
+                rust_index(n, 1)?;
+                Ok(v)
+            }
         }
     });
     def!(f, set_nth(list, n, value), |s| {
@@ -46,29 +53,10 @@ pub fn register(f: &mut BTreeMap<&'static str, SassFunction>) {
         |s| {
             let (mut list1, sep1, bra1) = get_list(s.get("list1")?);
             let (mut list2, sep2, _bra2) = get_list(s.get("list2")?);
-            let separator = match s.get("separator")? {
-                Value::Literal(ref sep, _)
-                    if sep.to_lowercase() == "comma" =>
-                {
-                    ListSeparator::Comma
-                }
-                Value::Literal(ref sep, _)
-                    if sep.to_lowercase() == "space" =>
-                {
-                    ListSeparator::Space
-                }
-                Value::Literal(ref sep, _)
-                    if sep.to_lowercase() == "auto" =>
-                {
-                    sep1.or(sep2).unwrap_or(ListSeparator::Space)
-                }
-                ref other => {
-                    return Err(Error::badarg(
-                        "'comma', 'space', or 'auto'",
-                        other,
-                    ));
-                }
-            };
+            let separator = get_separator(s.get("separator")?)?
+                .or(sep1)
+                .or(sep2)
+                .unwrap_or(ListSeparator::Space);
             list1.append(&mut list2);
             let bra = match s.get("bracketed")? {
                 Value::Literal(ref s, _) if s == "auto" => bra1,
@@ -77,22 +65,16 @@ pub fn register(f: &mut BTreeMap<&'static str, SassFunction>) {
             Ok(Value::List(list1, separator, bra))
         }
     );
-    def!(f, append(list, val, separator), |s| {
+    def!(f, append(list, val, separator = b"auto"), |s| {
         let (mut list, sep, bra) = get_list(s.get("list")?);
-        let sep = match (s.get("separator")?, sep) {
-            (Value::Literal(ref s, _), _) if s == "comma" => {
-                ListSeparator::Comma
-            }
-            (Value::Literal(ref s, _), _) if s == "space" => {
-                ListSeparator::Space
-            }
-            (_, s) => s.unwrap_or(ListSeparator::Space),
-        };
+        let sep = get_separator(s.get("separator")?)?
+            .or(sep)
+            .unwrap_or(ListSeparator::Space);
         list.push(s.get("val")?);
         Ok(Value::List(list, sep, bra))
     });
     def_va!(f, zip(lists), |s| match s.get("lists")? {
-        Value::List(v, _, _) => {
+        Value::ArgList(ArgList { positional: v, .. }) => {
             let lists =
                 v.into_iter().map(|v| v.iter_items()).collect::<Vec<_>>();
             let len = lists.iter().map(|v| v.len()).min().unwrap_or(0);
@@ -107,10 +89,11 @@ pub fn register(f: &mut BTreeMap<&'static str, SassFunction>) {
         v => Err(Error::badarg("list", &v)),
     });
     def!(f, index(list, value), |s| match s.get("list")? {
-        Value::List(v, _, _) => {
+        Value::List(v, _, _)
+        | Value::ArgList(ArgList { positional: v, .. }) => {
             let value = s.get("value")?;
             for (i, v) in v.iter().enumerate() {
-                if v == &value {
+                if equal_values(v, &value) {
                     return Ok(Value::scalar(i as isize + 1));
                 }
             }
@@ -119,7 +102,7 @@ pub fn register(f: &mut BTreeMap<&'static str, SassFunction>) {
         Value::Map(map) => match s.get("value")? {
             Value::List(ref l, ListSeparator::Space, _) if l.len() == 2 => {
                 for (i, &(ref k, ref v)) in map.iter().enumerate() {
-                    if *k == l[0] && *v == l[1] {
+                    if equal_values(k, &l[0]) && equal_values(v, &l[1]) {
                         return Ok(Value::scalar(i as isize + 1));
                     }
                 }
@@ -128,7 +111,7 @@ pub fn register(f: &mut BTreeMap<&'static str, SassFunction>) {
             _ => Ok(Value::Null),
         },
         v => {
-            if v == s.get("value")? {
+            if equal_values(&v, &s.get("value")?) {
                 Ok(Value::scalar(1))
             } else {
                 Ok(Value::Null)
@@ -138,6 +121,8 @@ pub fn register(f: &mut BTreeMap<&'static str, SassFunction>) {
     def!(f, list_separator(list), |s| Ok(Value::Literal(
         match s.get("list")? {
             Value::List(_, ListSeparator::Comma, _) => "comma",
+            Value::ArgList(_) => "comma",
+            Value::List(_, ListSeparator::Slash, _) => "slash",
             Value::Map(_) => "comma",
             _ => "space",
         }
@@ -150,16 +135,50 @@ pub fn register(f: &mut BTreeMap<&'static str, SassFunction>) {
     }));
 }
 
+pub fn create_module() -> Module {
+    let mut f = BTreeMap::new();
+    for &name in &[
+        "append",
+        "index",
+        "is_bracketed",
+        "join",
+        "length",
+        "nth",
+        "set_nth",
+        "zip",
+    ] {
+        expose(&mut f, name, name);
+    }
+    expose(&mut f, "list_separator", "separator");
+
+    def_va!(f, slash(elements), |s| match s.get("elements")? {
+        Value::ArgList(ref args) if args.positional.len() < 2 => {
+            Err(Error::S("At least two elements are required.".into()))
+        }
+        Value::ArgList(ArgList { positional: v, .. }) =>
+            Ok(Value::List(v, ListSeparator::Slash, false)),
+        v => Err(Error::badarg("list", &v)),
+    });
+    f
+}
+
+/// Get the items, separator and bracketedness of a list.
+///
+/// The separator is None if it is undecided, i.e. for empty and
+/// single-item space-separated lists.
 fn get_list(value: Value) -> (Vec<Value>, Option<ListSeparator>, bool) {
     match value {
         Value::List(v, s, bra) => {
-            let sep = if v.is_empty() && s == ListSeparator::Space {
+            let sep = if v.len() < 2 && s == ListSeparator::Space {
                 None
             } else {
                 Some(s)
             };
             (v, sep, bra)
         }
+        Value::ArgList(args) => {
+            (args.positional, Some(ListSeparator::Comma), false)
+        }
         Value::Map(map) => (
             map.iter()
                 .map(|&(ref k, ref v)| {
@@ -177,6 +196,24 @@ fn get_list(value: Value) -> (Vec<Value>, Option<ListSeparator>, bool) {
     }
 }
 
+/// Get a separator argument, None for `auto`.
+fn get_separator(sep: Value) -> Result<Option<ListSeparator>, Error> {
+    match sep {
+        Value::Literal(ref s, _) => match s.to_lowercase().as_ref() {
+            "comma" => Ok(Some(ListSeparator::Comma)),
+            "space" => Ok(Some(ListSeparator::Space)),
+            "slash" => Ok(Some(ListSeparator::Slash)),
+            "auto" => Ok(None),
+            _ => Err(Error::BadArguments(
+                "$separator: Must be \"space\", \"comma\", \"slash\", \
+                 or \"auto\"."
+                    .into(),
+            )),
+        },
+        ref other => Err(Error::badarg("string", other)),
+    }
+}
+
 fn list_index(n: isize, list: &[Value]) -> Result<usize, Error> {
     let len = list.len();
     rust_index(n, len)
@@ -187,15 +224,20 @@ fn rust_index(n: isize, len: usize) -> Result<usize, Error> {
         Ok((n - 1) as usize)
     } else if n < 0 && n >= -(len as isize) {
         Ok((len as isize + n) as usize)
+    } else if n == 0 {
+        Err(Error::BadArguments("$n: List index may not be 0.".into()))
     } else {
-        let msg =
-            format!("Expected index for list of length {}, got {}", len, n);
-        Err(Error::BadArguments(msg))
+        Err(Error::BadArguments(format!(
+            "$n: Invalid index {} for a list with {} elements.",
+            n, len
+        )))
     }
 }
 
 #[cfg(test)]
 mod test {
+    use crate::variablescope::test::check_err;
+
     // Append fuction tests from
     // http://sass-lang.com/documentation/Sass/Script/Functions.html
     #[test]
@@ -274,11 +316,75 @@ mod test {
         }
     }
 
+    #[test]
+    fn append_bracketed() {
+        check_val("append([a b], c);", "[a b c]")
+    }
+    #[test]
+    fn append_single_bracketed_comma() {
+        check_val("append([a], b, comma);", "[a, b]")
+    }
+    #[test]
+    fn join_single_takes_second_separator() {
+        check_val("join([a], (b, c));", "[a, b, c]")
+    }
+    #[test]
+    fn index_sass_equality() {
+        check_val("index(1in 2in, 192px);", "2")
+    }
+    #[test]
+    fn nth_negative() {
+        check_val("nth([a b c], -1);", "c")
+    }
+    #[test]
+    fn set_nth_bracketed() {
+        check_val("set-nth([a b c], 2, x);", "[a x c]")
+    }
+    #[test]
+    fn nth_zero() {
+        check_err("nth(a b, 0);", "$n: List index may not be 0.")
+    }
+    #[test]
+    fn nth_too_high() {
+        check_err(
+            "nth(a b, 3);",
+            "$n: Invalid index 3 for a list with 2 elements.",
+        )
+    }
+    #[test]
+    fn append_bad_separator() {
+        check_err(
+            "append(a, b, $separator: foo);",
+            "$separator: Must be \"space\", \"comma\", \"slash\", \
+             or \"auto\".",
+        )
+    }
+
     #[test]
     fn is_bracketed() {
         check_val("is_bracketed([foo]);", "true");
     }
 
+    #[test]
+    fn append_slash() {
+        check_val("append(a, b, $separator: slash);", "a/b")
+    }
+
+    #[test]
+    fn list_slash() {
+        check_val("list.slash(1px, 2px, 3px);", "1px/2px/3px")
+    }
+
+    #[test]
+    fn separator_slash() {
+        check_val("list-separator(list.slash(a, b));", "slash")
+    }
+
+    #[test]
+    fn join_slash() {
+        check_val("join(a b, c, $separator: slash);", "a/b/c")
+    }
+
     #[test]
     fn zip() {
         check_val(
@@ -1,27 +1,48 @@
-use super::{Error, SassFunction};
+use super::{expose, Error, Module, SassFunction};
 use crate::css::Value;
 use crate::ordermap::OrderMap;
 use crate::value::ListSeparator;
 use std::collections::BTreeMap;
 
 pub fn register(f: &mut BTreeMap<&'static str, SassFunction>) {
-    def!(f, map_get(map, key), |s| Ok(get_map(s.get("map")?)?
-        .get(&s.get("key")?)
-        .cloned()
-        .unwrap_or(Value::Null)));
-    def!(f, map_merge(map1, map2), |s| {
-        let mut map1 = get_map(s.get("map1")?)?;
-        let map2 = get_map(s.get("map2")?)?;
-        for (key, value) in map2 {
-            map1.insert(key, value);
+    def_va!(f, map_get(map, key, keys), |s| {
+        let mut value = Value::Map(get_map(s.get("map")?)?);
+        for key in key_path(s.get("key")?, s.get("keys")?) {
+            value = match value {
+                Value::Map(map) => {
+                    map.get(&key).cloned().unwrap_or(Value::Null)
+                }
+                _ => return Ok(Value::Null),
+            };
         }
-        Ok(Value::Map(map1))
+        Ok(value)
+    });
+    def_va!(f, map_merge(map1, map2, args), |s| {
+        let map1 = get_map(s.get("map1")?)?;
+        let mut args = va_list(s.get("args")?);
+        // With more than two arguments, all but the last are a path
+        // of keys to the nested map to merge into.
+        let (keys, map2) = match args.pop() {
+            Some(map2) => {
+                let mut keys = vec![s.get("map2")?];
+                keys.extend(args);
+                (keys, map2)
+            }
+            None => (vec![], s.get("map2")?),
+        };
+        let map2 = get_map(map2)?;
+        Ok(Value::Map(modify_nested(map1, &keys, |mut map| {
+            for (key, value) in map2 {
+                map.insert(key, value);
+            }
+            map
+        })))
     });
     def_va!(f, map_remove(map, keys), |s| {
         let mut map = get_map(s.get("map")?)?;
         match s.get("keys")? {
-            Value::List(keys, ..) => {
-                for key in keys {
+            Value::ArgList(args) => {
+                for key in args.positional {
                     map.remove(&key);
                 }
             }
@@ -39,10 +60,142 @@ pub fn register(f: &mut BTreeMap<&'static str, SassFunction>) {
         let map = get_map(s.get("map")?)?;
         Ok(Value::List(map.values(), ListSeparator::Comma, false))
     });
-    def!(f, map_has_key(map, key), |s| {
+    def_va!(f, map_has_key(map, key, keys), |s| {
+        let map = get_map(s.get("map")?)?;
+        let mut keys = key_path(s.get("key")?, s.get("keys")?);
+        let key = keys.pop().unwrap();
+        Ok(Value::bool(match get_nested(&map, &keys) {
+            Some(map) => map.contains_key(&key),
+            None => false,
+        }))
+    });
+}
+
+pub fn create_module() -> Module {
+    let mut f = BTreeMap::new();
+    expose(&mut f, "map_get", "get");
+    expose(&mut f, "map_has_key", "has_key");
+    expose(&mut f, "map_keys", "keys");
+    expose(&mut f, "map_merge", "merge");
+    expose(&mut f, "map_remove", "remove");
+    expose(&mut f, "map_values", "values");
+
+    def_va!(f, set(map, args), |s| {
+        let map = get_map(s.get("map")?)?;
+        let mut args = va_list(s.get("args")?);
+        let (value, key) = match (args.pop(), args.pop()) {
+            (Some(value), Some(key)) => (value, key),
+            (Some(_), None) => {
+                return Err(Error::BadArguments(
+                    "Expected $args to contain a value.".into(),
+                ))
+            }
+            _ => {
+                return Err(Error::BadArguments(
+                    "Expected $args to contain a key.".into(),
+                ))
+            }
+        };
+        Ok(Value::Map(modify_nested(map, &args, |mut map| {
+            map.insert(key, value);
+            map
+        })))
+    });
+    def!(f, deep_merge(map1, map2), |s| {
+        let map1 = get_map(s.get("map1")?)?;
+        let map2 = get_map(s.get("map2")?)?;
+        Ok(Value::Map(deep_merge(map1, map2)))
+    });
+    def_va!(f, deep_remove(map, key, keys), |s| {
         let map = get_map(s.get("map")?)?;
-        Ok(Value::bool(map.contains_key(&s.get("key")?)))
+        let mut keys = key_path(s.get("key")?, s.get("keys")?);
+        let key = keys.pop().unwrap();
+        if get_nested(&map, &keys).is_none() {
+            return Ok(Value::Map(map));
+        }
+        Ok(Value::Map(modify_nested(map, &keys, |mut map| {
+            map.remove(&key);
+            map
+        })))
     });
+    f
+}
+
+/// Get the values of a variable argument list.
+fn va_list(args: Value) -> Vec<Value> {
+    match args {
+        Value::Null => vec![],
+        Value::ArgList(args) => args.positional,
+        Value::List(v, ListSeparator::Comma, false) => v,
+        v => vec![v],
+    }
+}
+
+/// Combine a required `key` argument and variable `keys` into a path.
+fn key_path(key: Value, keys: Value) -> Vec<Value> {
+    let mut path = vec![key];
+    path.extend(va_list(keys));
+    path
+}
+
+/// Get the map nested in `map` at the given key path, if any.
+fn get_nested<'a>(
+    map: &'a OrderMap<Value, Value>,
+    path: &[Value],
+) -> Option<&'a OrderMap<Value, Value>> {
+    match path.split_first() {
+        None => Some(map),
+        Some((key, rest)) => match map.get(key) {
+            Some(Value::Map(ref inner)) => get_nested(inner, rest),
+            _ => None,
+        },
+    }
+}
+
+/// Apply `f` to the map nested in `map` at the given key path.
+///
+/// Any key on the path that is missing or does not refer to a map is
+/// set to an empty map first.
+fn modify_nested<F>(
+    mut map: OrderMap<Value, Value>,
+    path: &[Value],
+    f: F,
+) -> OrderMap<Value, Value>
+where
+    F: FnOnce(OrderMap<Value, Value>) -> OrderMap<Value, Value>,
+{
+    match path.split_first() {
+        None => f(map),
+        Some((key, rest)) => {
+            let inner = match map.get(key) {
+                Some(Value::Map(inner)) => inner.clone(),
+                _ => OrderMap::new(),
+            };
+            map.insert(
+                key.clone(),
+                Value::Map(modify_nested(inner, rest, f)),
+            );
+            map
+        }
+    }
+}
+
+/// Merge `map2` into `map1`, recursively merging values that are
+/// maps in both.
+fn deep_merge(
+    mut map1: OrderMap<Value, Value>,
+    map2: OrderMap<Value, Value>,
+) -> OrderMap<Value, Value> {
+    for (key, value) in map2 {
+        let value = match (map1.get(&key), value) {
+            (Some(Value::Map(inner1)), Value::Map(inner2)) => {
+                Value::Map(deep_merge(inner1.clone(), inner2))
+            }
+            (_, value) => value,
+        };
+        map1.insert(key, value);
+    }
+    map1
 }
 
 fn get_map(v: Value) -> Result<OrderMap<Value, Value>, Error> {
@@ -94,6 +247,114 @@ mod test {
         }
     }
 
+    mod key_equality {
+        use super::check_val;
+
+        #[test]
+        fn quoted_and_unquoted() {
+            check_val("map-get((\"foo\": 1), foo);", "1")
+        }
+        #[test]
+        fn converted_units() {
+            check_val("map-get((1in: a), 96px);", "a")
+        }
+        #[test]
+        fn different_units() {
+            check_val("map-has-key((1px: a), 1);", "false")
+        }
+    }
+
+    mod nested {
+        use super::check_val;
+        use crate::variablescope::test::check_err;
+
+        const THEME: &str =
+            "(colors: (primary: (500: blue, 700: navy)), size: 1em)";
+
+        #[test]
+        fn get() {
+            check_val(
+                &format!("map.get({}, colors, primary, 500);", THEME),
+                "blue",
+            )
+        }
+        #[test]
+        fn get_missing() {
+            check_val(&format!("map.get({}, size, small);", THEME), "")
+        }
+        #[test]
+        fn has_key() {
+            check_val(
+                &format!("map.has-key({}, colors, primary, 700);", THEME),
+                "true",
+            )
+        }
+        #[test]
+        fn has_key_missing() {
+            check_val(
+                &format!("map.has-key({}, colors, accent, 700);", THEME),
+                "false",
+            )
+        }
+        #[test]
+        fn set() {
+            check_val(
+                "inspect(map.set((a: (b: 1)), a, c, 2));",
+                "(a: (b: 1, c: 2))",
+            )
+        }
+        #[test]
+        fn set_new_path() {
+            check_val(
+                "inspect(map.set((a: 1), b, c, 2));",
+                "(a: 1, b: (c: 2))",
+            )
+        }
+        #[test]
+        fn merge() {
+            check_val(
+                "inspect(map.merge((a: (b: 1)), a, (c: 2)));",
+                "(a: (b: 1, c: 2))",
+            )
+        }
+        #[test]
+        fn merge_global() {
+            check_val(
+                "inspect(map-merge((a: (b: 1)), a, (c: 2)));",
+                "(a: (b: 1, c: 2))",
+            )
+        }
+        #[test]
+        fn set_no_key() {
+            check_err("map.set((a: 1));", "Expected $args to contain a key.")
+        }
+        #[test]
+        fn set_no_value() {
+            check_err(
+                "map.set((a: 1), b);",
+                "Expected $args to contain a value.",
+            )
+        }
+        #[test]
+        fn deep_merge() {
+            check_val(
+                "inspect(map.deep-merge((a: (b: 1, c: 2)), (a: (c: 3), d: 4)));",
+                "(a: (b: 1, c: 3), d: 4)",
+            )
+        }
+        #[test]
+        fn deep_remove() {
+            check_val(
+                "inspect(map.deep-remove((a: (b: 1, c: 2)), a, b));",
+                "(a: (c: 2))",
+            )
+        }
+        #[test]
+        fn deep_remove_missing() {
+            check_val("inspect(map.deep-remove((a: 1), a, b));", "(a: 1)")
+        }
+    }
+
     fn check_val(src: &str, correct: &str) {
         use crate::variablescope::test::do_evaluate;
         assert_eq!(do_evaluate(&[], src.as_bytes()), correct)
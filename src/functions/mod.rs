@@ -9,9 +9,11 @@ use std::{cmp, fmt};
 #[macro_use]
 mod macros;
 
+mod colors_contrast;
 mod colors_hsl;
 mod colors_other;
 mod colors_rgb;
+mod colors_space;
 mod introspection;
 mod lists;
 mod maps;
@@ -19,12 +21,54 @@ mod numbers;
 mod selector;
 mod strings;
 
+/// Get a builtin function by name.
+///
+/// The name may be qualified with a builtin module, as in `math.div`.
 pub fn get_builtin_function(name: &str) -> Option<&'static SassFunction> {
     let name = name.replace("-", "_");
-    let name: &str = &name;
-    FUNCTIONS.get(name)
+    let mut parts = name.splitn(2, '.');
+    match (parts.next(), parts.next()) {
+        (Some(module), Some(name)) => get_builtin_module(module)?.get(name),
+        (Some(name), None) => FUNCTIONS.get(name),
+        _ => None,
+    }
+}
+
+/// Get a builtin module (e.g. `sass:math` is named `math`) by name.
+pub fn get_builtin_module(name: &str) -> Option<&'static Module> {
+    MODULES.get(name)
+}
+
+/// Get a variable defined in a builtin module, e.g. `math.pi`.
+pub fn get_builtin_variable(name: &str) -> Option<css::Value> {
+    let name = name.replace("-", "_");
+    let mut parts = name.splitn(2, '.');
+    match (parts.next(), parts.next()) {
+        (Some("math"), Some(name)) => numbers::module_variable(name),
+        _ => None,
+    }
 }
 
+/// Get all variables defined in a builtin module, by their local names.
+pub fn get_builtin_module_variables(
+    module: &str,
+) -> Vec<(&'static str, css::Value)> {
+    let names = match module {
+        "math" => numbers::MODULE_VARIABLES,
+        _ => &[],
+    };
+    names
+        .iter()
+        .filter_map(|&name| {
+            get_builtin_variable(&format!("{}.{}", module, name))
+                .map(|value| (name, value))
+        })
+        .collect()
+}
+
+/// The functions of a builtin module, by their local names.
+pub type Module = BTreeMap<&'static str, SassFunction>;
+
 type BuiltinFn =
     dyn Fn(&dyn Scope) -> Result<css::Value, Error> + Send + Sync;
 
@@ -104,7 +148,11 @@ impl SassFunction {
         body: Arc<BuiltinFn>,
     ) -> Self {
         SassFunction {
-            args: sass::FormalArgs::new(args, is_varargs),
+            // Arguments of builtin functions defaults to null.
+            args: sass::FormalArgs::new(
+                args.into_iter().map(|(k, v)| (k, Some(v))).collect(),
+                is_varargs,
+            ),
             body: FuncImpl::Builtin(body),
         }
     }
@@ -124,12 +172,15 @@ impl SassFunction {
         scope: &dyn Scope,
         args: &css::CallArgs,
     ) -> Result<css::Value, Error> {
-        let mut s = self.args.eval(scope, args)?;
         match self.body {
-            FuncImpl::Builtin(ref body) => body(&s),
-            FuncImpl::UserDefined(ref body) => {
-                Ok(s.eval_body(body)?.unwrap_or(css::Value::Null))
+            FuncImpl::Builtin(ref body) => {
+                body(&self.args.eval_builtin(scope, args)?)
             }
+            FuncImpl::UserDefined(ref body) => Ok(self
+                .args
+                .eval(scope, args)?
+                .eval_body(body)?
+                .unwrap_or(css::Value::Null)),
         }
     }
 }
@@ -147,6 +198,8 @@ lazy_static! {
         colors_hsl::register(&mut f);
         colors_rgb::register(&mut f);
         colors_other::register(&mut f);
+        colors_space::register(&mut f);
+        colors_contrast::register(&mut f);
         introspection::register(&mut f);
         selector::register(&mut f);
         strings::register(&mut f);
@@ -155,6 +208,25 @@ lazy_static! {
         maps::register(&mut f);
         f
     };
+    static ref MODULES: BTreeMap<&'static str, Module> = {
+        let mut m = BTreeMap::new();
+        m.insert("color", colors_other::create_module());
+        m.insert("list", lists::create_module());
+        m.insert("map", maps::create_module());
+        m.insert("math", numbers::create_module());
+        m.insert("meta", introspection::create_module());
+        m.insert("selector", selector::create_module());
+        m.insert("string", strings::create_module());
+        m
+    };
+}
+
+/// Expose a global builtin function in a module, by a local name.
+fn expose(m: &mut Module, global: &str, local: &'static str) {
+    let f = FUNCTIONS
+        .get(global)
+        .expect("Exposed function should exist");
+    m.insert(local, f.clone());
 }
 
 fn make_call(name: &str, args: Vec<css::Value>) -> css::Value {
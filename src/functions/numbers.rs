@@ -1,11 +1,12 @@
-use super::{Error, SassFunction};
-use crate::css::Value;
-use crate::value::{Number, Unit};
+use super::{expose, Error, Module, SassFunction};
+use crate::css::{ArgList, Value};
+use crate::value::{to_f64, Number, Quotes, Unit};
+use crate::variablescope::Scope;
 use num_rational::Rational;
-use num_traits::Signed;
-use rand::{thread_rng, Rng};
+use num_traits::{Signed, Zero};
 use std::cmp::Ordering;
 use std::collections::BTreeMap;
+use std::convert::TryFrom;
 
 pub fn register(f: &mut BTreeMap<&'static str, SassFunction>) {
     def!(f, abs(number), |s| match s.get("number")? {
@@ -31,24 +32,25 @@ pub fn register(f: &mut BTreeMap<&'static str, SassFunction>) {
         v => Err(Error::badarg("number", &v)),
     });
     def_va!(f, max(numbers), |s| match s.get("numbers")? {
-        Value::List(v, _, _) => {
+        Value::ArgList(ArgList { positional: v, .. }) => {
             Ok(find_extreme(&v, Ordering::Greater).clone())
         }
         single_value => Ok(single_value),
     });
     def_va!(f, min(numbers), |s| match s.get("numbers")? {
-        Value::List(v, _, _) => Ok(find_extreme(&v, Ordering::Less).clone()),
+        Value::ArgList(ArgList { positional: v, .. }) =>
+            Ok(find_extreme(&v, Ordering::Less).clone()),
         single_value => Ok(single_value),
     });
     def!(f, random(limit), |s| match s.get("limit")? {
         Value::Null => {
             let rez = 1_000_000;
-            Ok(number(Rational::new(intrand(rez), rez), Unit::None))
+            Ok(number(Rational::new(intrand(s, rez), rez), Unit::None))
         }
         Value::Numeric(val, ..) => {
             let bound = val.to_integer();
             if bound > 0 {
-                let res = 1 + intrand(bound);
+                let res = 1 + intrand(s, bound);
                 Ok(number(Rational::from_integer(res), Unit::None))
             } else {
                 Err(Error::S("bound must be > 0".into()))
@@ -58,6 +60,274 @@ pub fn register(f: &mut BTreeMap<&'static str, SassFunction>) {
     });
 }
 
+/// Create the `sass:math` module.
+pub fn create_module() -> Module {
+    let mut f = BTreeMap::new();
+    for &name in &["abs", "ceil", "floor", "max", "min", "percentage"] {
+        expose(&mut f, name, name);
+    }
+    expose(&mut f, "random", "random");
+    expose(&mut f, "round", "round");
+    expose(&mut f, "unit", "unit");
+    expose(&mut f, "unitless", "is_unitless");
+    expose(&mut f, "comparable", "compatible");
+
+    def!(f, div(number1, number2), |s| {
+        let (a, au) = get_number(s, "number1")?;
+        let (b, bu) = get_number(s, "number2")?;
+        if b.is_zero() {
+            return Err(Error::S("Division by zero".into()));
+        }
+        if bu == Unit::None {
+            Ok(number(a / b, au))
+        } else if let Some(scale) = bu.scale_to(&au) {
+            Ok(number(a / (b * scale), Unit::None))
+        } else {
+            Err(Error::BadArguments(format!(
+                "Can't divide {}{} by {}{}, the result would have \
+                 an unsupported unit",
+                Number::from(a).format(Default::default()),
+                au,
+                Number::from(b).format(Default::default()),
+                bu,
+            )))
+        }
+    });
+    def!(f, pow(base, exponent), |s| {
+        let base = get_unitless(s, "base")?;
+        let exponent = get_unitless(s, "exponent")?;
+        let result = if exponent.is_integer() {
+            // Keep exact results for the simple cases.
+            i32::try_from(exponent.to_integer())
+                .ok()
+                .and_then(|e| checked_pow(base, e))
+        } else {
+            None
+        };
+        match result {
+            Some(result) => Ok(number(result, Unit::None)),
+            None => Ok(number(
+                from_float(to_f64(base).powf(to_f64(exponent)), "pow")?,
+                Unit::None,
+            )),
+        }
+    });
+    def!(f, sqrt(number), |s| {
+        let v = to_f64(get_unitless(s, "number")?);
+        float_number(v.sqrt(), Unit::None, "sqrt")
+    });
+    def!(f, log(number, base), |s| {
+        let v = to_f64(get_unitless(s, "number")?);
+        let result = match s.get("base")? {
+            Value::Null => v.ln(),
+            _ => v.log(to_f64(get_unitless(s, "base")?)),
+        };
+        float_number(result, Unit::None, "log")
+    });
+    def!(f, sin(number), |s| {
+        let v = get_radians(s, "number")?.sin();
+        float_number(v, Unit::None, "sin")
+    });
+    def!(f, cos(number), |s| {
+        let v = get_radians(s, "number")?.cos();
+        float_number(v, Unit::None, "cos")
+    });
+    def!(f, tan(number), |s| {
+        let v = get_radians(s, "number")?.tan();
+        float_number(v, Unit::None, "tan")
+    });
+    def!(f, asin(number), |s| {
+        let v = to_f64(get_unitless(s, "number")?).asin();
+        float_number(v.to_degrees(), Unit::Deg, "asin")
+    });
+    def!(f, acos(number), |s| {
+        let v = to_f64(get_unitless(s, "number")?).acos();
+        float_number(v.to_degrees(), Unit::Deg, "acos")
+    });
+    def!(f, atan(number), |s| {
+        let v = to_f64(get_unitless(s, "number")?).atan();
+        float_number(v.to_degrees(), Unit::Deg, "atan")
+    });
+    def!(f, atan2(y, x), |s| {
+        let (y, yu) = get_number(s, "y")?;
+        let (x, xu) = get_number(s, "x")?;
+        let x = convert(x, &xu, &yu).ok_or_else(|| incompatible(&yu, &xu))?;
+        let v = to_f64(y).atan2(to_f64(x));
+        float_number(v.to_degrees(), Unit::Deg, "atan2")
+    });
+    def_va!(f, hypot(numbers), |s| {
+        let numbers = match s.get("numbers")? {
+            Value::ArgList(ArgList { positional: v, .. }) => v,
+            v => vec![v],
+        };
+        let mut args = Vec::new();
+        for (i, v) in numbers.into_iter().enumerate() {
+            match v {
+                Value::Numeric(v, u, ..) => {
+                    let name = if i == 0 { "Argument" } else { "argument" };
+                    args.push((format!("{} {}", name, i + 1), v.value, u));
+                }
+                v => return Err(Error::badarg("number", &v)),
+            }
+        }
+        all_or_no_units(&args)?;
+        let unit = args.first().map(|a| a.2.clone()).unwrap_or(Unit::None);
+        let mut sum = 0.;
+        for (_, v, u) in args {
+            let v = convert(v, &u, &unit)
+                .ok_or_else(|| incompatible(&unit, &u))?;
+            sum += to_f64(v).powi(2);
+        }
+        float_number(sum.sqrt(), unit, "hypot")
+    });
+    def!(f, clamp(min, number, max), |s| {
+        let (min, min_u) = get_number(s, "min")?;
+        let (v, u) = get_number(s, "number")?;
+        let (max, max_u) = get_number(s, "max")?;
+        all_or_no_units(&[
+            ("$min".into(), min, min_u.clone()),
+            ("$number".into(), v, u.clone()),
+            ("$max".into(), max, max_u.clone()),
+        ])?;
+        let min_v = convert(min, &min_u, &u)
+            .ok_or_else(|| incompatible(&min_u, &u))?;
+        let max_v = convert(max, &max_u, &u)
+            .ok_or_else(|| incompatible(&max_u, &u))?;
+        if v <= min_v {
+            Ok(number(min, min_u))
+        } else if v >= max_v {
+            Ok(number(max, max_u))
+        } else {
+            Ok(number(v, u))
+        }
+    });
+    f
+}
+
+/// The names of the variables in the `sass:math` module.
+pub static MODULE_VARIABLES: &[&str] = &["e", "pi"];
+
+/// Get the value of a variable in the `sass:math` module.
+pub fn module_variable(name: &str) -> Option<Value> {
+    match name {
+        "pi" => Some(std::f64::consts::PI),
+        "e" => Some(std::f64::consts::E),
+        _ => None,
+    }
+    .and_then(Rational::approximate_float)
+    .map(|v| number(v, Unit::None))
+}
+
+fn get_number(s: &dyn Scope, name: &str) -> Result<(Rational, Unit), Error> {
+    match s.get(name)? {
+        Value::Numeric(v, u, ..) => Ok((v.value, u)),
+        v => Err(Error::badarg("number", &v)),
+    }
+}
+
+fn get_unitless(s: &dyn Scope, name: &str) -> Result<Rational, Error> {
+    match s.get(name)? {
+        Value::Numeric(v, Unit::None, ..) => Ok(v.value),
+        v => Err(Error::badarg("unitless number", &v)),
+    }
+}
+
+/// Get an angle in radians.  A unitless number is taken as radians.
+fn get_radians(s: &dyn Scope, name: &str) -> Result<f64, Error> {
+    match s.get(name)? {
+        Value::Numeric(v, Unit::None, ..) => Ok(to_f64(v.value)),
+        Value::Numeric(ref v, ref u, ..) if u.dimension() == "angle" => {
+            let turns = convert(v.value, u, &Unit::Turn).unwrap();
+            Ok(to_f64(turns) * 2. * std::f64::consts::PI)
+        }
+        v => Err(Error::badarg("angle", &v)),
+    }
+}
+
+/// Convert a value from one unit to another.
+///
+/// A unitless value is compatible with any unit, see
+/// `Unit::compatible_scale`.
+fn convert(v: Rational, from: &Unit, to: &Unit) -> Option<Rational> {
+    from.compatible_scale(to).map(|scale| v * scale)
+}
+
+fn incompatible(a: &Unit, b: &Unit) -> Error {
+    Error::BadArguments(format!("Incompatible units {} and {}", a, b))
+}
+
+/// Check that either all or none of the named arguments have units.
+///
+/// A unitless number is otherwise compatible with any unit, but
+/// dart-sass requires this of the arguments to `hypot` and `clamp`.
+fn all_or_no_units(args: &[(String, Rational, Unit)]) -> Result<(), Error> {
+    let describe = |u: &Unit| {
+        if *u == Unit::None {
+            "is unitless".to_string()
+        } else {
+            format!("has unit {}", u)
+        }
+    };
+    if let Some((first, rest)) = args.split_first() {
+        let has_unit = |a: &(String, Rational, Unit)| a.2 != Unit::None;
+        if let Some(other) =
+            rest.iter().find(|a| has_unit(a) != has_unit(first))
+        {
+            return Err(Error::BadArguments(format!(
+                "{} {} but {} {}. Arguments must all have units or all \
+                 be unitless.",
+                first.0,
+                describe(&first.2),
+                other.0,
+                describe(&other.2),
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Make a number of a float result.
+///
+/// Numbers are rationals, so they can't be infinite or NaN.  Such
+/// results are given as the unquoted strings that dart-sass writes
+/// for them, e.g. `-Infinity` or `NaNdeg`.
+fn float_number(v: f64, unit: Unit, fn_name: &str) -> Result<Value, Error> {
+    if v.is_nan() || v.is_infinite() {
+        let v = if v.is_nan() {
+            "NaN"
+        } else if v > 0. {
+            "Infinity"
+        } else {
+            "-Infinity"
+        };
+        Ok(Value::Literal(format!("{}{}", v, unit), Quotes::None))
+    } else {
+        Ok(number(from_float(v, fn_name)?, unit))
+    }
+}
+
+fn from_float(v: f64, fn_name: &str) -> Result<Rational, Error> {
+    Rational::approximate_float(v).ok_or_else(|| {
+        Error::S(format!(
+            "Result of {}() is not a representable number",
+            fn_name
+        ))
+    })
+}
+
+fn checked_pow(base: Rational, exponent: i32) -> Option<Rational> {
+    let (n, d) = if exponent < 0 {
+        (*base.denom(), *base.numer())
+    } else {
+        (*base.numer(), *base.denom())
+    };
+    let e = u32::try_from(i64::from(exponent).abs()).ok()?;
+    if d == 0 {
+        return None;
+    }
+    Some(Rational::new(n.checked_pow(e)?, d.checked_pow(e)?))
+}
+
 fn number(v: Rational, unit: Unit) -> Value {
     Value::Numeric(Number::from(v), unit, true)
 }
@@ -100,6 +370,123 @@ fn find_extreme(v: &[Value], pref: Ordering) -> &Value {
 
 static NULL_VALUE: Value = Value::Null;
 
-fn intrand(lim: isize) -> isize {
-    thread_rng().gen_range(0, lim)
+/// Get a random integer in `0..lim` from the generator of the scope.
+///
+/// Numbers from the top of the range, where `% lim` would favor the
+/// low results, are rejected to keep the results uniform.
+fn intrand(s: &dyn Scope, lim: isize) -> isize {
+    let lim = lim as u64;
+    let zone = std::u64::MAX - std::u64::MAX % lim;
+    loop {
+        let r = s.next_random();
+        if r < zone {
+            return (r % lim) as isize;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::variablescope::test::check_err;
+
+    #[test]
+    fn div_keeps_unit() {
+        check_val("math.div(10px, 4);", "2.5px")
+    }
+    #[test]
+    fn div_compatible_units() {
+        check_val("math.div(1in, 48px);", "2")
+    }
+    #[test]
+    fn pow_integer() {
+        check_val("math.pow(2, 10);", "1024")
+    }
+    #[test]
+    fn pow_fraction() {
+        check_val("math.pow(4, 0.5);", "2")
+    }
+    #[test]
+    fn sqrt() {
+        check_val("math.sqrt(2);", "1.414214")
+    }
+    #[test]
+    fn log_base() {
+        check_val("math.log(8, 2);", "3")
+    }
+    #[test]
+    fn sin_deg() {
+        check_val("math.sin(90deg);", "1")
+    }
+    #[test]
+    fn atan2() {
+        check_val("math.atan2(1, 1);", "45deg")
+    }
+    #[test]
+    fn hypot() {
+        check_val("math.hypot(3px, 4px);", "5px")
+    }
+    #[test]
+    fn hypot_unitless_and_unit() {
+        check_err(
+            "math.hypot(3, 4px);",
+            "Argument 1 is unitless but argument 2 has unit px. \
+             Arguments must all have units or all be unitless.",
+        )
+    }
+    #[test]
+    fn clamp() {
+        check_val("math.clamp(1px, 3cm, 2in);", "3cm")
+    }
+    #[test]
+    fn clamp_unitless_and_unit() {
+        check_err(
+            "math.clamp(1, 3px, 4);",
+            "$min is unitless but $number has unit px. \
+             Arguments must all have units or all be unitless.",
+        )
+    }
+    #[test]
+    fn log_zero() {
+        check_val("math.log(0);", "-Infinity")
+    }
+    #[test]
+    fn sqrt_negative() {
+        check_val("math.sqrt(-1);", "NaN")
+    }
+    #[test]
+    fn asin_out_of_range() {
+        check_val("math.asin(2);", "NaNdeg")
+    }
+    #[test]
+    fn pi() {
+        check_val("math.$pi;", "3.141593")
+    }
+
+    #[test]
+    fn random_is_deterministic() {
+        assert_eq!(random_with_seed(0), random_with_seed(0))
+    }
+    #[test]
+    fn random_depends_on_seed() {
+        assert_ne!(random_with_seed(0), random_with_seed(17))
+    }
+    #[test]
+    fn random_limit_in_range() {
+        check_val("random(3) >= 1 and random(3) <= 3;", "true")
+    }
+
+    fn random_with_seed(seed: u64) -> String {
+        use crate::parser::value::value_expression;
+        use crate::variablescope::{GlobalScope, Scope};
+        let mut scope = GlobalScope::new(Default::default());
+        scope.set_seed(seed);
+        let (_, value) = value_expression(b"random(1000000);").unwrap();
+        let value = value.evaluate(&scope).unwrap();
+        value.format(scope.get_format()).to_string()
+    }
+
+    fn check_val(src: &str, correct: &str) {
+        use crate::variablescope::test::do_evaluate;
+        assert_eq!(do_evaluate(&[], src.as_bytes()), correct)
+    }
 }
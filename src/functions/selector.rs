@@ -1,20 +1,20 @@
-use super::SassFunction;
-use crate::css::Value;
+use super::{expose, Module, SassFunction};
+use crate::css::{ArgList, Value};
 use crate::error::Error;
 use crate::parser::selectors::{selector, selectors};
 use crate::selectors::{Selector, Selectors};
-use crate::value::Quotes;
+use crate::value::{ListSeparator, Quotes};
 use std::collections::BTreeMap;
 
 pub fn register(f: &mut BTreeMap<&'static str, SassFunction>) {
     def_va!(f, selector_nest(selectors), |s| match s.get("selectors")? {
-        Value::List(v, _, _) => Ok(Value::Literal(
+        Value::ArgList(ArgList { positional: v, .. }) => Ok(Value::Literal(
             format!(
                 "{}",
                 v.into_iter()
                     .map(parse_selectors)
                     .try_fold(Selectors::root(), |b, e| e
-                        .map(|e| e.inside(&b)))?
+                        .and_then(|e| e.inside(&b)))?
             ),
             Quotes::None,
         )),
@@ -27,25 +27,31 @@ pub fn register(f: &mut BTreeMap<&'static str, SassFunction>) {
         f,
         selector_append(selectors),
         |s| match s.get("selectors")? {
-            Value::List(v, _, _) => Ok(Value::Literal(
-                format!(
-                    "{}",
-                    v.into_iter().map(parse_selectors).try_fold(
-                        Selectors::root(),
-                        |base, ext| ext.and_then(|ext| Ok(Selectors::new(
-                            base.s
-                                .into_iter()
-                                .flat_map(|b| {
-                                    ext.s.iter().map(move |e| {
-                                        parse_selector(&format!("{}{}", b, e))
-                                    })
-                                })
-                                .collect::<Result<_, _>>()?
-                        ))),
-                    )?,
-                ),
-                Quotes::None,
-            )),
+            Value::ArgList(ArgList { positional: v, .. }) =>
+                Ok(Value::Literal(
+                    format!(
+                        "{}",
+                        v.into_iter().map(parse_selectors).try_fold(
+                            Selectors::root(),
+                            |base, ext| ext.and_then(|ext| Ok(
+                                Selectors::new(
+                                    base.s
+                                        .into_iter()
+                                        .flat_map(|b| {
+                                            ext.s.iter().map(move |e| {
+                                                parse_selector(&format!(
+                                                    "{}{}",
+                                                    b, e
+                                                ))
+                                            })
+                                        })
+                                        .collect::<Result<_, _>>()?
+                                )
+                            )),
+                        )?,
+                    ),
+                    Quotes::None,
+                )),
             v => Ok(Value::Literal(
                 format!("{}", parse_selectors(v)?),
                 Quotes::None,
@@ -56,6 +62,75 @@ pub fn register(f: &mut BTreeMap<&'static str, SassFunction>) {
         s.get("selector")?
     )?
     .to_value()));
+    def!(f, is_superselector(super, sub), |s| {
+        let sup = parse_selectors(s.get("super")?)?;
+        let sub = parse_selectors(s.get("sub")?)?;
+        Ok(Value::bool(sup.is_superselector(&sub)))
+    });
+    def!(f, selector_unify(selector1, selector2), |s| {
+        let s1 = parse_selectors(s.get("selector1")?)?;
+        let s2 = parse_selectors(s.get("selector2")?)?;
+        Ok(s1.unify(&s2).map(|s| s.to_value()).unwrap_or(Value::Null))
+    });
+    def!(f, simple_selectors(selector), |s| {
+        let v = s.get("selector")?;
+        let selectors = parse_selectors(v.clone())?;
+        match selectors.s.as_slice() {
+            [one] => match one.simple_selectors() {
+                Some(parts) => Ok(Value::List(
+                    parts
+                        .iter()
+                        .map(|p| Value::Literal(p.to_string(), Quotes::None))
+                        .collect(),
+                    ListSeparator::Comma,
+                    false,
+                )),
+                None => Err(Error::badarg("compound selector", &v)),
+            },
+            _ => Err(Error::badarg("compound selector", &v)),
+        }
+    });
+    def!(f, selector_extend(selector, extendee, extender), |s| {
+        let selector = parse_selectors(s.get("selector")?)?;
+        let extendee = parse_selectors(s.get("extendee")?)?;
+        let extender = parse_selectors(s.get("extender")?)?;
+        Ok(selector.extend(&extendee, &extender)?.to_value())
+    });
+    def!(f, selector_replace(selector, original, replacement), |s| {
+        let selector = parse_selectors(s.get("selector")?)?;
+        let original = parse_selectors(s.get("original")?)?;
+        let replacement = parse_selectors(s.get("replacement")?)?;
+        Ok(selector.replace(&original, &replacement)?.to_value())
+    });
+}
+
+pub fn create_module() -> Module {
+    let mut f = BTreeMap::new();
+    for &(global, local) in &[
+        ("is_superselector", "is_superselector"),
+        ("selector_append", "append"),
+        ("selector_extend", "extend"),
+        ("selector_nest", "nest"),
+        ("selector_parse", "parse"),
+        ("selector_replace", "replace"),
+        ("selector_unify", "unify"),
+        ("simple_selectors", "simple_selectors"),
+    ] {
+        expose(&mut f, global, local);
+    }
+    def!(f, specificity(selector), |s| {
+        let (a, b, c) = parse_selectors(s.get("selector")?)?.specificity();
+        Ok(Value::List(
+            vec![
+                Value::scalar(a as isize),
+                Value::scalar(b as isize),
+                Value::scalar(c as isize),
+            ],
+            ListSeparator::Space,
+            false,
+        ))
+    });
+    f
 }
 
 fn parse_selectors(v: Value) -> Result<Selectors, Error> {
@@ -86,3 +161,72 @@ fn parse_selector(s: &str) -> Result<Selector, Error> {
         Ok(result)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use crate::variablescope::test::do_evaluate;
+
+    #[test]
+    fn unify_compound() {
+        assert_eq!(do_evaluate(&[], b"selector-unify(\"a\", \".b\");"), "a.b")
+    }
+    #[test]
+    fn unify_complex() {
+        assert_eq!(
+            do_evaluate(&[], b"selector-unify(\".a .x\", \".b .y\");"),
+            ".a .b .x.y, .b .a .x.y"
+        )
+    }
+    #[test]
+    fn unify_child() {
+        assert_eq!(
+            do_evaluate(&[], b"selector-unify(\".a > .x\", \".b > .y\");"),
+            ".a.b > .x.y"
+        )
+    }
+    #[test]
+    fn unify_impossible() {
+        assert_eq!(do_evaluate(&[], b"selector-unify(\"#a\", \"#b\");"), "")
+    }
+    #[test]
+    fn simple_selectors() {
+        assert_eq!(
+            do_evaluate(&[], b"simple-selectors(\"a.b#c:d\");"),
+            "a, .b, #c, :d"
+        )
+    }
+    #[test]
+    fn extend() {
+        assert_eq!(
+            do_evaluate(
+                &[],
+                b"selector-extend(\".a .b\", \".b\", \".c .d\");"
+            ),
+            ".a .b, .a .c .d, .c .a .d"
+        )
+    }
+    #[test]
+    fn replace() {
+        assert_eq!(
+            do_evaluate(
+                &[],
+                b"selector-replace(\".x .b, .y\", \".b\", \".c\");"
+            ),
+            ".x .c, .y"
+        )
+    }
+    #[test]
+    fn specificity() {
+        assert_eq!(
+            do_evaluate(&[], b"selector.specificity(\"#a .b:hover > p\");"),
+            "1 2 1"
+        )
+    }
+    #[test]
+    fn module_is_superselector() {
+        assert_eq!(
+            do_evaluate(&[], b"selector.is-superselector(\".a\", \".a.b\");"),
+            "true"
+        )
+    }
+}
@@ -1,11 +1,83 @@
-use super::{Error, SassFunction};
+use super::{expose, Error, Module, SassFunction};
 use crate::css::Value;
-use crate::value::{Number, Quotes, Unit};
-use lazy_static::lazy_static;
+use crate::value::{ListSeparator, Number, Quotes, Unit};
 use num_rational::Rational;
-use std::cmp::max;
+use std::cmp::{max, min};
 use std::collections::BTreeMap;
-use std::sync::Mutex;
+
+pub fn create_module() -> Module {
+    let mut f = BTreeMap::new();
+    for &name in &[
+        "quote",
+        "to_lower_case",
+        "to_upper_case",
+        "unique_id",
+        "unquote",
+    ] {
+        expose(&mut f, name, name);
+    }
+    expose(&mut f, "str_index", "index");
+    expose(&mut f, "str_insert", "insert");
+    expose(&mut f, "str_length", "length");
+    expose(&mut f, "str_slice", "slice");
+
+    def!(f, split(string, separator, limit), |s| match (
+        s.get("string")?,
+        s.get("separator")?,
+    ) {
+        (Value::Literal(string, q), Value::Literal(separator, _)) => {
+            let limit = match s.get("limit")? {
+                Value::Null => None,
+                Value::Numeric(v, Unit::None, _) => {
+                    let limit = require_integer(v.value)?;
+                    if limit < 1 {
+                        return Err(Error::BadArguments(format!(
+                            "$limit: Must be 1 or greater, was {}.",
+                            limit
+                        )));
+                    }
+                    Some(limit as usize)
+                }
+                v => return Err(Error::badarg("number", &v)),
+            };
+            let parts: Vec<String> = if string.is_empty() {
+                vec![]
+            } else if separator.is_empty() {
+                let mut chars = string.chars();
+                let mut parts = chars
+                    .by_ref()
+                    .take(limit.unwrap_or(std::usize::MAX))
+                    .map(String::from)
+                    .collect::<Vec<_>>();
+                let rest = chars.as_str();
+                if !rest.is_empty() {
+                    parts.push(rest.into());
+                }
+                parts
+            } else if let Some(limit) = limit {
+                string
+                    .splitn(limit + 1, &separator)
+                    .map(String::from)
+                    .collect()
+            } else {
+                string.split(&separator).map(String::from).collect()
+            };
+            Ok(Value::List(
+                parts
+                    .into_iter()
+                    .map(|part| Value::Literal(part, q))
+                    .collect(),
+                ListSeparator::Comma,
+                true,
+            ))
+        }
+        (string, separator) => Err(Error::badargs(
+            &["string", "string"],
+            &[&string, &separator]
+        )),
+    });
+    f
+}
 
 pub fn register(f: &mut BTreeMap<&'static str, SassFunction>) {
     def!(f, quote(string), |s| {
@@ -39,14 +111,13 @@ pub fn register(f: &mut BTreeMap<&'static str, SassFunction>) {
             Value::Literal(insert, _),
             Value::Numeric(index, Unit::None, ..),
         ) => {
-            let index = index.value.to_integer();
+            let len = s.chars().count();
+            let index = require_integer(index.value)?;
+            // Negative indexes start beyond the end of the string.
             let i = if index.is_negative() {
-                let len = s.chars().count() as isize;
-                max(len + 1 + index, 0) as usize
-            } else if index.is_positive() {
-                index as usize - 1
+                codepoint_for_index(len as isize + index + 2, len)
             } else {
-                0
+                codepoint_for_index(index, len)
             };
             let mut s = s.chars();
             Ok(Value::Literal(
@@ -74,22 +145,25 @@ pub fn register(f: &mut BTreeMap<&'static str, SassFunction>) {
             Value::Numeric(start_at, Unit::None, ..),
             Value::Numeric(end_at, Unit::None, ..),
         ) => {
-            let start_at = index_to_rust(start_at.value, &s)?;
-            let end_at = index_to_rust_end(end_at.value, &s)?;
-            let c = s.chars();
-            if start_at <= end_at {
-                Ok(Value::Literal(
-                    c.skip(start_at)
-                        .take(end_at - start_at)
-                        .collect::<String>(),
-                    q,
-                ))
+            let len = s.chars().count();
+            let start_at = require_integer(start_at.value)?;
+            let end_at = require_integer(end_at.value)?;
+            let start = codepoint_for_index(start_at, len);
+            let end = if end_at.is_negative() {
+                len as isize + end_at + 1
             } else {
-                Err(Error::S(format!(
-                    "Bad indexes: {}..{}",
-                    start_at, end_at
-                )))
-            }
+                codepoint_for_index(end_at, len) as isize
+                    + if end_at == 0 { 0 } else { 1 }
+            };
+            let end = min(max(end, 0) as usize, len);
+            Ok(Value::Literal(
+                if start < end {
+                    s.chars().skip(start).take(end - start).collect()
+                } else {
+                    String::new()
+                },
+                q,
+            ))
         }
         (v, s, e) => Err(Error::badargs(
             &["string", "number", "number"],
@@ -122,17 +196,9 @@ pub fn register(f: &mut BTreeMap<&'static str, SassFunction>) {
         Value::Literal(v, q) => Ok(Value::Literal(v.to_lowercase(), q)),
         v => Ok(v),
     });
-    def!(f, unique_id(), |_s| {
-        lazy_static! {
-            static ref CALL_ID: Mutex<u64> =
-                Mutex::new(u64::from(std::process::id()) * 0xa01);
-        };
+    def!(f, unique_id(), |s| {
         Ok(Value::Literal(
-            format!("x{:x}", {
-                let mut v = CALL_ID.lock().unwrap();
-                *v += 1;
-                *v
-            }),
+            format!("u{:016x}", s.next_random()),
             Quotes::None,
         ))
     });
@@ -148,55 +214,117 @@ fn intvalue(n: usize) -> Value {
     Value::Numeric(Number::from(n as isize), Unit::None, true)
 }
 
-/// Convert index from sass (rational number, first is one) to rust
-/// (usize, first is zero).  Sass values might be negative, then -1 is
-/// the last char in the string.
-fn index_to_rust(index: Rational, s: &str) -> Result<usize, Error> {
-    let index = require_integer(index)?;
-    let len = s.chars().count();
-    Ok(if index.is_negative() {
-        let i = index.abs() as usize;
-        if i <= len {
-            len - i
-        } else {
-            0
-        }
-    } else if index.is_positive() {
-        let i = index as usize - 1;
-        if i <= len {
-            i
-        } else {
-            len
-        }
-    } else {
-        0
-    })
-}
-
-/// Convert index from sass (rational number, first is one) to rust
-/// (usize, first is zero).  Sass values might be negative, then -1 is
-/// the last char in the string.
-fn index_to_rust_end(index: Rational, s: &str) -> Result<usize, Error> {
-    let index = require_integer(index)?;
-    Ok(if index.is_negative() {
-        let len = s.chars().count();
-        let i = index.abs() as usize - 1;
-        if i <= len {
-            len - i
-        } else {
-            0
-        }
-    } else if index.is_positive() {
-        index as usize
+/// Convert index from sass (first is one) to a code point index in
+/// rust (first is zero) in a string of `len` code points.
+///
+/// Negative sass indexes count from the end, -1 is the last code point.
+/// Indexes out of range are clamped to the string.
+fn codepoint_for_index(index: isize, len: usize) -> usize {
+    if index.is_positive() {
+        min(index as usize - 1, len)
+    } else if index.is_negative() {
+        max(len as isize + index, 0) as usize
     } else {
         0
-    })
+    }
 }
 
 fn require_integer(value: Rational) -> Result<isize, Error> {
+    let value = Number::from(value);
     if value.is_integer() {
-        Ok(value.to_integer())
+        Ok(value.value.round().to_integer())
     } else {
-        Err(Error::S(format!("{} is not an int", value)))
+        Err(Error::S(format!(
+            "{} is not an int",
+            value.format(Default::default())
+        )))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::Error;
+    use crate::variablescope::test::{
+        check_err, do_evaluate, do_evaluate_or_error,
+    };
+
+    #[test]
+    fn split() {
+        assert_eq!(
+            do_evaluate(&[], b"string.split(\"a b c\", \" \");"),
+            "[\"a\", \"b\", \"c\"]"
+        )
+    }
+    #[test]
+    fn split_limit() {
+        assert_eq!(
+            do_evaluate(&[], b"string.split(a-b-c-d, \"-\", 2);"),
+            "[a, b, c-d]"
+        )
+    }
+    #[test]
+    fn split_empty_separator() {
+        assert_eq!(
+            do_evaluate(
+                &[],
+                b"string.split(\"\xc3\xa5\xc3\xa4\xc3\xb6\", \"\");"
+            ),
+            "[\"\u{e5}\", \"\u{e4}\", \"\u{f6}\"]"
+        )
+    }
+    #[test]
+    fn split_empty_string() {
+        assert_eq!(do_evaluate(&[], b"string.split(\"\", \",\");"), "[]")
+    }
+    #[test]
+    fn split_bad_limit() {
+        check_err(
+            "string.split(a, \"\", 0);",
+            "$limit: Must be 1 or greater, was 0.",
+        )
+    }
+    #[test]
+    fn slice_index_not_int() {
+        match do_evaluate_or_error(&[], b"str-slice(abcd, 1.5);") {
+            Err(Error::S(ref msg)) => assert_eq!(msg, "1.5 is not an int"),
+            other => panic!("Expected error, got {:?}", other),
+        }
+    }
+    #[test]
+    fn slice_non_ascii() {
+        assert_eq!(
+            do_evaluate(&[], b"str-slice(\"bl\xc3\xa5b\xc3\xa4r\", 3, -2);"),
+            "\"\u{e5}b\u{e4}\""
+        )
+    }
+    #[test]
+    fn slice_end_before_start() {
+        assert_eq!(do_evaluate(&[], b"str-slice(\"abcd\", 3, 2);"), "\"\"")
+    }
+    #[test]
+    fn slice_end_zero() {
+        assert_eq!(do_evaluate(&[], b"str-slice(\"abcd\", 1, 0);"), "\"\"")
+    }
+    #[test]
+    fn slice_negative_out_of_range() {
+        assert_eq!(do_evaluate(&[], b"str-slice(\"abcd\", -10, -6);"), "\"\"")
+    }
+    #[test]
+    fn insert_negative() {
+        assert_eq!(
+            do_evaluate(&[], b"str-insert(\"\xc3\xa5b\", \"x\", -1);"),
+            "\"\u{e5}bx\""
+        )
+    }
+    #[test]
+    fn unique_id_is_deterministic() {
+        assert_eq!(
+            do_evaluate(&[], b"unique-id();"),
+            do_evaluate(&[], b"unique-id();"),
+        )
+    }
+    #[test]
+    fn unique_ids_differ() {
+        assert_eq!(do_evaluate(&[], b"unique-id() == unique-id();"), "false")
     }
 }
@@ -10,10 +10,7 @@
 //! use rsass::{compile_scss_file, output};
 //!
 //! let file = "tests/basic/14_imports/a.scss".as_ref();
-//! let format = output::Format {
-//!     style: output::Style::Compressed,
-//!     precision: 5,
-//! };
+//! let format = output::Format::new(output::Style::Compressed, 5);
 //! let css = compile_scss_file(file, format).unwrap();
 //!
 //! assert_eq!(css, b"div span{moo:goo}\n")
@@ -57,7 +54,7 @@ pub use crate::functions::SassFunction;
 use crate::output::Format;
 pub use crate::parser::{parse_scss_data, parse_scss_file, parse_value_data};
 pub use crate::sass::Item;
-pub use crate::value::{ListSeparator, Number, Quotes, Unit};
+pub use crate::value::{CalcName, ListSeparator, Number, Quotes, Unit};
 pub use crate::variablescope::{GlobalScope, Scope};
 pub use num_rational::Rational;
 
@@ -122,7 +119,7 @@ pub fn compile_scss(input: &[u8], format: Format) -> Result<Vec<u8>, Error> {
 /// assert_eq!(
 ///     compile_scss_file(
 ///         "tests/basic/14_imports/a.scss".as_ref(),
-///         Format { style: Style::Compressed, precision: 5 },
+///         Format::new(Style::Compressed, 5),
 ///     ).unwrap(),
 ///     b"div span{moo:goo}\n"
 /// )
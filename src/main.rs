@@ -1,5 +1,5 @@
 use rsass::{
-    output::{Format, Style},
+    output::{ColorFormat, Format, Style},
     parse_scss_file, Error, FileContext, GlobalScope,
 };
 use std::io::{stdout, Write};
@@ -33,12 +33,33 @@ struct Args {
     #[structopt(long, default_value = "5")]
     precision: usize,
 
+    /// Use the fixed precision of dart-sass (10 digits) rather than
+    /// --precision.
+    #[structopt(long)]
+    dart_sass_precision: bool,
+
     /// How to format output.
     #[structopt(long, short = "t", case_insensitive = true,
                 default_value = "expanded",
                 possible_values = Style::variants())]
     style: Style,
 
+    /// How to write colors.
+    #[structopt(long, case_insensitive = true,
+                default_value = "auto",
+                possible_values = ColorFormat::variants())]
+    colors: ColorFormat,
+
+    /// Write translucent colors as #rrggbbaa.
+    #[structopt(long)]
+    hex_alpha: bool,
+
+    /// Seed for random() and unique-id().
+    ///
+    /// Compiling the same input with the same seed gives the same output.
+    #[structopt(long, default_value = "0")]
+    random_seed: u64,
+
     /// Where to search for included resources.
     #[structopt(long, short = "I")]
     include_path: Option<PathBuf>,
@@ -50,10 +71,13 @@ struct Args {
 
 impl Args {
     fn run(self) -> Result<(), Error> {
-        let format = Format {
-            style: self.style,
-            precision: self.precision,
-        };
+        let format = if self.dart_sass_precision {
+            Format::dart_sass(self.style)
+        } else {
+            Format::new(self.style, self.precision)
+        }
+        .with_colors(self.colors)
+        .with_hex_alpha(self.hex_alpha);
         for name in &self.input {
             let mut file_context = FileContext::new();
             if let Some(include_path) = &self.include_path {
@@ -61,11 +85,10 @@ impl Args {
             }
             let (sub_context, file) = file_context.file(name.as_ref());
             let items = parse_scss_file(&file)?;
-            let result = format.write_root(
-                &items,
-                &mut GlobalScope::new(format),
-                &sub_context,
-            )?;
+            let mut globals = GlobalScope::new(format);
+            globals.set_seed(self.random_seed);
+            let result =
+                format.write_root(&items, &mut globals, &sub_context)?;
             let out = stdout();
             out.lock().write_all(&result)?;
         }
@@ -1,61 +1,176 @@
+use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
 use std::iter::FromIterator;
 use std::slice::Iter;
 use std::vec::IntoIter;
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
-pub struct OrderMap<K, V>(Vec<(K, V)>);
+/// A key in an `OrderMap`.
+///
+/// Keys are compared by `eq_key` rather than `==`, so that values that
+/// are equal in sass (e.g. `"a"` and `a`) are the same key.
+pub trait MapKey {
+    /// Hash the key.  Keys that are `eq_key` must hash the same.
+    fn hash_key<H: Hasher>(&self, state: &mut H);
+    /// Check if two keys are the same.
+    fn eq_key(&self, other: &Self) -> bool;
+}
+
+/// A map that keeps its keys in insertion order.
+///
+/// Lookup is done through a hash index, so it is not linear in the
+/// size of the map.
+#[derive(Clone, Debug)]
+pub struct OrderMap<K, V> {
+    items: Vec<(K, V)>,
+    index: HashMap<u64, Vec<usize>>,
+}
 
-impl<K: Clone + PartialEq, V: Clone> OrderMap<K, V> {
+impl<K: MapKey, V> OrderMap<K, V> {
     pub fn new() -> Self {
-        OrderMap(Vec::new())
+        OrderMap {
+            items: Vec::new(),
+            index: HashMap::new(),
+        }
     }
     pub fn insert(&mut self, key: K, value: V) {
-        for &mut (ref k, ref mut v) in &mut self.0 {
-            if k == &key {
-                *v = value;
-                return;
-            }
+        if let Some(i) = self.position(&key) {
+            self.items[i].1 = value;
+        } else {
+            self.index
+                .entry(hash_of(&key))
+                .or_default()
+                .push(self.items.len());
+            self.items.push((key, value));
         }
-        self.0.push((key, value));
     }
     pub fn iter(&self) -> Iter<(K, V)> {
-        self.0.iter()
-    }
-    // TODO Should return a specialized iterator!
-    pub fn keys(&self) -> Vec<K> {
-        self.0.iter().map(|&(ref k, ref _v)| k).cloned().collect()
-    }
-    // TODO Should return a specialized iterator!
-    pub fn values(&self) -> Vec<V> {
-        self.0.iter().map(|&(ref _k, ref v)| v).cloned().collect()
+        self.items.iter()
     }
     pub fn get(&self, key: &K) -> Option<&V> {
-        for &(ref k, ref v) in &self.0 {
-            if k == key {
-                return Some(v);
-            }
-        }
-        None
+        self.position(key).map(|i| &self.items[i].1)
     }
+    /// Remove `key` from the map, if present.
+    ///
+    /// This is linear in the size of the map, since the following
+    /// items are moved to keep the order.
     pub fn remove(&mut self, key: &K) {
-        self.0.retain(|&(ref k, ref _v)| k != key);
+        if let Some(i) = self.position(key) {
+            self.remove_at(i);
+        }
     }
     pub fn contains_key(&self, key: &K) -> bool {
-        for &(ref k, ref _v) in &self.0 {
-            if k == key {
-                return true;
-            }
-        }
-        false
+        self.position(key).is_some()
     }
     pub fn len(&self) -> usize {
-        self.0.len()
+        self.items.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
     }
     pub fn get_item(&self, i: usize) -> Option<&(K, V)> {
-        self.0.get(i)
+        self.items.get(i)
     }
+    /// Replace the item at position `i`.
+    ///
+    /// If `key` is already in the map at another position, that item
+    /// is removed, so the keys are kept unique.
     pub fn set_item(&mut self, i: usize, key: K, value: V) {
-        self.0[i] = (key, value);
+        let old_hash = hash_of(&self.items[i].0);
+        self.unindex(old_hash, i);
+        let i = match self.position(&key) {
+            Some(j) => {
+                self.remove_at(j);
+                if j < i {
+                    i - 1
+                } else {
+                    i
+                }
+            }
+            None => i,
+        };
+        self.index.entry(hash_of(&key)).or_default().push(i);
+        self.items[i] = (key, value);
+    }
+
+    fn position(&self, key: &K) -> Option<usize> {
+        self.index
+            .get(&hash_of(key))?
+            .iter()
+            .cloned()
+            .find(|&i| self.items[i].0.eq_key(key))
+    }
+    fn remove_at(&mut self, i: usize) {
+        let (key, _) = self.items.remove(i);
+        self.unindex(hash_of(&key), i);
+        for positions in self.index.values_mut() {
+            for p in positions.iter_mut().filter(|p| **p > i) {
+                *p -= 1;
+            }
+        }
+    }
+    fn unindex(&mut self, hash: u64, i: usize) {
+        if let Some(positions) = self.index.get_mut(&hash) {
+            positions.retain(|&p| p != i);
+            if positions.is_empty() {
+                self.index.remove(&hash);
+            }
+        }
+    }
+}
+
+impl<K: Clone, V: Clone> OrderMap<K, V> {
+    // TODO Should return a specialized iterator!
+    pub fn keys(&self) -> Vec<K> {
+        self.items
+            .iter()
+            .map(|&(ref k, ref _v)| k)
+            .cloned()
+            .collect()
+    }
+    // TODO Should return a specialized iterator!
+    pub fn values(&self) -> Vec<V> {
+        self.items
+            .iter()
+            .map(|&(ref _k, ref v)| v)
+            .cloned()
+            .collect()
+    }
+}
+
+fn hash_of<K: MapKey>(key: &K) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    key.hash_key(&mut hasher);
+    hasher.finish()
+}
+
+// The index is derived from the items, so only the items are
+// compared or hashed.
+
+impl<K: PartialEq, V: PartialEq> PartialEq for OrderMap<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.items == other.items
+    }
+}
+
+impl<K: Eq, V: Eq> Eq for OrderMap<K, V> {}
+
+impl<K: PartialOrd, V: PartialOrd> PartialOrd for OrderMap<K, V> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.items.partial_cmp(&other.items)
+    }
+}
+
+impl<K: Ord, V: Ord> Ord for OrderMap<K, V> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.items.cmp(&other.items)
+    }
+}
+
+impl<K: Hash, V: Hash> Hash for OrderMap<K, V> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.items.hash(state)
     }
 }
 
@@ -63,15 +178,89 @@ impl<K, V> IntoIterator for OrderMap<K, V> {
     type Item = (K, V);
     type IntoIter = IntoIter<(K, V)>;
     fn into_iter(self) -> IntoIter<(K, V)> {
-        self.0.into_iter()
+        self.items.into_iter()
     }
 }
 
-impl<K, V> FromIterator<(K, V)> for OrderMap<K, V> {
+impl<K: MapKey, V> FromIterator<(K, V)> for OrderMap<K, V> {
     fn from_iter<T>(i: T) -> Self
     where
         T: IntoIterator<Item = (K, V)>,
     {
-        OrderMap(Vec::from_iter(i))
+        let mut map = OrderMap::new();
+        for (k, v) in i {
+            map.insert(k, v);
+        }
+        map
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{MapKey, OrderMap};
+    use std::hash::Hasher;
+
+    /// Strings that are equal ignoring ascii case.
+    impl MapKey for &'static str {
+        fn hash_key<H: Hasher>(&self, state: &mut H) {
+            state.write(self.to_ascii_lowercase().as_bytes())
+        }
+        fn eq_key(&self, other: &Self) -> bool {
+            self.eq_ignore_ascii_case(other)
+        }
+    }
+
+    #[test]
+    fn insert_keeps_order() {
+        let map: OrderMap<_, _> =
+            vec![("b", 1), ("a", 2), ("c", 3)].into_iter().collect();
+        assert_eq!(map.keys(), vec!["b", "a", "c"]);
+    }
+    #[test]
+    fn insert_replaces_equal_key() {
+        let mut map = OrderMap::new();
+        map.insert("a", 1);
+        map.insert("b", 2);
+        map.insert("A", 3);
+        assert_eq!(map.keys(), vec!["a", "b"]);
+        assert_eq!(map.get(&"a"), Some(&3));
+    }
+    #[test]
+    fn remove_and_lookup() {
+        let mut map: OrderMap<_, _> =
+            vec![("a", 1), ("b", 2), ("c", 3)].into_iter().collect();
+        map.remove(&"B");
+        assert_eq!(map.get(&"c"), Some(&3));
+        assert!(!map.contains_key(&"b"));
+        assert_eq!(map.len(), 2);
+    }
+    #[test]
+    fn remove_keeps_lookup_of_later_keys() {
+        let mut map: OrderMap<_, _> =
+            vec![("a", 1), ("b", 2), ("c", 3), ("d", 4)]
+                .into_iter()
+                .collect();
+        map.remove(&"a");
+        map.remove(&"c");
+        assert_eq!(map.keys(), vec!["b", "d"]);
+        assert_eq!(map.get(&"b"), Some(&2));
+        assert_eq!(map.get(&"d"), Some(&4));
+    }
+    #[test]
+    fn set_item_reindexes() {
+        let mut map: OrderMap<_, _> =
+            vec![("a", 1), ("b", 2)].into_iter().collect();
+        map.set_item(0, "x", 7);
+        assert_eq!(map.get(&"x"), Some(&7));
+        assert_eq!(map.get(&"a"), None);
+    }
+    #[test]
+    fn set_item_existing_key() {
+        let mut map: OrderMap<_, _> =
+            vec![("a", 1), ("b", 2), ("c", 3)].into_iter().collect();
+        map.set_item(2, "A", 7);
+        assert_eq!(map.keys(), vec!["b", "A"]);
+        assert_eq!(map.get(&"a"), Some(&7));
+        assert_eq!(map.get(&"b"), Some(&2));
     }
 }
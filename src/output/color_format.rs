@@ -0,0 +1,62 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// How colors are written in css output.
+///
+/// Colors that are outside of the srgb gamut are always written in
+/// their own color space.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorFormat {
+    /// Keep the original spelling of colors given literally, and
+    /// write other colors as a name or hex code.
+    ///
+    /// The shorter of name and hex is used in compressed style.
+    Auto,
+    /// Always use the shortest of a name, `#rgb` and `#rrggbb`.
+    Shortest,
+    /// Always use `#rrggbb`.
+    Hex,
+    /// Use the space-separated `rgb(r g b)` or `rgb(r g b / a%)`
+    /// syntax from css colors level 4.
+    Rgb,
+}
+
+impl Default for ColorFormat {
+    fn default() -> Self {
+        ColorFormat::Auto
+    }
+}
+
+impl fmt::Display for ColorFormat {
+    fn fmt(&self, out: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        out.write_str(match self {
+            ColorFormat::Auto => "auto",
+            ColorFormat::Shortest => "shortest",
+            ColorFormat::Hex => "hex",
+            ColorFormat::Rgb => "rgb",
+        })
+    }
+}
+
+/// Get a color format from its name.
+impl FromStr for ColorFormat {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_ref() {
+            "auto" => Ok(ColorFormat::Auto),
+            "shortest" => Ok(ColorFormat::Shortest),
+            "hex" => Ok(ColorFormat::Hex),
+            "rgb" => Ok(ColorFormat::Rgb),
+            s => Err(format!("Color format {:?} not supported", s)),
+        }
+    }
+}
+
+static COLOR_FORMAT_NAMES: [&str; 4] = ["Auto", "Shortest", "Hex", "Rgb"];
+
+impl ColorFormat {
+    /// Get the names of the supported color formats.
+    pub fn variants() -> &'static [&'static str] {
+        &COLOR_FORMAT_NAMES
+    }
+}
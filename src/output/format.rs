@@ -1,16 +1,66 @@
-use super::Style;
+use super::{ColorFormat, Style};
 
 /// Specifies the format for outputing css.
 ///
-/// The format is the style (expanded or compressed) and the precision
-/// for numeric values.
+/// The format is the style (expanded or compressed), the precision
+/// for numeric values, and how to write colors.
+///
+/// Create a `Format` with [`Format::new`] and the `with_` methods
+/// for the options you care about, the rest get their default values.
+///
+/// ```
+/// use rsass::output::{ColorFormat, Format, Style};
+/// let format = Format::new(Style::Compressed, 5)
+///     .with_colors(ColorFormat::Hex)
+///     .with_hex_alpha(true);
+/// assert!(format.is_compressed());
+/// ```
+///
+/// [`Format::new`]: #method.new
 #[derive(Clone, Copy, Debug)]
 pub struct Format {
     pub style: Style,
     pub precision: usize,
+    /// How to write colors.
+    colors: ColorFormat,
+    /// Write translucent colors as `#rrggbbaa` rather than `rgba(...)`.
+    ///
+    /// This does not apply to the `rgb()` color format.
+    hex_alpha: bool,
 }
 
 impl Format {
+    /// A format with the given style and precision, and default
+    /// values for everything else.
+    pub fn new(style: Style, precision: usize) -> Format {
+        Format {
+            style,
+            precision,
+            ..Default::default()
+        }
+    }
+    /// This format, but with `colors` as the way to write colors.
+    pub fn with_colors(self, colors: ColorFormat) -> Format {
+        Format { colors, ..self }
+    }
+    /// This format, but writing translucent colors as `#rrggbbaa` if
+    /// `hex_alpha` is true.
+    pub fn with_hex_alpha(self, hex_alpha: bool) -> Format {
+        Format { hex_alpha, ..self }
+    }
+    /// A format with the given style and the fixed precision of
+    /// dart-sass, which always writes up to 10 decimals.
+    pub fn dart_sass(style: Style) -> Format {
+        Format::new(style, 10)
+    }
+    /// How to write colors.
+    pub fn colors(&self) -> ColorFormat {
+        self.colors
+    }
+    /// True if translucent colors are written as `#rrggbbaa`.
+    pub fn hex_alpha(&self) -> bool {
+        self.hex_alpha
+    }
     pub fn is_compressed(&self) -> bool {
         self.style == Style::Compressed
     }
@@ -21,6 +71,8 @@ impl Default for Format {
         Format {
             style: Style::Expanded,
             precision: 6,
+            colors: ColorFormat::Auto,
+            hex_alpha: false,
         }
     }
 }
@@ -34,3 +86,23 @@ pub struct Formatted<'a, T> {
     pub value: &'a T,
     pub format: Format,
 }
+
+#[test]
+fn new_has_default_options() {
+    let format = Format::new(Style::Compressed, 3);
+    assert_eq!(format.style, Style::Compressed);
+    assert_eq!(format.precision, 3);
+    assert_eq!(format.colors(), ColorFormat::Auto);
+    assert_eq!(format.hex_alpha(), false);
+}
+
+#[test]
+fn with_options() {
+    let format = Format::new(Style::Compressed, 3)
+        .with_colors(ColorFormat::Hex)
+        .with_hex_alpha(true);
+    assert_eq!(format.style, Style::Compressed);
+    assert_eq!(format.precision, 3);
+    assert_eq!(format.colors(), ColorFormat::Hex);
+    assert_eq!(format.hex_alpha(), true);
+}
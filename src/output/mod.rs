@@ -1,6 +1,8 @@
 //! Types describing how to format output.
+mod color_format;
 mod format;
 mod style;
 
+pub use color_format::ColorFormat;
 pub use format::{Format, Formatted};
 pub use style::Style;
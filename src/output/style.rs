@@ -1,11 +1,13 @@
 use super::Format;
-use crate::css::Value;
+use crate::css::{merge_lists, MediaQuery, Value};
 use crate::error::Error;
 use crate::file_context::FileContext;
+use crate::functions::get_builtin_module;
 use crate::parser::parse_scss_file;
-use crate::sass::{FormalArgs, Item};
+use crate::sass::{self, AtRootQuery, FormalArgs, Item};
 use crate::selectors::Selectors;
-use crate::variablescope::{Scope, ScopeImpl};
+use crate::value::Quotes;
+use crate::variablescope::{GlobalScope, Scope, ScopeImpl};
 use std::fmt;
 use std::io::Write;
 use std::str::FromStr;
@@ -138,6 +140,9 @@ impl Format {
                     }
                 }
             }
+            Item::Use(ref name, ref namespace) => {
+                use_module(name, namespace.as_ref(), scope)?
+            }
             Item::VariableDeclaration {
                 ref name,
                 ref val,
@@ -154,74 +159,36 @@ impl Format {
                 }
             }
             Item::AtRoot {
+                ref query,
                 ref selectors,
                 ref body,
             } => {
-                let selectors = selectors
-                    .eval(scope)?
-                    .with_backref(scope.get_selectors().one());
-                let mut s1 = vec![];
-                let mut s2 = vec![];
-                self.handle_body(
-                    &mut s1,
-                    &mut s2,
-                    &mut ScopeImpl::sub_selectors(scope, selectors.clone()),
+                self.write_at_root(
+                    query,
+                    selectors,
                     body,
+                    result.to_content(),
+                    scope,
                     file_context,
                     0,
                 )?;
-
-                if !s1.is_empty() {
-                    if self.is_compressed() {
-                        write!(result.to_content(), "{:#}{{", selectors)?;
-                    } else {
-                        write!(result.to_content(), "{} {{", selectors)?;
-                    }
-                    self.write_items(result.to_content(), &s1, 2)?;
-                    write!(result.to_content(), "}}")?;
-                    self.do_indent(result.to_content(), 0)?;
-                }
-                if !s2.is_empty() {
-                    result.to_content().write_all(&s2)?;
-                }
             }
-            Item::AtRule {
-                ref name,
-                ref args,
-                ref body,
-            } => {
-                result.do_separate()?;
-                let args = args.evaluate(scope)?;
-                write!(result.to_content(), "@{}", name)?;
-                if !args.is_null() {
-                    write!(result.to_content(), " {}", args.format(*self))?;
-                }
-                if let Some(ref body) = *body {
-                    if self.is_compressed() {
-                        write!(result.to_content(), "{{")?;
-                    } else {
-                        write!(result.to_content(), " {{")?;
-                    }
-                    let mut direct = vec![];
-                    let mut sub = vec![];
-                    self.handle_body(
-                        &mut direct,
-                        &mut sub,
-                        &mut ScopeImpl::sub(scope),
-                        body,
-                        file_context,
-                        2,
-                    )?;
-                    self.write_items(result.to_content(), &direct, 2)?;
-                    if !sub.is_empty() {
-                        if direct.is_empty() {
-                            result.do_indent(0)?;
-                        }
-                        result.to_content().write_all(&sub)?;
+            Item::AtRule { .. } | Item::Supports { .. } => {
+                let mut css = vec![];
+                self.write_at_rule_item(
+                    item,
+                    &mut css,
+                    scope,
+                    file_context,
+                    0,
+                )?;
+                if !css.is_empty() {
+                    result.do_separate()?;
+                    // Top-level items are separated by do_separate.
+                    if css.last() == Some(&b'\n') {
+                        css.pop();
                     }
-                    write!(result.to_content(), "}}")?;
-                } else {
-                    write!(result.to_content(), ";")?;
+                    result.to_content().write_all(&css)?;
                 }
             }
 
@@ -233,16 +200,25 @@ impl Format {
             Item::MixinCall {
                 ref name,
                 ref args,
+                ref body_args,
                 ref body,
             } => {
-                if let Some((m_args, m_body)) = scope.get_mixin(name) {
+                if let Some(loaded) =
+                    load_css(name, args, scope, file_context, *self)?
+                {
+                    let mut module = loaded.scope;
+                    for item in &loaded.items {
+                        self.handle_root_item(
+                            item,
+                            &mut module,
+                            &loaded.context,
+                            result,
+                        )?;
+                    }
+                } else if let Some((m_args, m_body)) = scope.get_mixin(name) {
                     let mut scope =
                         m_args.eval(scope, &args.evaluate(scope, true)?)?;
-                    scope.define_mixin(
-                        "%%BODY%%",
-                        &FormalArgs::default(),
-                        body,
-                    );
+                    scope.define_mixin("%%BODY%%", body_args, body);
                     for item in m_body {
                         self.handle_root_item(
                             &item,
@@ -258,10 +234,23 @@ impl Format {
                     )));
                 }
             }
-            Item::Content => {
-                return Err(Error::S(
-                    "@content not allowed in global context".into(),
-                ));
+            Item::Content(ref args) => {
+                if let Some((m_args, m_body)) = scope.get_mixin("%%BODY%%") {
+                    let mut scope =
+                        m_args.eval(scope, &args.evaluate(scope, true)?)?;
+                    for item in m_body {
+                        self.handle_root_item(
+                            &item,
+                            &mut scope,
+                            file_context,
+                            result,
+                        )?;
+                    }
+                } else {
+                    return Err(Error::S(
+                        "@content not allowed in global context".into(),
+                    ));
+                }
             }
 
             Item::FunctionDeclaration { ref name, ref func } => {
@@ -379,7 +368,8 @@ impl Format {
         file_context: &FileContext,
         indent: usize,
     ) -> Result<(), Error> {
-        let selectors = selectors.eval(scope)?.inside(scope.get_selectors());
+        let selectors =
+            selectors.eval(scope)?.inside(scope.get_selectors())?;
         let mut direct = Vec::new();
         let mut sub = Vec::new();
         self.handle_body(
@@ -405,6 +395,304 @@ impl Format {
         Ok(())
     }
 
+    /// Write the css of an `@at-root` rule.
+    ///
+    /// If the query excludes any enclosing at-rule, the css is instead
+    /// written after the outermost excluded at-rule, wrapped in the
+    /// at-rules inside it that are not excluded.
+    #[allow(clippy::too_many_arguments)]
+    fn write_at_root(
+        &self,
+        query: &AtRootQuery,
+        selectors: &Selectors,
+        body: &[Item],
+        out: &mut dyn Write,
+        scope: &mut dyn Scope,
+        file_context: &FileContext,
+        indent: usize,
+    ) -> Result<(), Error> {
+        let selectors = selectors.eval(scope)?;
+        let selectors = if query.excludes_style_rules() {
+            selectors.with_backref(scope.get_selectors().one())?
+        } else {
+            selectors.inside(scope.get_selectors())?
+        };
+        let at_rules = scope.get_at_rules();
+        let outer =
+            at_rules.iter().position(|r| query.excludes_name(&r.name));
+        let kept = match outer {
+            Some(outer) => at_rules[outer + 1..]
+                .iter()
+                .filter(|r| !query.excludes_name(&r.name))
+                .collect(),
+            None => vec![],
+        };
+        let indent = match outer {
+            Some(outer) => at_rules[outer].indent + 2 * kept.len(),
+            None => indent,
+        };
+
+        let mut css = vec![];
+        let mut s1 = vec![];
+        let mut s2 = vec![];
+        self.handle_body(
+            &mut s1,
+            &mut s2,
+            &mut ScopeImpl::sub_selectors(scope, selectors.clone()),
+            body,
+            file_context,
+            indent,
+        )?;
+        if !s1.is_empty() && selectors.s.iter().all(|s| s.0.is_empty()) {
+            return Err(Error::S(
+                "Declarations may only be used within style rules".into(),
+            ));
+        }
+        if !s1.is_empty() {
+            self.do_indent_no_lf(&mut css, indent)?;
+            if self.is_compressed() {
+                write!(css, "{:#}{{", selectors)?;
+            } else {
+                write!(css, "{} {{", selectors)?;
+            }
+            self.write_items(&mut css, &s1, indent + 2)?;
+            write!(css, "}}")?;
+            self.do_indent(&mut css, 0)?;
+        }
+        css.extend(s2);
+
+        match outer {
+            Some(outer) if !css.is_empty() => {
+                let base = at_rules[outer].indent;
+                for (depth, rule) in kept.iter().enumerate().rev() {
+                    let mut wrapped = vec![];
+                    self.write_at_rule_block(
+                        &mut wrapped,
+                        &rule.name,
+                        &rule.args,
+                        &[],
+                        &css,
+                        &selectors,
+                        base + 2 * depth,
+                    )?;
+                    css = wrapped;
+                }
+                at_rules[outer].escape(&css);
+            }
+            _ => out.write_all(&css)?,
+        }
+        Ok(())
+    }
+
+    /// Write an `Item::AtRule` or `Item::Supports`.
+    ///
+    /// The arguments or condition of the rule is evaluated here, so
+    /// that both kinds are written (and bubbled) the same way.
+    fn write_at_rule_item(
+        &self,
+        item: &Item,
+        out: &mut dyn Write,
+        scope: &mut dyn Scope,
+        file_context: &FileContext,
+        indent: usize,
+    ) -> Result<(), Error> {
+        match item {
+            Item::AtRule { name, args, body } => {
+                let args = args.evaluate(scope)?;
+                self.write_at_rule(
+                    name,
+                    args,
+                    body.as_ref().map(Vec::as_slice),
+                    out,
+                    scope,
+                    file_context,
+                    indent,
+                )
+            }
+            Item::Supports { condition, body } => {
+                let args =
+                    Value::Literal(condition.evaluate(scope)?, Quotes::None);
+                self.write_at_rule(
+                    "supports",
+                    args,
+                    Some(body),
+                    out,
+                    scope,
+                    file_context,
+                    indent,
+                )
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    /// Write an at-rule, like `@media print { ... }`.
+    ///
+    /// A `@media` rule inside another `@media` rule gets the merged
+    /// query, and is written after the enclosing rule rather than
+    /// inside it.  If no media can match the merged query, nothing is
+    /// written.  If the merged query can't be expressed, the rule is
+    /// kept nested.
+    #[allow(clippy::too_many_arguments)]
+    fn write_at_rule(
+        &self,
+        name: &str,
+        args: Value,
+        body: Option<&[Item]>,
+        out: &mut dyn Write,
+        scope: &mut dyn Scope,
+        file_context: &FileContext,
+        indent: usize,
+    ) -> Result<(), Error> {
+        let mut args = args;
+        let body = if let Some(body) = body {
+            body
+        } else {
+            self.do_indent_no_lf(out, indent)?;
+            write!(out, "@{}", name)?;
+            if !args.is_null() {
+                write!(out, " {}", args.format(*self))?;
+            }
+            write!(out, ";")?;
+            self.do_indent(out, 0)?;
+            return Ok(());
+        };
+        let at_rules = scope.get_at_rules();
+        let mut target = None;
+        let mut indent = indent;
+        if name.eq_ignore_ascii_case("media") {
+            if let Some(parent) =
+                at_rules.last().filter(|r| r.name == "media")
+            {
+                match merge_media(&parent.args, &args) {
+                    Some(Some(merged)) => {
+                        args = merged;
+                        target = Some(parent);
+                        indent = parent.indent;
+                    }
+                    Some(None) => return Ok(()),
+                    None => (),
+                }
+            }
+        }
+
+        // Keyframe blocks and declarations in eg. `@font-face` don't
+        // belong to any enclosing style rule.
+        let selectors = if ignores_style_rules(name) {
+            Some(Selectors::root())
+        } else {
+            None
+        };
+        let mut at_scope = ScopeImpl::sub_at_rule(
+            scope,
+            name,
+            args.clone(),
+            selectors,
+            indent,
+        );
+        let mut css = vec![];
+        let mut direct = vec![];
+        let mut sub = vec![];
+        let mut escaped_any = false;
+        for item in body {
+            self.handle_body(
+                &mut direct,
+                &mut sub,
+                &mut at_scope,
+                std::slice::from_ref(item),
+                file_context,
+                indent + 2,
+            )?;
+            // Css that breaks out of this rule goes after what is
+            // before it in the body, so the rule is split there.
+            let escaped = at_scope.take_escaped();
+            if !escaped.is_empty() {
+                if !direct.is_empty() || !sub.is_empty() {
+                    self.write_at_rule_block(
+                        &mut css,
+                        name,
+                        &args,
+                        &direct,
+                        &sub,
+                        at_scope.get_selectors(),
+                        indent,
+                    )?;
+                    direct.clear();
+                    sub.clear();
+                }
+                css.extend(escaped);
+                escaped_any = true;
+            }
+        }
+        if !direct.is_empty()
+            || !sub.is_empty()
+            || !(escaped_any || hidden_when_empty(name))
+        {
+            self.write_at_rule_block(
+                &mut css,
+                name,
+                &args,
+                &direct,
+                &sub,
+                at_scope.get_selectors(),
+                indent,
+            )?;
+        }
+        match target {
+            Some(target) => target.escape(&css),
+            None => out.write_all(&css)?,
+        }
+        Ok(())
+    }
+
+    /// Write a single block of an at-rule.
+    ///
+    /// Any `direct` properties are wrapped in the `selectors`, unless
+    /// the at-rule is at the root.
+    #[allow(clippy::too_many_arguments)]
+    fn write_at_rule_block(
+        &self,
+        out: &mut dyn Write,
+        name: &str,
+        args: &Value,
+        direct: &[CssBodyItem],
+        sub: &[u8],
+        selectors: &Selectors,
+        indent: usize,
+    ) -> Result<(), Error> {
+        self.do_indent_no_lf(out, indent)?;
+        write!(out, "@{}", name)?;
+        if !args.is_null() {
+            write!(out, " {}", args.format(*self))?;
+        }
+        if self.is_compressed() {
+            write!(out, "{{")?;
+        } else {
+            write!(out, " {{")?;
+        }
+        if *selectors == Selectors::root() && !direct.is_empty() {
+            self.write_items(out, direct, indent + 2)?;
+            out.write_all(sub)?;
+        } else if !sub.is_empty() || !direct.is_empty() {
+            if !direct.is_empty() {
+                self.do_indent(out, indent + 2)?;
+                if self.is_compressed() {
+                    write!(out, "{:#}{{", selectors)?;
+                } else {
+                    write!(out, "{} {{", selectors)?;
+                }
+                self.write_items(out, direct, indent + 4)?;
+                write!(out, "}}")?;
+            }
+            self.do_indent(out, 0)?;
+            out.write_all(sub)?;
+            self.do_indent_no_lf(out, indent)?;
+        }
+        write!(out, "}}")?;
+        self.do_indent(out, 0)?;
+        Ok(())
+    }
+
     fn handle_body(
         &self,
         direct: &mut Vec<CssBodyItem>,
@@ -430,7 +718,7 @@ impl Format {
                                     scope,
                                     &items,
                                     &sub_context,
-                                    0,
+                                    indent,
                                 )?;
                             } else {
                                 write!(
@@ -457,6 +745,9 @@ impl Format {
                         }
                     }
                 }
+                Item::Use(ref name, ref namespace) => {
+                    use_module(name, namespace.as_ref(), scope)?
+                }
                 Item::VariableDeclaration {
                     ref name,
                     ref val,
@@ -473,90 +764,28 @@ impl Format {
                     }
                 }
                 Item::AtRoot {
+                    ref query,
                     ref selectors,
                     ref body,
                 } => {
-                    let selectors = selectors
-                        .eval(scope)?
-                        .with_backref(scope.get_selectors().one());
-                    let mut s1 = vec![];
-                    let mut s2 = vec![];
-                    self.handle_body(
-                        &mut s1,
-                        &mut s2,
-                        &mut ScopeImpl::sub_selectors(
-                            scope,
-                            selectors.clone(),
-                        ),
+                    self.write_at_root(
+                        query,
+                        selectors,
                         body,
+                        sub,
+                        scope,
                         file_context,
                         indent,
                     )?;
-
-                    if !s1.is_empty() {
-                        if indent > 0 {
-                            self.do_indent(sub, indent)?;
-                        }
-                        if self.is_compressed() {
-                            write!(sub, "{:#}{{", selectors)?;
-                        } else {
-                            write!(sub, "{} {{", selectors)?;
-                        }
-                        self.write_items(sub, &s1, indent + 2)?;
-                        write!(sub, "}}")?;
-                        self.do_indent(sub, 0)?;
-                    }
-                    if !s2.is_empty() {
-                        sub.write_all(&s2)?;
-                    }
                 }
-                Item::AtRule {
-                    ref name,
-                    ref args,
-                    ref body,
-                } => {
-                    write!(sub, "@{}", name)?;
-                    let args = args.evaluate(scope)?;
-                    if !args.is_null() {
-                        write!(sub, " {}", args.format(*self))?;
-                    }
-                    if let Some(ref body) = *body {
-                        if self.is_compressed() {
-                            write!(sub, "{{")?;
-                        } else {
-                            write!(sub, " {{")?;
-                        }
-
-                        let mut s1 = vec![];
-                        let mut s2 = vec![];
-                        self.handle_body(
-                            &mut s1,
-                            &mut s2,
-                            &mut ScopeImpl::sub(scope),
-                            body,
-                            file_context,
-                            2,
-                        )?;
-
-                        if !s1.is_empty() {
-                            self.do_indent(sub, 2)?;
-                            if self.is_compressed() {
-                                write!(sub, "{:#}{{", scope.get_selectors())?;
-                            } else {
-                                write!(sub, "{} {{", scope.get_selectors())?;
-                            }
-                            self.write_items(sub, &s1, 4)?;
-                            write!(sub, "}}")?;
-                        }
-                        self.do_indent(sub, 0)?;
-                        if !s2.is_empty() {
-                            sub.write_all(&s2)?;
-                        }
-                        write!(sub, "}}")?;
-                        self.do_indent(sub, 0)?;
-                    } else {
-                        write!(sub, ";")?;
-                    }
+                Item::AtRule { .. } | Item::Supports { .. } => {
+                    self.write_at_rule_item(
+                        b,
+                        sub,
+                        scope,
+                        file_context,
+                        indent,
+                    )?;
                 }
 
                 Item::MixinDeclaration {
@@ -569,16 +798,30 @@ impl Format {
                 Item::MixinCall {
                     ref name,
                     ref args,
+                    ref body_args,
                     ref body,
                 } => {
-                    if let Some((m_args, m_body)) = scope.get_mixin(name) {
+                    if let Some(loaded) =
+                        load_css(name, args, scope, file_context, *self)?
+                    {
+                        let mut module = ScopeImpl::sub_selectors(
+                            &loaded.scope,
+                            scope.get_selectors().clone(),
+                        );
+                        self.handle_body(
+                            direct,
+                            sub,
+                            &mut module,
+                            &loaded.items,
+                            &loaded.context,
+                            indent,
+                        )?;
+                    } else if let Some((m_args, m_body)) =
+                        scope.get_mixin(name)
+                    {
                         let mut argscope = m_args
                             .eval(scope, &args.evaluate(scope, true)?)?;
-                        argscope.define_mixin(
-                            "%%BODY%%",
-                            &FormalArgs::default(),
-                            body,
-                        );
+                        argscope.define_mixin("%%BODY%%", body_args, body);
                         self.handle_body(
                             direct,
                             sub,
@@ -594,13 +837,16 @@ impl Format {
                         )));
                     }
                 }
-                Item::Content => {
-                    if let Some((_args, m_body)) = scope.get_mixin("%%BODY%%")
+                Item::Content(ref args) => {
+                    if let Some((m_args, m_body)) =
+                        scope.get_mixin("%%BODY%%")
                     {
+                        let mut argscope = m_args
+                            .eval(scope, &args.evaluate(scope, true)?)?;
                         self.handle_body(
                             direct,
                             sub,
-                            scope,
+                            &mut argscope,
                             &m_body,
                             file_context,
                             indent,
@@ -624,7 +870,7 @@ impl Format {
                         &mut ScopeImpl::sub(scope),
                         items,
                         file_context,
-                        0,
+                        indent,
                     )?;
                 }
                 Item::Each(ref names, ref values, ref body) => {
@@ -637,7 +883,7 @@ impl Format {
                             &mut scope,
                             body,
                             file_context,
-                            0,
+                            indent,
                         )?;
                     }
                 }
@@ -660,7 +906,7 @@ impl Format {
                             &mut scope,
                             body,
                             file_context,
-                            0,
+                            indent,
                         )?;
                     }
                 }
@@ -685,7 +931,7 @@ impl Format {
                             &mut scope,
                             body,
                             file_context,
-                            0,
+                            indent,
                         )?;
                     }
                 }
@@ -787,6 +1033,179 @@ impl Format {
     }
 }
 
+/// Merge the queries of a `@media` rule with those of an enclosing
+/// `@media` rule.
+///
+/// Gives `Some(None)` if no media can match both, and `None` if the
+/// merged queries can't be expressed or parsed.
+fn merge_media(outer: &Value, inner: &Value) -> Option<Option<Value>> {
+    let format = Format::default();
+    let outer = MediaQuery::parse_list(&outer.format(format).to_string())?;
+    let inner = MediaQuery::parse_list(&inner.format(format).to_string())?;
+    let merged = merge_lists(&outer, &inner)?;
+    if merged.is_empty() {
+        return Some(None);
+    }
+    let merged = merged
+        .iter()
+        .map(|q| q.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    Some(Some(Value::Literal(merged, Quotes::None)))
+}
+
+/// True for at-rules that are not written at all when their body is
+/// empty.
+fn hidden_when_empty(name: &str) -> bool {
+    name.eq_ignore_ascii_case("media")
+        || name.eq_ignore_ascii_case("supports")
+}
+
+/// True for at-rules whose body is not nested in enclosing style
+/// rules, i.e. `@keyframes` (with any vendor prefix), and at-rules that
+/// contains declarations of their own, like `@font-face` and `@page`.
+fn ignores_style_rules(name: &str) -> bool {
+    let name = name.to_lowercase();
+    let unvendored = if name.starts_with('-') {
+        name[1..].find('-').map_or(&name[..], |i| &name[i + 2..])
+    } else {
+        &name[..]
+    };
+    unvendored == "keyframes"
+        || unvendored == "viewport"
+        || [
+            "font-face",
+            "page",
+            "counter-style",
+            "property",
+            "font-feature-values",
+            "font-palette-values",
+        ]
+        .contains(&&name[..])
+}
+
+/// Handle a `@use` directive.
+///
+/// Only the builtin `sass:` modules are supported so far.  Their
+/// functions are always available by their default namespace, so
+/// using them only requires checking that they exist.
+///
+/// The namespace is remembered for the `load-css` mixin, see
+/// [`used_module`].
+fn use_module(
+    name: &crate::sass::Value,
+    namespace: Option<&String>,
+    scope: &dyn Scope,
+) -> Result<(), Error> {
+    let name = name.evaluate(scope)?.unquote();
+    if let Value::Literal(ref name, _) = name {
+        if name.starts_with("sass:") {
+            let module = &name["sass:".len()..];
+            if get_builtin_module(module).is_some() {
+                let namespace = namespace.map_or(module, |ns| ns.as_ref());
+                scope.define_global(
+                    &used_module_var(namespace),
+                    &Value::Literal(module.into(), Quotes::None),
+                );
+                return Ok(());
+            }
+        }
+    }
+    Err(Error::S(format!(
+        "Can't find module {}",
+        name.format(Default::default())
+    )))
+}
+
+/// Get the name of the module used by `namespace`, if any.
+fn used_module(namespace: &str, scope: &dyn Scope) -> Option<String> {
+    match scope.get_global_or_none(&used_module_var(namespace)) {
+        Some(Value::Literal(module, _)) => Some(module),
+        _ => None,
+    }
+}
+
+/// The used modules are kept as global variables, with a name that
+/// can't be used in sass.
+fn used_module_var(namespace: &str) -> String {
+    format!("%use.{}", namespace.replace('-', "_"))
+}
+
+/// A stylesheet loaded by the `meta.load-css` mixin.
+struct LoadedCss {
+    context: FileContext,
+    items: Vec<Item>,
+    scope: GlobalScope,
+}
+
+/// Load a stylesheet if `name` is the `load-css` mixin of a used
+/// `sass:meta` module, e.g. `meta.load-css`.
+///
+/// The stylesheet gets a global scope of its own, where the variables
+/// in the `$with` map are defined before any `!default` variables.
+/// Loading a builtin module is allowed, but gives no css.
+fn load_css(
+    name: &str,
+    args: &sass::CallArgs,
+    scope: &dyn Scope,
+    file_context: &FileContext,
+    format: Format,
+) -> Result<Option<LoadedCss>, Error> {
+    let name = name.replace('-', "_");
+    let mut parts = name.splitn(2, '.');
+    match (parts.next(), parts.next()) {
+        (Some(namespace), Some("load_css"))
+            if used_module(namespace, scope).as_ref().map(String::as_ref)
+                == Some("meta") => {}
+        _ => return Ok(None),
+    }
+    let formal = FormalArgs::new(
+        vec![
+            ("url".into(), None),
+            ("with".into(), Some(sass::Value::Null)),
+        ],
+        false,
+    );
+    let args = formal.eval(scope, &args.evaluate(scope, true)?)?;
+    let url = match args.get("url")? {
+        Value::Literal(url, _) => url,
+        v => return Err(Error::badarg("string", &v)),
+    };
+    let mut module = GlobalScope::new(format);
+    module.set_seed(scope.next_random());
+    match args.get("with")? {
+        Value::Null => (),
+        Value::Map(with) => {
+            for (name, value) in with {
+                match name {
+                    Value::Literal(name, _) => module.define(&name, &value),
+                    name => return Err(Error::badarg("string", &name)),
+                }
+            }
+        }
+        v => return Err(Error::badarg("map", &v)),
+    }
+    let (context, items) = if url.starts_with("sass:") {
+        if get_builtin_module(&url["sass:".len()..]).is_none() {
+            return Err(Error::S(format!("Can't find module {:?}", url)));
+        }
+        (file_context.clone(), vec![])
+    } else if let Some((context, file)) = file_context.find_file(url.as_ref())
+    {
+        (context, parse_scss_file(&file)?)
+    } else {
+        return Err(Error::S(format!(
+            "Can't find stylesheet to load: {:?}",
+            url
+        )));
+    };
+    Ok(Some(LoadedCss {
+        context,
+        items,
+        scope: module,
+    }))
+}
+
 struct CssWriter {
     imports: Vec<u8>,
     contents: Vec<u8>,
@@ -1,6 +1,6 @@
 use super::strings::name;
 use super::util::{ignore_comments, opt_spacelike};
-use super::value::space_list;
+use super::value::{single_expression, space_list};
 use crate::sass::{CallArgs, FormalArgs, Value};
 use nom::branch::alt;
 use nom::bytes::complete::tag;
@@ -22,7 +22,7 @@ pub fn formal_args(input: &[u8]) -> IResult<&[u8], FormalArgs> {
                     opt_spacelike,
                 )),
             ),
-            |(name, d)| (name.replace('-', "_"), d.unwrap_or(Value::Null)),
+            |(name, d)| (name.replace('-', "_"), d),
         ),
     )(input)?;
     let (input, _) = terminated(opt(tag(",")), opt_spacelike)(input)?;
@@ -35,21 +35,53 @@ pub fn call_args(input: &[u8]) -> IResult<&[u8], CallArgs> {
     let (input, _) = tag("(")(input)?;
     let (input, v) = separated_list(
         delimited(opt_spacelike, tag(","), opt_spacelike),
-        pair(
-            opt(delimited(
-                tag("$"),
-                map(name, |n: String| n.replace("-", "_")),
-                preceded(ignore_comments, tag(":")),
-            )),
-            alt((
-                space_list,
-                delimited(ignore_comments, space_list, ignore_comments),
-            )),
-        ),
+        alt((
+            map(
+                delimited(
+                    ignore_comments,
+                    single_expression,
+                    preceded(opt_spacelike, tag("...")),
+                ),
+                CallArg::Rest,
+            ),
+            map(
+                pair(
+                    opt(delimited(
+                        tag("$"),
+                        map(name, |n: String| n.replace("-", "_")),
+                        preceded(ignore_comments, tag(":")),
+                    )),
+                    alt((
+                        space_list,
+                        delimited(
+                            ignore_comments,
+                            space_list,
+                            ignore_comments,
+                        ),
+                    )),
+                ),
+                |(name, value)| CallArg::Single(name, value),
+            ),
+        )),
     )(input)?;
     let (input, _) = preceded(
         opt(delimited(opt_spacelike, opt(tag(",")), opt_spacelike)),
         tag(")"),
     )(input)?;
-    Ok((input, CallArgs::new(v)))
+    let mut args = vec![];
+    let mut rest = vec![];
+    for arg in v {
+        match arg {
+            CallArg::Single(name, value) => args.push((name, value)),
+            CallArg::Rest(value) => rest.push(value),
+        }
+    }
+    Ok((input, CallArgs::new_with_rest(args, rest)))
+}
+
+/// A single argument in `call_args`.
+enum CallArg {
+    Single(Option<String>, Value),
+    /// An argument to be spread, as in `$args...`.
+    Rest(Value),
 }
@@ -1,6 +1,7 @@
 pub mod formalargs;
 pub mod selectors;
 mod strings;
+mod supports;
 mod unit;
 mod util;
 pub mod value;
@@ -8,6 +9,7 @@ pub mod value;
 use self::formalargs::{call_args, formal_args};
 use self::selectors::selectors;
 use self::strings::{name, sass_string, sass_string_dq, sass_string_sq};
+use self::supports::supports_condition;
 use self::util::{
     comment2, ignore_comments, ignore_space, opt_spacelike, spacelike,
 };
@@ -16,9 +18,9 @@ use self::value::{
 };
 use crate::error::{ErrPos, Error};
 use crate::functions::SassFunction;
+use crate::sass::{AtRootQuery, Item, Value};
 #[cfg(test)]
 use crate::sass::{CallArgs, FormalArgs};
-use crate::sass::{Item, Value};
 use crate::selectors::Selectors;
 use crate::value::ListSeparator;
 #[cfg(test)]
@@ -104,6 +106,8 @@ fn top_level_item(input: &[u8]) -> IResult<&[u8], Item> {
         tag("@import"),
         tag("@include"),
         tag("@mixin"),
+        tag("@supports"),
+        tag("@use"),
         tag("@warn"),
         tag("@while"),
         tag("@"),
@@ -120,6 +124,8 @@ fn top_level_item(input: &[u8]) -> IResult<&[u8], Item> {
         b"@import" => import2(input),
         b"@include" => mixin_call2(input),
         b"@mixin" => mixin_declaration2(input),
+        b"@supports" => supports2(input),
+        b"@use" => use2(input),
         b"@warn" => warn2(input),
         b"@while" => while_loop2(input),
         b"@" => at_rule2(input),
@@ -163,6 +169,7 @@ fn body_item(input: &[u8]) -> IResult<&[u8], Item> {
         tag("@include"),
         tag("@mixin"),
         tag("@return"),
+        tag("@supports"),
         tag("@warn"),
         tag("@while"),
         tag("@"),
@@ -183,6 +190,7 @@ fn body_item(input: &[u8]) -> IResult<&[u8], Item> {
         b"@include" => mixin_call2(input),
         b"@mixin" => mixin_declaration2(input),
         b"@return" => return_stmt2(input),
+        b"@supports" => supports2(input),
         b"@warn" => warn2(input),
         b"@while" => while_loop2(input),
         b"@" => at_rule2(input),
@@ -217,20 +225,107 @@ fn import2(input: &[u8]) -> IResult<&[u8], Item> {
     )(input)
 }
 
+/// What follows the `@use` tag.
+fn use2(input: &[u8]) -> IResult<&[u8], Item> {
+    map(
+        delimited(
+            spacelike,
+            pair(
+                single_value,
+                opt(preceded(
+                    delimited(spacelike, tag("as"), spacelike),
+                    name,
+                )),
+            ),
+            preceded(opt_spacelike, tag(";")),
+        ),
+        |(url, namespace)| Item::Use(url, namespace),
+    )(input)
+}
+
 /// What follows the `@at-root` tag.
 fn at_root2(input: &[u8]) -> IResult<&[u8], Item> {
-    preceded(
+    let (input, query) = preceded(
         opt_spacelike,
-        map(
+        opt(terminated(at_root_query, opt_spacelike)),
+    )(input)?;
+    map(
+        pair(
+            map(opt(selectors), |s| s.unwrap_or_else(Selectors::root)),
+            body_block,
+        ),
+        move |(selectors, body)| Item::AtRoot {
+            query: query.clone().unwrap_or_default(),
+            selectors,
+            body,
+        },
+    )(input)
+}
+
+/// What follows the `@supports` tag.
+fn supports2(input: &[u8]) -> IResult<&[u8], Item> {
+    map(
+        pair(
+            delimited(opt_spacelike, supports_condition, opt_spacelike),
+            body_block,
+        ),
+        |(condition, body)| Item::Supports { condition, body },
+    )(input)
+}
+
+/// A query like `(without: media supports)` for `@at-root`.
+fn at_root_query(input: &[u8]) -> IResult<&[u8], AtRootQuery> {
+    map(
+        delimited(
+            terminated(tag("("), opt_spacelike),
             pair(
-                map(opt(selectors), |s| s.unwrap_or_else(Selectors::root)),
-                body_block,
+                terminated(
+                    alt((
+                        value(false, tag("without")),
+                        value(true, tag("with")),
+                    )),
+                    delimited(opt_spacelike, tag(":"), opt_spacelike),
+                ),
+                separated_nonempty_list(
+                    spacelike,
+                    alt((
+                        name,
+                        delimited(tag("\""), name, tag("\"")),
+                        delimited(tag("'"), name, tag("'")),
+                    )),
+                ),
             ),
-            |(selectors, body)| Item::AtRoot { selectors, body },
+            preceded(opt_spacelike, tag(")")),
         ),
+        |(include, names)| AtRootQuery::new(include, names),
     )(input)
 }
 
+#[test]
+fn test_at_root_query() {
+    assert_eq!(
+        at_root_query(b"(without: media \"supports\") {"),
+        Ok((
+            &b" {"[..],
+            AtRootQuery::new(false, vec!["media".into(), "supports".into()]),
+        ))
+    );
+}
+#[test]
+fn test_at_root_with_query() {
+    assert_eq!(
+        at_root2(b" ( with : rule ) {}"),
+        Ok((
+            &b""[..],
+            Item::AtRoot {
+                query: AtRootQuery::new(true, vec!["rule".into()]),
+                selectors: Selectors::root(),
+                body: vec![],
+            }
+        ))
+    );
+}
+
 #[cfg(test)] // TODO: Or remove this?
 fn mixin_call(input: &[u8]) -> IResult<&[u8], Item> {
     preceded(tag("@include"), mixin_call2)(input)
@@ -238,8 +333,16 @@ fn mixin_call(input: &[u8]) -> IResult<&[u8], Item> {
 
 /// What follows the `@include` tag.
 fn mixin_call2(input: &[u8]) -> IResult<&[u8], Item> {
-    let (input, name) = delimited(spacelike, name, opt_spacelike)(input)?;
+    let (input, name) =
+        delimited(spacelike, qualified_name, opt_spacelike)(input)?;
     let (input, args) = terminated(opt(call_args), opt_spacelike)(input)?;
+    let (input, body_args) = terminated(
+        opt(preceded(
+            terminated(tag("using"), opt_spacelike),
+            formal_args,
+        )),
+        opt_spacelike,
+    )(input)?;
     let (input, body) = terminated(
         opt(body_block),
         terminated(opt_spacelike, opt(tag(";"))),
@@ -249,11 +352,23 @@ fn mixin_call2(input: &[u8]) -> IResult<&[u8], Item> {
         Item::MixinCall {
             name,
             args: args.unwrap_or_default(),
+            body_args: body_args.unwrap_or_default(),
             body: body.unwrap_or_default(),
         },
     ))
 }
 
+/// A name, optionally with a module namespace, e.g. `meta.load-css`.
+fn qualified_name(input: &[u8]) -> IResult<&[u8], String> {
+    map(
+        pair(name, opt(preceded(tag("."), name))),
+        |(module, name)| match name {
+            Some(name) => format!("{}.{}", module, name),
+            None => module,
+        },
+    )(input)
+}
+
 /// What follows an `@` sign (unless specifically handled).
 fn at_rule2(input: &[u8]) -> IResult<&[u8], Item> {
     let (input, name) = name(input)?;
@@ -462,11 +577,13 @@ fn return_stmt2(input: &[u8]) -> IResult<&[u8], Item> {
     Ok((input, Item::Return(v)))
 }
 
-/// The "rest" of an `@content` statement is just an optional terminator
+/// The "rest" of an `@content` statement is optional arguments and
+/// an optional terminator.
 fn content_stmt2(input: &[u8]) -> IResult<&[u8], Item> {
     let (input, _) = opt_spacelike(input)?;
+    let (input, args) = terminated(opt(call_args), opt_spacelike)(input)?;
     let (input, _) = opt(tag(";"))(input)?;
-    Ok((input, Item::Content))
+    Ok((input, Item::Content(args.unwrap_or_default())))
 }
 
 fn property_or_namespace_rule(input: &[u8]) -> IResult<&[u8], Item> {
@@ -601,6 +718,7 @@ fn test_mixin_call_noargs() {
             Item::MixinCall {
                 name: "foo".to_string(),
                 args: CallArgs::new(vec![]),
+                body_args: FormalArgs::default(),
                 body: vec![],
             }
         ))
@@ -619,12 +737,46 @@ fn test_mixin_call_pos_args() {
                     (None, string("bar")),
                     (None, string("baz")),
                 ]),
+                body_args: FormalArgs::default(),
                 body: vec![],
             }
         ))
     )
 }
 
+#[test]
+fn test_mixin_call_rest_args_using() {
+    assert_eq!(
+        mixin_call(b"@include foo($a, $b...) using ($x) {}"),
+        Ok((
+            &b""[..],
+            Item::MixinCall {
+                name: "foo".to_string(),
+                args: CallArgs::new_with_rest(
+                    vec![(None, Value::Variable("a".into()))],
+                    vec![Value::Variable("b".into())],
+                ),
+                body_args: FormalArgs::new(vec![("x".into(), None)], false),
+                body: vec![],
+            }
+        ))
+    )
+}
+
+#[test]
+fn test_content_args() {
+    assert_eq!(
+        body_item(b"@content($x, $y: 1);"),
+        Ok((
+            &b""[..],
+            Item::Content(CallArgs::new(vec![
+                (None, Value::Variable("x".into())),
+                (Some("y".into()), Value::scalar(1)),
+            ]))
+        ))
+    )
+}
+
 #[test]
 fn test_mixin_call_named_args() {
     assert_eq!(
@@ -637,6 +789,7 @@ fn test_mixin_call_named_args() {
                     (Some("x".into()), string("bar")),
                     (Some("y".into()), string("baz")),
                 ]),
+                body_args: FormalArgs::default(),
                 body: vec![],
             }
         ))
@@ -666,7 +819,7 @@ fn test_mixin_declaration() {
             &b"\n"[..],
             Item::MixinDeclaration {
                 name: "foo".into(),
-                args: FormalArgs::new(vec![("x".into(), Value::Null)], false),
+                args: FormalArgs::new(vec![("x".into(), None)], false),
                 body: vec![Item::Property(
                     "foo-bar".into(),
                     Value::List(
@@ -698,8 +851,8 @@ fn test_mixin_declaration_default_and_subrules() {
                 name: "bar".into(),
                 args: FormalArgs::new(
                     vec![
-                        ("a".into(), Value::Null),
-                        ("b".into(), string("flug")),
+                        ("a".into(), None),
+                        ("b".into(), Some(string("flug"))),
                     ],
                     false
                 ),
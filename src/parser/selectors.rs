@@ -1,11 +1,16 @@
-use super::input_to_string;
-use super::strings::{sass_string, sass_string_dq, sass_string_sq};
+use super::strings::{
+    sass_string, sass_string_dq, sass_string_sq, selector_name, special_args,
+    string_part_interpolation,
+};
 use super::util::{opt_spacelike, spacelike2};
-use crate::selectors::{Selector, SelectorPart, Selectors};
+use super::{input_to_str, input_to_string};
+use crate::sass::{SassString, StringPart};
+use crate::selectors::{unprefixed, Selector, SelectorPart, Selectors};
+use crate::value::Quotes;
 use nom::branch::alt;
-use nom::bytes::complete::{is_a, tag};
-use nom::character::complete::one_of;
-use nom::combinator::{map, map_res, opt, value};
+use nom::bytes::complete::{is_a, tag, tag_no_case};
+use nom::character::complete::{digit0, digit1, one_of};
+use nom::combinator::{map, map_res, not, opt, peek, recognize, value};
 use nom::multi::{many1, separated_nonempty_list};
 use nom::sequence::{delimited, pair, preceded, terminated, tuple};
 use nom::IResult;
@@ -28,75 +33,30 @@ pub fn selector(input: &[u8]) -> IResult<&[u8], Selector> {
 
 fn selector_part(input: &[u8]) -> IResult<&[u8], SelectorPart> {
     alt((
-        map(sass_string, SelectorPart::Simple),
-        value(SelectorPart::Simple("*".into()), tag("*")),
-        map(
-            preceded(
-                tag("::"),
-                pair(
-                    sass_string,
-                    opt(delimited(tag("("), selectors, tag(")"))),
-                ),
-            ),
-            |(name, arg)| SelectorPart::PseudoElement { name, arg },
-        ),
-        map(
-            preceded(
-                tag(":"),
-                pair(
-                    sass_string,
-                    opt(delimited(tag("("), selectors, tag(")"))),
-                ),
-            ),
-            |(name, arg)| SelectorPart::Pseudo { name, arg },
-        ),
-        map(
-            delimited(
-                terminated(tag("["), opt_spacelike),
-                tuple((
-                    terminated(sass_string, opt_spacelike),
-                    terminated(
-                        map_res(
-                            alt((tag("*="), tag("|="), tag("="))),
-                            input_to_string,
-                        ),
-                        opt_spacelike,
-                    ),
-                    terminated(
-                        alt((sass_string_dq, sass_string_sq, sass_string)),
-                        opt_spacelike,
-                    ),
-                    opt(terminated(
-                        one_of(
-                            "ABCDEFGHIJKLMNOPQRSTUVWXYZ\
-                             abcdefghijklmnopqrstuvwxyz",
-                        ),
-                        opt_spacelike,
-                    )),
-                )),
-                tag("]"),
-            ),
-            |(name, op, val, modifier)| SelectorPart::Attribute {
+        map(keyframe_selector, SelectorPart::Percentage),
+        type_selector,
+        map(preceded(tag("."), selector_name), SelectorPart::Class),
+        map(preceded(tag("#"), selector_name), SelectorPart::Id),
+        map(preceded(tag("%"), selector_name), SelectorPart::Placeholder),
+        map(preceded(tag("::"), pseudo), |(name, arg, selector)| {
+            SelectorPart::PseudoElement {
                 name,
-                op,
-                val,
-                modifier,
-            },
-        ),
-        map(
-            delimited(
-                terminated(tag("["), opt_spacelike),
-                sass_string,
-                preceded(opt_spacelike, tag("]")),
-            ),
-            |name| SelectorPart::Attribute {
+                arg,
+                selector,
+            }
+        }),
+        map(preceded(tag(":"), pseudo), |(name, arg, selector)| {
+            SelectorPart::Pseudo {
                 name,
-                op: "".to_string(),
-                val: "".into(),
-                modifier: None,
-            },
+                arg,
+                selector,
+            }
+        }),
+        attribute,
+        map(
+            preceded(tag("&"), opt(selector_name)),
+            SelectorPart::BackRef,
         ),
-        value(SelectorPart::BackRef, tag("&")),
         delimited(
             opt_spacelike,
             alt((
@@ -111,6 +71,179 @@ fn selector_part(input: &[u8]) -> IResult<&[u8], SelectorPart> {
     ))(input)
 }
 
+/// A keyframe selector percentage, such as `50%` or `#{$i * 10}%`.
+///
+/// The result is the number, without the percent sign.
+/// The `from` and `to` keyframe selectors are plain type selectors.
+fn keyframe_selector(input: &[u8]) -> IResult<&[u8], SassString> {
+    map(
+        terminated(
+            many1(alt((
+                string_part_interpolation,
+                map(map_res(is_a("0123456789."), input_to_str), |s| {
+                    StringPart::from(s)
+                }),
+            ))),
+            tag("%"),
+        ),
+        |parts| SassString::new(parts, Quotes::None),
+    )(input)
+}
+
+/// A type selector or the universal selector, with optional namespace.
+fn type_selector(input: &[u8]) -> IResult<&[u8], SelectorPart> {
+    let (input, ns) = opt(namespace_prefix)(input)?;
+    let (input, name) = name_or_star(input)?;
+    Ok((input, SelectorPart::Type { ns, name }))
+}
+
+/// A namespace prefix, such as `svg|`, `*|` or just `|`.
+fn namespace_prefix(input: &[u8]) -> IResult<&[u8], SassString> {
+    terminated(
+        alt((name_or_star, value(SassString::from(""), tag("")))),
+        terminated(tag("|"), not(tag("="))),
+    )(input)
+}
+
+fn name_or_star(input: &[u8]) -> IResult<&[u8], SassString> {
+    alt((selector_name, value(SassString::from("*"), tag("*"))))(input)
+}
+
+fn attribute(input: &[u8]) -> IResult<&[u8], SelectorPart> {
+    let (input, _) = terminated(tag("["), opt_spacelike)(input)?;
+    let (input, ns) = opt(namespace_prefix)(input)?;
+    let (input, name) = terminated(selector_name, opt_spacelike)(input)?;
+    let (input, matcher) = opt(tuple((
+        terminated(
+            map_res(
+                alt((
+                    tag("="),
+                    tag("~="),
+                    tag("|="),
+                    tag("^="),
+                    tag("$="),
+                    tag("*="),
+                )),
+                input_to_string,
+            ),
+            opt_spacelike,
+        ),
+        terminated(
+            alt((sass_string_dq, sass_string_sq, sass_string)),
+            opt_spacelike,
+        ),
+        opt(terminated(
+            one_of(
+                "ABCDEFGHIJKLMNOPQRSTUVWXYZ\
+                 abcdefghijklmnopqrstuvwxyz",
+            ),
+            opt_spacelike,
+        )),
+    )))(input)?;
+    let (input, _) = tag("]")(input)?;
+    let (op, val, modifier) =
+        matcher.unwrap_or_else(|| (String::new(), "".into(), None));
+    Ok((
+        input,
+        SelectorPart::Attribute {
+            ns,
+            name,
+            op,
+            val,
+            modifier,
+        },
+    ))
+}
+
+/// The name, plain argument and selector argument of a pseudo selector.
+type Pseudo = (SassString, Option<SassString>, Option<Selectors>);
+
+/// The name and arguments of a pseudo-class or pseudo-element.
+fn pseudo(input: &[u8]) -> IResult<&[u8], Pseudo> {
+    let (input, name) = selector_name(input)?;
+    let (input, args) = opt(delimited(
+        terminated(tag("("), opt_spacelike),
+        |input| pseudo_args(input, &name),
+        tag(")"),
+    ))(input)?;
+    let (arg, selector) = args.unwrap_or((None, None));
+    Ok((input, (name, arg, selector)))
+}
+
+/// The arguments of a pseudo selector, depending on its name.
+///
+/// Arguments that don't fit the syntax expected for the name are
+/// kept as plain arguments.
+fn pseudo_args<'a>(
+    input: &'a [u8],
+    name: &SassString,
+) -> IResult<&'a [u8], (Option<SassString>, Option<Selectors>)> {
+    let plain = map(special_args, |arg| (Some(arg), None));
+    match name.single_raw().map(unprefixed) {
+        Some("is") | Some("matches") | Some("any") | Some("where")
+        | Some("not") | Some("has") | Some("host") | Some("host-context")
+        | Some("slotted") | Some("current") => alt((
+            map(before_end(selectors), |s| (None, Some(s))),
+            plain,
+        ))(input),
+        Some("nth-child") | Some("nth-last-child") => alt((
+            map(
+                before_end(pair(
+                    an_plus_b,
+                    opt(preceded(
+                        delimited(spacelike2, tag("of"), spacelike2),
+                        selectors,
+                    )),
+                )),
+                |(arg, selector)| (Some(arg), selector),
+            ),
+            plain,
+        ))(input),
+        Some("nth-of-type") | Some("nth-last-of-type") => alt((
+            map(before_end(an_plus_b), |arg| (Some(arg), None)),
+            plain,
+        ))(input),
+        _ => plain(input),
+    }
+}
+
+/// The `An+B` argument of `:nth-child()` and similar, without
+/// whitespace.
+fn an_plus_b(input: &[u8]) -> IResult<&[u8], SassString> {
+    map_res(
+        alt((
+            recognize(tuple((
+                opt(one_of("+-")),
+                digit0,
+                one_of("nN"),
+                opt(tuple((
+                    opt_spacelike,
+                    one_of("+-"),
+                    opt_spacelike,
+                    digit1,
+                ))),
+            ))),
+            recognize(pair(opt(one_of("+-")), digit1)),
+            tag_no_case("odd"),
+            tag_no_case("even"),
+        )),
+        |s| {
+            input_to_str(s).map(|s| {
+                let s: String = s.split_whitespace().collect();
+                SassString::from(s.as_str())
+            })
+        },
+    )(input)
+}
+
+/// Parse with `f`, and optional whitespace, if followed by `)`.
+fn before_end<'a, O, F>(f: F) -> impl Fn(&'a [u8]) -> IResult<&'a [u8], O>
+where
+    F: Fn(&'a [u8]) -> IResult<&'a [u8], O>,
+{
+    terminated(f, terminated(opt_spacelike, peek(tag(")"))))
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -121,17 +254,14 @@ mod test {
     fn simple_selector() {
         assert_eq!(
             selector(b"foo "),
-            Ok((
-                &b""[..],
-                Selector(vec![SelectorPart::Simple("foo".into())])
-            ))
+            Ok((&b""[..], Selector(vec![SelectorPart::type_sel("foo")])))
         )
     }
     #[test]
     fn escaped_simple_selector() {
         assert_eq!(
             selector(b"\\E9m "),
-            Ok((&b""[..], Selector(vec![SelectorPart::Simple("ém".into())])))
+            Ok((&b""[..], Selector(vec![SelectorPart::type_sel("ém")])))
         )
     }
 
@@ -142,9 +272,9 @@ mod test {
             Ok((
                 &b""[..],
                 Selector(vec![
-                    SelectorPart::Simple("foo".into()),
+                    SelectorPart::type_sel("foo"),
                     SelectorPart::Descendant,
-                    SelectorPart::Simple("bar".into()),
+                    SelectorPart::type_sel("bar"),
                 ])
             ))
         )
@@ -157,9 +287,9 @@ mod test {
             Ok((
                 &b""[..],
                 Selector(vec![
-                    SelectorPart::Simple("foo".into()),
+                    SelectorPart::type_sel("foo"),
                     SelectorPart::RelOp(b'>'),
-                    SelectorPart::Simple("bar".into()),
+                    SelectorPart::type_sel("bar"),
                 ])
             ))
         )
@@ -172,6 +302,7 @@ mod test {
             Ok((
                 &b""[..],
                 Selector(vec![SelectorPart::Attribute {
+                    ns: None,
                     name: "data-icon".into(),
                     op: "=".into(),
                     val: SassString::new(
@@ -193,6 +324,7 @@ mod test {
                 Selector(vec![SelectorPart::Pseudo {
                     name: "before".into(),
                     arg: None,
+                    selector: None,
                 }])
             ))
         )
@@ -204,15 +336,154 @@ mod test {
             Ok((
                 &b""[..],
                 Selector(vec![
-                    SelectorPart::Simple("figure".into()),
+                    SelectorPart::type_sel("figure"),
                     SelectorPart::Pseudo {
                         name: "before".into(),
                         arg: None,
+                        selector: None,
+                    },
+                ])
+            ))
+        )
+    }
+
+    #[test]
+    fn attribute_operators() {
+        for op in &["=", "~=", "|=", "^=", "$=", "*="] {
+            let input = format!("[a{}b] ", op);
+            let (rest, s) = selector(input.as_bytes()).unwrap();
+            assert_eq!(rest, b"");
+            assert_eq!(
+                s,
+                Selector(vec![SelectorPart::Attribute {
+                    ns: None,
+                    name: "a".into(),
+                    op: op.to_string(),
+                    val: "b".into(),
+                    modifier: None,
+                }])
+            );
+        }
+    }
+    #[test]
+    fn attribute_namespace() {
+        assert_eq!(
+            selector(b"[xlink|href] "),
+            Ok((
+                &b""[..],
+                Selector(vec![SelectorPart::Attribute {
+                    ns: Some("xlink".into()),
+                    name: "href".into(),
+                    op: "".into(),
+                    val: "".into(),
+                    modifier: None,
+                }])
+            ))
+        )
+    }
+    #[test]
+    fn type_namespace() {
+        assert_eq!(
+            selector(b"svg|rect *|* |a "),
+            Ok((
+                &b""[..],
+                Selector(vec![
+                    SelectorPart::Type {
+                        ns: Some("svg".into()),
+                        name: "rect".into(),
                     },
+                    SelectorPart::Descendant,
+                    SelectorPart::Type {
+                        ns: Some("*".into()),
+                        name: "*".into(),
+                    },
+                    SelectorPart::Descendant,
+                    SelectorPart::Type {
+                        ns: Some("".into()),
+                        name: "a".into(),
+                    },
+                ])
+            ))
+        )
+    }
+    #[test]
+    fn nth_child_of() {
+        let (_, s) = selector(b":nth-child(2n + 1 of .a, b) ").unwrap();
+        assert_eq!(
+            s,
+            Selector(vec![SelectorPart::Pseudo {
+                name: "nth-child".into(),
+                arg: Some("2n+1".into()),
+                selector: Some(Selectors::new(vec![
+                    Selector(vec![SelectorPart::Class("a".into())]),
+                    Selector(vec![SelectorPart::type_sel("b")]),
+                ])),
+            }])
+        );
+    }
+    #[test]
+    fn round_trip() {
+        for s in &[
+            "a[href^=\"http\"]",
+            "a[class$=foo]",
+            "[lang~=en i]",
+            "[lang|=en]",
+            "svg|rect",
+            "*|*",
+            "|a",
+            "[*|href]",
+            ":nth-child(2n+1 of .foo)",
+            ":nth-last-child(odd)",
+            ":nth-of-type(-n+3)",
+            ":host(.dark) ::slotted(p)",
+            "::part(label active)",
+            ":lang(en)",
+            ":not(.a, .b)",
+        ] {
+            let input = format!("{} ", s);
+            let (rest, sel) = selector(input.as_bytes()).unwrap();
+            assert_eq!((rest, sel.to_string()), (&b""[..], s.to_string()));
+        }
+    }
+
+    #[test]
+    fn compound_selector() {
+        assert_eq!(
+            selector(b"p.foo#bar%baz "),
+            Ok((
+                &b""[..],
+                Selector(vec![
+                    SelectorPart::type_sel("p"),
+                    SelectorPart::Class("foo".into()),
+                    SelectorPart::Id("bar".into()),
+                    SelectorPart::Placeholder("baz".into()),
+                ])
+            ))
+        )
+    }
+    #[test]
+    fn universal_with_class() {
+        assert_eq!(
+            selector(b"*.foo "),
+            Ok((
+                &b""[..],
+                Selector(vec![
+                    SelectorPart::type_sel("*"),
+                    SelectorPart::Class("foo".into()),
                 ])
             ))
         )
     }
+    #[test]
+    fn keyframe_selector() {
+        assert_eq!(
+            selector(b"12.5% "),
+            Ok((
+                &b""[..],
+                Selector(vec![SelectorPart::Percentage("12.5".into())])
+            ))
+        )
+    }
 
     #[test]
     fn selectors_simple() {
@@ -221,8 +492,8 @@ mod test {
             Ok((
                 &b""[..],
                 Selectors::new(vec![
-                    Selector(vec![SelectorPart::Simple("foo".into())]),
-                    Selector(vec![SelectorPart::Simple("bar".into())]),
+                    Selector(vec![SelectorPart::type_sel("foo")]),
+                    Selector(vec![SelectorPart::type_sel("bar")]),
                 ])
             ))
         )
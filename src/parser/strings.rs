@@ -20,12 +20,28 @@ pub fn sass_string(input: &[u8]) -> IResult<&[u8], SassString> {
     Ok((input, SassString::new(parts, Quotes::None)))
 }
 
+/// A name in a selector, i.e. the name of a type, class, id or
+/// placeholder selector, possibly containing interpolation.
+pub fn selector_name(input: &[u8]) -> IResult<&[u8], SassString> {
+    let (input, parts) = many1(alt((
+        string_part_interpolation,
+        map(selector_name_string, StringPart::Raw),
+    )))(input)?;
+    Ok((input, SassString::new(parts, Quotes::None)))
+}
+
 pub fn sass_string_ext(input: &[u8]) -> IResult<&[u8], SassString> {
     let (input, parts) =
         many1(alt((string_part_interpolation, extended_part)))(input)?;
     Ok((input, SassString::new(parts, Quotes::None)))
 }
 
+/// A string consisting only of interpolations, as in `#{$a}#{$b}`.
+pub fn sass_string_interpolated(input: &[u8]) -> IResult<&[u8], SassString> {
+    let (input, parts) = many1(string_part_interpolation)(input)?;
+    Ok((input, SassString::new(parts, Quotes::None)))
+}
+
 pub fn special_args(input: &[u8]) -> IResult<&[u8], SassString> {
     let (input, parts) = special_arg_parts(input)?;
     Ok((input, SassString::new(parts, Quotes::None)))
@@ -101,7 +117,7 @@ pub fn sass_string_sq(input: &[u8]) -> IResult<&[u8], SassString> {
     Ok((input, SassString::new(parts, Quotes::Single)))
 }
 
-fn string_part_interpolation(input: &[u8]) -> IResult<&[u8], StringPart> {
+pub fn string_part_interpolation(input: &[u8]) -> IResult<&[u8], StringPart> {
     let (input, expr) =
         delimited(tag("#{"), value_expression, tag("}"))(input)?;
     Ok((input, StringPart::Interpolation(expr)))
@@ -133,6 +149,32 @@ pub fn selector_string(input: &[u8]) -> IResult<&[u8], String> {
     )(input)
 }
 
+fn selector_name_string(input: &[u8]) -> IResult<&[u8], String> {
+    fold_many1(
+        alt((
+            map(selector_name_part, String::from),
+            map(tag("\\ "), |_| "\\ ".to_string()),
+            map(tag("\\\""), |_| "\\\"".to_string()),
+            map(tag("\\\'"), |_| "\\\'".to_string()),
+            map(tag("\\\\"), |_| "\\\\".to_string()),
+            map(escaped_char, |c| format!("{}", c)),
+        )),
+        String::new(),
+        |mut acc: String, item: String| {
+            acc.push_str(&item);
+            acc
+        },
+    )(input)
+}
+
+/// Like `selector_plain_part`, but ends where a class, id or
+/// placeholder selector, a namespace or an attribute operator begins.
+fn selector_name_part(input: &[u8]) -> IResult<&[u8], &str> {
+    map_res(is_not("\r\n\t >$\"'\\#+*/()[]{}:;,=!&@.%~|^"), input_to_str)(
+        input,
+    )
+}
+
 fn selector_plain_part(input: &[u8]) -> IResult<&[u8], &str> {
     map_res(is_not("\r\n\t >$\"'\\#+*/()[]{}:;,=!&@"), input_to_str)(input)
 }
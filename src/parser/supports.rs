@@ -0,0 +1,193 @@
+//! Parser for the condition of a `@supports` rule.
+use super::strings::{
+    name, sass_string, sass_string_interpolated, special_args,
+};
+use super::util::opt_spacelike;
+use super::value::{single_expression, value_expression};
+use crate::sass::{SupportsCondition, Value};
+use nom::branch::alt;
+use nom::bytes::complete::{tag, tag_no_case};
+use nom::combinator::{map, peek};
+use nom::multi::many1;
+use nom::sequence::{delimited, pair, preceded, terminated};
+use nom::IResult;
+
+/// A full `@supports` condition, like `not (display: grid)` or
+/// `(a: b) and (c: d)`.
+pub fn supports_condition(input: &[u8]) -> IResult<&[u8], SupportsCondition> {
+    alt((
+        map(
+            preceded(
+                terminated(tag_no_case("not"), opt_spacelike),
+                condition_in_parens,
+            ),
+            |c| SupportsCondition::Not(Box::new(c)),
+        ),
+        operation,
+    ))(input)
+}
+
+/// Conditions separated by either `and` or `or`.
+fn operation(input: &[u8]) -> IResult<&[u8], SupportsCondition> {
+    let (input, first) = condition_in_parens(input)?;
+    let and_chain =
+        many1(preceded(|i| operator("and", i), condition_in_parens));
+    let or_chain =
+        many1(preceded(|i| operator("or", i), condition_in_parens));
+    if let Ok((rest, more)) = and_chain(input) {
+        Ok((rest, SupportsCondition::And(with_first(first, more))))
+    } else if let Ok((rest, more)) = or_chain(input) {
+        Ok((rest, SupportsCondition::Or(with_first(first, more))))
+    } else {
+        Ok((input, first))
+    }
+}
+
+fn operator<'a>(op: &'static str, input: &'a [u8]) -> IResult<&'a [u8], ()> {
+    map(
+        delimited(opt_spacelike, tag_no_case(op), opt_spacelike),
+        |_| (),
+    )(input)
+}
+
+fn with_first(
+    first: SupportsCondition,
+    more: Vec<SupportsCondition>,
+) -> Vec<SupportsCondition> {
+    let mut result = vec![first];
+    result.extend(more);
+    result
+}
+
+/// A condition that can be an operand of `not`, `and` or `or`.
+fn condition_in_parens(input: &[u8]) -> IResult<&[u8], SupportsCondition> {
+    alt((
+        map(
+            pair(
+                terminated(name, tag("(")),
+                terminated(special_args, tag(")")),
+            ),
+            |(name, args)| SupportsCondition::Function(name, args),
+        ),
+        map(sass_string_interpolated, |s| {
+            SupportsCondition::Interpolation(Value::Literal(s))
+        }),
+        preceded(
+            terminated(tag("("), opt_spacelike),
+            alt((
+                terminated(supports_condition, end_paren),
+                terminated(custom_declaration, tag(")")),
+                terminated(declaration, end_paren),
+                map(terminated(special_args, tag(")")), |s| {
+                    SupportsCondition::Anything(s)
+                }),
+            )),
+        ),
+    ))(input)
+}
+
+fn end_paren(input: &[u8]) -> IResult<&[u8], &[u8]> {
+    preceded(opt_spacelike, tag(")"))(input)
+}
+
+/// A declaration, like `display: grid`, where both the name and the
+/// value are sass expressions.
+fn declaration(input: &[u8]) -> IResult<&[u8], SupportsCondition> {
+    map(
+        pair(
+            terminated(
+                single_expression,
+                delimited(opt_spacelike, tag(":"), opt_spacelike),
+            ),
+            value_expression,
+        ),
+        |(name, value)| SupportsCondition::Declaration(name, value),
+    )(input)
+}
+
+/// A declaration of a custom property, like `--foo: bar`.
+///
+/// The value of a custom property is not evaluated, except for
+/// interpolation.
+fn custom_declaration(input: &[u8]) -> IResult<&[u8], SupportsCondition> {
+    map(
+        pair(
+            preceded(peek(tag("--")), sass_string),
+            preceded(terminated(tag(":"), opt_spacelike), special_args),
+        ),
+        |(name, value)| {
+            SupportsCondition::Declaration(
+                Value::Literal(name),
+                Value::Literal(value),
+            )
+        },
+    )(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::supports_condition;
+    use crate::sass::SupportsCondition;
+    use crate::variablescope::GlobalScope;
+
+    fn check(input: &str) -> String {
+        let (rest, condition) = supports_condition(input.as_bytes())
+            .unwrap_or_else(|e| panic!("Failed to parse {:?}: {}", input, e));
+        assert_eq!(
+            std::str::from_utf8(rest).unwrap().trim(),
+            "{",
+            "Parsing {:?}",
+            input
+        );
+        condition
+            .evaluate(&GlobalScope::new(Default::default()))
+            .unwrap()
+    }
+
+    #[test]
+    fn declaration() {
+        assert_eq!(check("(display:grid){"), "(display: grid)");
+    }
+    #[test]
+    fn not() {
+        assert_eq!(check("not (display: grid) {"), "not (display: grid)");
+    }
+    #[test]
+    fn and() {
+        assert_eq!(
+            check("(a: b) and (c: d) AND (e: f) {"),
+            "(a: b) and (c: d) and (e: f)"
+        );
+    }
+    #[test]
+    fn nested() {
+        assert_eq!(
+            check("(a: b) or ((c: d) and (not (e: f))) {"),
+            "(a: b) or ((c: d) and (not (e: f)))"
+        );
+    }
+    #[test]
+    fn expressions() {
+        assert_eq!(check("(a#{1 + 1}: 1 + 2) {"), "(a2: 3)");
+    }
+    #[test]
+    fn custom_property() {
+        assert_eq!(check("(--a: 1 + 2) {"), "(--a: 1 + 2)");
+    }
+    #[test]
+    fn function() {
+        assert_eq!(check("selector(a > b) {"), "selector(a > b)");
+    }
+    #[test]
+    fn anything() {
+        assert_eq!(check("(foo bar) {"), "(foo bar)");
+    }
+    #[test]
+    fn not_not() {
+        let (_, c) = supports_condition(b"not (not (a: b))").unwrap();
+        match c {
+            SupportsCondition::Not(_) => (),
+            c => panic!("Expected a not condition, got {:?}", c),
+        }
+    }
+}
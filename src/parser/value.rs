@@ -1,13 +1,13 @@
 use super::formalargs::call_args;
 use super::strings::{
-    name, sass_string_dq, sass_string_ext, sass_string_sq, selector_string,
-    special_args, special_url,
+    name, sass_string_dq, sass_string_ext, sass_string_interpolated,
+    sass_string_sq, selector_string, special_args, special_url,
 };
 use super::unit::unit;
 use super::util::{opt_spacelike, spacelike2};
 use super::{input_to_string, sass_string};
 use crate::sass::{SassString, Value};
-use crate::value::{ListSeparator, Number, Operator, Rgba};
+use crate::value::{CalcName, ListSeparator, Number, Operator, Rgba};
 use nom::branch::alt;
 use nom::bytes::complete::{tag, tag_no_case};
 use nom::character::complete::{
@@ -79,7 +79,7 @@ fn se_or_ext_string(input: &[u8]) -> IResult<&[u8], Value> {
     alt((single_expression, map(sass_string_ext, Value::Literal)))(input)
 }
 
-fn single_expression(input: &[u8]) -> IResult<&[u8], Value> {
+pub fn single_expression(input: &[u8]) -> IResult<&[u8], Value> {
     let (input, a) = logic_expression(input)?;
     fold_many0(
         pair(
@@ -149,9 +149,14 @@ fn sum_expression(input: &[u8]) -> IResult<&[u8], Value> {
     Ok((rest, v))
 }
 
+/// A product, quotient or modulo of single values.
+///
+/// Values separated only by `/`, like `12px/1.5` or `1 / 2 / 3`, are
+/// a slash-separated list, which is evaluated as division only where
+/// arithmetic is expected.
 fn term_value(input: &[u8]) -> IResult<&[u8], Value> {
-    let (mut rest, mut v) = single_value(input)?;
-    while let Ok((nrest, (s1, op, s2, v2))) = tuple((
+    let (rest, first) = single_value(input)?;
+    let (rest, terms) = many0(tuple((
         map(multispace0, |s: &[u8]| !s.is_empty()),
         alt((
             value(Operator::Multiply, tag(b"*")),
@@ -160,11 +165,20 @@ fn term_value(input: &[u8]) -> IResult<&[u8], Value> {
         )),
         map(multispace0, |s: &[u8]| !s.is_empty()),
         single_value,
-    ))(rest)
+    )))(rest)?;
+    if !terms.is_empty()
+        && terms.iter().all(|(_, op, _, _)| *op == Operator::Div)
     {
-        rest = nrest;
-        v = Value::BinOp(Box::new(v), s1, op, s2, Box::new(v2));
-    }
+        let mut items = vec![first];
+        items.extend(terms.into_iter().map(|(_, _, _, v)| v));
+        return Ok((
+            rest,
+            Value::List(items, ListSeparator::Slash, false, false),
+        ));
+    }
+    let v = terms.into_iter().fold(first, |v, (s1, op, s2, v2)| {
+        Value::BinOp(Box::new(v), s1, op, s2, Box::new(v2))
+    });
     Ok((rest, v))
 }
 
@@ -199,6 +213,7 @@ fn simple_value(input: &[u8]) -> IResult<&[u8], Value> {
         hex_color,
         value(Value::Null, tag("null")),
         map(special_url, Value::Literal),
+        calculation,
         special_function,
         // Really ugly special case ... sorry.
         value(Value::Literal("-null".into()), tag("-null")),
@@ -306,8 +321,15 @@ pub fn decimal_decimals(input: &[u8]) -> IResult<&[u8], Rational> {
     )(input)
 }
 
+/// A variable, possibly qualified by a module name, as in `math.$pi`.
 pub fn variable(input: &[u8]) -> IResult<&[u8], Value> {
-    map(preceded(tag("$"), name), Value::Variable)(input)
+    map(
+        pair(opt(terminated(name, tag("."))), preceded(tag("$"), name)),
+        |(module, name)| match module {
+            Some(module) => Value::Variable(format!("{}.{}", module, name)),
+            None => Value::Variable(name),
+        },
+    )(input)
 }
 
 fn hex_color(input: &[u8]) -> IResult<&[u8], Value> {
@@ -352,6 +374,98 @@ pub fn unary_op(input: &[u8]) -> IResult<&[u8], Value> {
     )(input)
 }
 
+/// A css calculation; `calc()`, `min()`, `max()` or `clamp()`.
+///
+/// Fails if the arguments are not valid in a calculation (e.g. if
+/// they use sass-only operators), so a `min()` or `max()` can be
+/// parsed as a sass function call instead.
+fn calculation(input: &[u8]) -> IResult<&[u8], Value> {
+    let (input, name) = terminated(
+        alt((
+            value(CalcName::Calc, tag_no_case("calc")),
+            value(CalcName::Min, tag_no_case("min")),
+            value(CalcName::Max, tag_no_case("max")),
+            value(CalcName::Clamp, tag_no_case("clamp")),
+        )),
+        tag("("),
+    )(input)?;
+    let (input, args) = terminated(
+        separated_nonempty_list(
+            tag(","),
+            delimited(opt_spacelike, calc_sum, opt_spacelike),
+        ),
+        tag(")"),
+    )(input)?;
+    Ok((input, Value::Calc(name, args)))
+}
+
+fn calc_sum(input: &[u8]) -> IResult<&[u8], Value> {
+    let (mut rest, mut v) = calc_product(input)?;
+    // In css, `+` and `-` must be surrounded by whitespace.
+    while let Ok((nrest, (op, v2))) = pair(
+        delimited(
+            spacelike2,
+            alt((
+                value(Operator::Plus, tag("+")),
+                value(Operator::Minus, tag("-")),
+            )),
+            spacelike2,
+        ),
+        calc_product,
+    )(rest)
+    {
+        v = Value::BinOp(Box::new(v), true, op, true, Box::new(v2));
+        rest = nrest;
+    }
+    Ok((rest, v))
+}
+
+fn calc_product(input: &[u8]) -> IResult<&[u8], Value> {
+    let (mut rest, mut v) = calc_value(input)?;
+    while let Ok((nrest, (op, v2))) = pair(
+        delimited(
+            opt_spacelike,
+            alt((
+                value(Operator::Multiply, tag("*")),
+                value(Operator::Div, tag("/")),
+            )),
+            opt_spacelike,
+        ),
+        calc_value,
+    )(rest)
+    {
+        v = Value::BinOp(Box::new(v), true, op, true, Box::new(v2));
+        rest = nrest;
+    }
+    Ok((rest, v))
+}
+
+fn calc_value(input: &[u8]) -> IResult<&[u8], Value> {
+    alt((
+        number,
+        variable,
+        calc_paren,
+        map(preceded(tag("-"), alt((variable, calc_paren))), |v| {
+            Value::UnaryOp(Operator::Minus, Box::new(v))
+        }),
+        calculation,
+        special_function,
+        function_call,
+        map(sass_string_interpolated, Value::Literal),
+    ))(input)
+}
+
+fn calc_paren(input: &[u8]) -> IResult<&[u8], Value> {
+    map(
+        delimited(
+            terminated(tag("("), opt_spacelike),
+            calc_sum,
+            preceded(opt_spacelike, tag(")")),
+        ),
+        |v| Value::Paren(Box::new(v), true),
+    )(input)
+}
+
 fn special_function(input: &[u8]) -> IResult<&[u8], Value> {
     let (input, start) = recognize(terminated(
         alt((
@@ -645,12 +759,11 @@ mod test {
             "15/10 2 3;",
             List(
                 vec![
-                    BinOp(
-                        Box::new(Value::scalar(15)),
+                    List(
+                        vec![Value::scalar(15), Value::scalar(10)],
+                        ListSeparator::Slash,
                         false,
-                        Operator::Div,
                         false,
-                        Box::new(Value::scalar(10)),
                     ),
                     Value::scalar(2),
                     Value::scalar(3),
@@ -666,6 +779,19 @@ mod test {
     fn double_div() {
         check_expr(
             "15/5/3;",
+            List(
+                vec![Value::scalar(15), Value::scalar(5), Value::scalar(3)],
+                ListSeparator::Slash,
+                false,
+                false,
+            ),
+        )
+    }
+
+    #[test]
+    fn div_and_multiply() {
+        check_expr(
+            "15/5*3;",
             BinOp(
                 Box::new(BinOp(
                     Box::new(Value::scalar(15)),
@@ -675,7 +801,7 @@ mod test {
                     Box::new(Value::scalar(5)),
                 )),
                 false,
-                Operator::Div,
+                Operator::Multiply,
                 false,
                 Box::new(Value::scalar(3)),
             ),
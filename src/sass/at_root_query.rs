@@ -0,0 +1,79 @@
+/// The query of an `@at-root` rule, like `(without: media)`.
+///
+/// The query tells which enclosing rules the body of the `@at-root`
+/// breaks out of.  Style rules are called `rule`, and `all` means
+/// every kind of enclosing rule.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct AtRootQuery {
+    /// True for a `with` query, false for a `without` query.
+    include: bool,
+    /// Lowercase names of at-rules (without the `@`), `rule` or `all`.
+    names: Vec<String>,
+}
+
+impl AtRootQuery {
+    pub fn new(include: bool, names: Vec<String>) -> Self {
+        AtRootQuery {
+            include,
+            names: names.into_iter().map(|n| n.to_lowercase()).collect(),
+        }
+    }
+
+    /// True if this query breaks out of an at-rule named `name`.
+    pub fn excludes_name(&self, name: &str) -> bool {
+        self.names
+            .iter()
+            .any(|n| n == "all" || n.eq_ignore_ascii_case(name))
+            != self.include
+    }
+
+    /// True if this query breaks out of enclosing style rules.
+    pub fn excludes_style_rules(&self) -> bool {
+        self.names.iter().any(|n| n == "all" || n == "rule") != self.include
+    }
+}
+
+/// The default query is `(without: rule)`.
+impl Default for AtRootQuery {
+    fn default() -> Self {
+        AtRootQuery::new(false, vec!["rule".into()])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::AtRootQuery;
+
+    #[test]
+    fn default_excludes_rules_only() {
+        let q = AtRootQuery::default();
+        assert!(q.excludes_style_rules());
+        assert!(!q.excludes_name("media"));
+    }
+    #[test]
+    fn without_media() {
+        let q = AtRootQuery::new(false, vec!["media".into()]);
+        assert!(!q.excludes_style_rules());
+        assert!(q.excludes_name("media"));
+        assert!(!q.excludes_name("supports"));
+    }
+    #[test]
+    fn with_media() {
+        let q = AtRootQuery::new(true, vec!["Media".into()]);
+        assert!(q.excludes_style_rules());
+        assert!(!q.excludes_name("media"));
+        assert!(q.excludes_name("supports"));
+    }
+    #[test]
+    fn with_all() {
+        let q = AtRootQuery::new(true, vec!["all".into()]);
+        assert!(!q.excludes_style_rules());
+        assert!(!q.excludes_name("media"));
+    }
+    #[test]
+    fn without_all() {
+        let q = AtRootQuery::new(false, vec!["all".into()]);
+        assert!(q.excludes_style_rules());
+        assert!(q.excludes_name("supports"));
+    }
+}
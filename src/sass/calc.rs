@@ -0,0 +1,285 @@
+//! Evaluation of css calculations, `calc()`, `min()`, `max()` and
+//! `clamp()`.
+//!
+//! A calculation is simplified as far as possible at compile time.
+//! What can't be simplified (e.g. `100% - 20px`) is kept as a
+//! `css::Value::Calc`, to be resolved by the browser.
+use super::Value;
+use crate::css;
+use crate::error::Error;
+use crate::value::{CalcName, Number, Operator, Quotes, Unit};
+use crate::variablescope::Scope;
+use num_traits::Zero;
+use std::cmp::Ordering;
+
+/// Evaluate a calculation with the given name and arguments.
+pub fn evaluate(
+    name: CalcName,
+    args: &[Value],
+    scope: &dyn Scope,
+) -> Result<css::Value, Error> {
+    if let (CalcName::Calc, [arg @ Value::Numeric(..)]) = (name, args) {
+        // A calc() of a plain number has nothing to simplify, and is
+        // kept as written (so that e.g. `rgb(calc(1), 2, 3)` is not a
+        // color).
+        return Ok(css::Value::Calc(name, vec![evaluate_arg(arg, scope)?]));
+    }
+    let args = args
+        .iter()
+        .map(|arg| evaluate_arg(arg, scope))
+        .collect::<Result<Vec<_>, _>>()?;
+    match (name, args.as_slice()) {
+        (CalcName::Calc, [css::Value::Numeric(..)])
+        | (CalcName::Calc, [css::Value::Calc(..)]) => {
+            Ok(args.into_iter().next().unwrap())
+        }
+        (CalcName::Calc, [_]) => Ok(css::Value::Calc(name, args)),
+        (CalcName::Calc, _) => Err(Error::S(format!(
+            "calc() takes exactly one argument, got {}",
+            args.len()
+        ))),
+        (_, []) => Err(Error::S(format!("{}() needs an argument", name))),
+        (CalcName::Min, _) => Ok(extreme(&args, Ordering::Less)
+            .unwrap_or_else(|| css::Value::Calc(name, args.clone()))),
+        (CalcName::Max, _) => Ok(extreme(&args, Ordering::Greater)
+            .unwrap_or_else(|| css::Value::Calc(name, args.clone()))),
+        (CalcName::Clamp, [min, number, max]) => Ok(clamp(min, number, max)
+            .unwrap_or_else(|| css::Value::Calc(name, args.clone()))),
+        (CalcName::Clamp, _) => Err(Error::S(format!(
+            "clamp() takes exactly three arguments, got {}",
+            args.len()
+        ))),
+    }
+}
+
+fn evaluate_arg(arg: &Value, scope: &dyn Scope) -> Result<css::Value, Error> {
+    match *arg {
+        Value::BinOp(ref a, _, ref op, _, ref b) => {
+            let a = evaluate_arg(a, scope)?;
+            let b = evaluate_arg(b, scope)?;
+            operate(a, op, b)
+        }
+        Value::UnaryOp(Operator::Minus, ref v) => {
+            match evaluate_arg(v, scope)? {
+                css::Value::Numeric(v, unit, _) => {
+                    Ok(css::Value::Numeric(-&v, unit, true))
+                }
+                v => operate(
+                    css::Value::Numeric(Number::from(-1), Unit::None, true),
+                    &Operator::Multiply,
+                    v,
+                ),
+            }
+        }
+        Value::Paren(ref v, _) => match evaluate_arg(v, scope)? {
+            v @ css::Value::Numeric(..) => Ok(v),
+            v => Ok(css::Value::Paren(Box::new(v))),
+        },
+        ref v => match v.do_evaluate(scope, true)? {
+            v @ css::Value::Numeric(..)
+            | v @ css::Value::Calc(..)
+            | v @ css::Value::Literal(_, Quotes::None) => Ok(v),
+            v => Err(Error::bad_value("number or calculation", &v)),
+        },
+    }
+}
+
+/// Apply an operator in a calculation, if possible.
+///
+/// If the operator can't be applied at compile time, it is kept as a
+/// binary operation to be resolved by the browser.
+/// Multiplying or dividing numbers to a unit that css can't express
+/// (e.g. `2px * 3px`) is an error.
+fn operate(
+    a: css::Value,
+    op: &Operator,
+    b: css::Value,
+) -> Result<css::Value, Error> {
+    if let (
+        css::Value::Numeric(ref av, ref au, _),
+        css::Value::Numeric(ref bv, ref bu, _),
+    ) = (&a, &b)
+    {
+        let result = match *op {
+            Operator::Plus => bu
+                .scale_to(au)
+                .map(|s| (av.value + bv.value * s, au.clone())),
+            Operator::Minus => bu
+                .scale_to(au)
+                .map(|s| (av.value - bv.value * s, au.clone())),
+            Operator::Multiply if *bu == Unit::None => {
+                Some((av.value * bv.value, au.clone()))
+            }
+            Operator::Multiply if *au == Unit::None => {
+                Some((av.value * bv.value, bu.clone()))
+            }
+            Operator::Div if bv.is_zero() => None,
+            Operator::Div if *bu == Unit::None => {
+                Some((av.value / bv.value, au.clone()))
+            }
+            Operator::Div => match au.scale_to(bu) {
+                Some(s) => Some((av.value * s / bv.value, Unit::None)),
+                None => return Err(incompatible(&a, op, &b)),
+            },
+            Operator::Multiply => return Err(incompatible(&a, op, &b)),
+            _ => None,
+        };
+        if let Some((value, unit)) = result {
+            return Ok(css::Value::Numeric(Number::from(value), unit, true));
+        }
+    }
+    Ok(css::Value::BinOp(
+        Box::new(a),
+        true,
+        op.clone(),
+        true,
+        Box::new(b),
+    ))
+}
+
+fn incompatible(a: &css::Value, op: &Operator, b: &css::Value) -> Error {
+    Error::S(format!(
+        "{} {} {} isn't compatible with CSS calculations.",
+        a.format(Default::default()),
+        op,
+        b.format(Default::default()),
+    ))
+}
+
+/// Find the smallest or largest argument, if all arguments are
+/// numbers with compatible units.
+fn extreme(args: &[css::Value], pref: Ordering) -> Option<css::Value> {
+    let mut result = None;
+    for arg in args {
+        result = match (result, arg) {
+            (None, v @ css::Value::Numeric(..)) => Some(v.clone()),
+            (Some(r), v) => {
+                if compare(v, &r)? == pref {
+                    Some(v.clone())
+                } else {
+                    Some(r)
+                }
+            }
+            _ => return None,
+        };
+    }
+    result
+}
+
+fn clamp(
+    min: &css::Value,
+    number: &css::Value,
+    max: &css::Value,
+) -> Option<css::Value> {
+    let (lower, upper) = (compare(number, min)?, compare(number, max)?);
+    if lower == Ordering::Less {
+        Some(min.clone())
+    } else if upper == Ordering::Greater {
+        Some(max.clone())
+    } else {
+        Some(number.clone())
+    }
+}
+
+/// Compare two numbers, if they have compatible units.
+///
+/// A unitless number is comparable to any number.
+fn compare(a: &css::Value, b: &css::Value) -> Option<Ordering> {
+    match (a, b) {
+        (
+            css::Value::Numeric(ref av, ref au, _),
+            css::Value::Numeric(ref bv, ref bu, _),
+        ) => Some(av.value.cmp(&(bv.value * bu.compatible_scale(au)?))),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::error::Error;
+    use crate::variablescope::test::{do_evaluate, do_evaluate_or_error};
+
+    #[test]
+    fn simplified() {
+        assert_eq!(do_evaluate(&[], b"calc(1px + 2px);"), "3px")
+    }
+    #[test]
+    fn converted_units() {
+        assert_eq!(do_evaluate(&[], b"calc(1in - 6px * 2);"), "0.875in")
+    }
+    #[test]
+    fn kept() {
+        assert_eq!(
+            do_evaluate(&[], b"calc(100% - 20px);"),
+            "calc(100% - 20px)"
+        )
+    }
+    #[test]
+    fn variable() {
+        assert_eq!(
+            do_evaluate(&[("gutter", "10px")], b"calc(100% - $gutter * 2);"),
+            "calc(100% - 20px)"
+        )
+    }
+    #[test]
+    fn nested_parens() {
+        assert_eq!(
+            do_evaluate(&[], b"calc((100% - (2px + 3px)) / 3);"),
+            "calc((100% - 5px) / 3)"
+        )
+    }
+    #[test]
+    fn css_var() {
+        assert_eq!(
+            do_evaluate(&[], b"calc(var(--x) * 2);"),
+            "calc(var(--x) * 2)"
+        )
+    }
+    #[test]
+    fn plain_number_kept() {
+        assert_eq!(do_evaluate(&[], b"calc(1);"), "calc(1)")
+    }
+    #[test]
+    fn negated_variable() {
+        assert_eq!(
+            do_evaluate(&[("x", "10px")], b"calc(100% + -$x);"),
+            "calc(100% + -10px)"
+        )
+    }
+    #[test]
+    fn negated_calc() {
+        assert_eq!(
+            do_evaluate(&[], b"calc(-(100% - 20px));"),
+            "calc(-1 * (100% - 20px))"
+        )
+    }
+    #[test]
+    fn complex_unit() {
+        match do_evaluate_or_error(&[], b"calc(2px * 3px);") {
+            Err(Error::S(ref msg)) => assert_eq!(
+                msg,
+                "2px * 3px isn't compatible with CSS calculations."
+            ),
+            other => panic!("Expected error, got {:?}", other),
+        }
+    }
+    #[test]
+    fn min_compatible() {
+        assert_eq!(do_evaluate(&[], b"min(1in, 100px);"), "1in")
+    }
+    #[test]
+    fn min_incompatible() {
+        assert_eq!(do_evaluate(&[], b"min(100%, 500px);"), "min(100%, 500px)")
+    }
+    #[test]
+    fn clamp_compatible() {
+        assert_eq!(do_evaluate(&[], b"clamp(1px, 3cm, 2in);"), "3cm")
+    }
+    #[test]
+    fn clamp_incompatible() {
+        assert_eq!(
+            do_evaluate(&[], b"clamp(10px, 5vw, 30px);"),
+            "clamp(10px, 5vw, 30px)"
+        )
+    }
+}
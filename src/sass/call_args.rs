@@ -8,20 +8,32 @@ use std::default::Default;
 ///
 /// Each argument has a Value.  Arguments may be named.
 /// If the optional name is None, the argument is positional.
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
-pub struct CallArgs(Vec<(Option<String>, Value)>);
+///
+/// Any arguments given with `...`, as in `foo($list...)` are kept
+/// separately, since they are spread into positional and named
+/// arguments when evaluated.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct CallArgs(Vec<(Option<String>, Value)>, Vec<Value>);
 
 impl CallArgs {
     pub fn new(v: Vec<(Option<String>, Value)>) -> Self {
-        CallArgs(v)
+        CallArgs(v, vec![])
+    }
+
+    /// Create call arguments with `rest` arguments to be spread.
+    pub fn new_with_rest(
+        v: Vec<(Option<String>, Value)>,
+        rest: Vec<Value>,
+    ) -> Self {
+        CallArgs(v, rest)
     }
 
     pub fn from_value(v: Value) -> Self {
         match v {
             Value::List(v, _, false, _) => {
-                CallArgs(v.into_iter().map(|v| (None, v)).collect())
+                CallArgs::new(v.into_iter().map(|v| (None, v)).collect())
             }
-            v => CallArgs(vec![(None, v)]),
+            v => CallArgs::new(vec![(None, v)]),
         }
     }
 
@@ -34,7 +46,7 @@ impl CallArgs {
     }
 
     pub fn is_empty(&self) -> bool {
-        self.0.is_empty()
+        self.0.is_empty() && self.1.is_empty()
     }
 
     pub fn get(&self, index: usize) -> Option<&(Option<String>, Value)> {
@@ -46,18 +58,43 @@ impl CallArgs {
         scope: &dyn Scope,
         arithmetic: bool,
     ) -> Result<css::CallArgs, Error> {
-        let args = self.0
+        let mut args = self.0
                 .iter()
                 .map(|&(ref n, ref v)| -> Result<(Option<String>, css::Value), Error> {
                     Ok((n.clone(), v.do_evaluate(scope, arithmetic)?))
                 })
                 .collect::<Result<Vec<_>, Error>>()?;
+        for rest in &self.1 {
+            match rest.do_evaluate(scope, arithmetic)? {
+                css::Value::ArgList(rest) => {
+                    args.extend(css::CallArgs::from(rest).0);
+                }
+                css::Value::Map(map) => {
+                    for (key, value) in map {
+                        match key {
+                            css::Value::Literal(name, _) => args
+                                .push((Some(name.replace('-', "_")), value)),
+                            key => {
+                                return Err(Error::S(format!(
+                                    "Variable keyword argument map must \
+                                     have string keys.\n{} is not a string.",
+                                    key.format(Default::default()),
+                                )))
+                            }
+                        }
+                    }
+                }
+                list @ css::Value::List(..) => args
+                    .extend(list.iter_items().into_iter().map(|v| (None, v))),
+                value => args.push((None, value)),
+            }
+        }
         Ok(css::CallArgs(args))
     }
 }
 
 impl Default for CallArgs {
     fn default() -> Self {
-        CallArgs(vec![])
+        CallArgs::new(vec![])
     }
 }
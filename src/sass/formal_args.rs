@@ -1,7 +1,6 @@
-use crate::css;
+use crate::css::{self, ArgList};
 use crate::error::Error;
 use crate::sass::Value;
-use crate::value::ListSeparator;
 use crate::variablescope::{Scope, ScopeImpl};
 use std::default::Default;
 
@@ -9,52 +8,137 @@ use std::default::Default;
 ///
 /// The arguments are ordered (so they have a position).
 /// Each argument also has a name and may have a default value.
+/// An argument without a default value is required.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
-pub struct FormalArgs(Vec<(String, Value)>, bool);
+pub struct FormalArgs(Vec<(String, Option<Value>)>, bool);
 
 impl FormalArgs {
-    pub fn new(a: Vec<(String, Value)>, is_varargs: bool) -> Self {
+    pub fn new(a: Vec<(String, Option<Value>)>, is_varargs: bool) -> Self {
         FormalArgs(a, is_varargs)
     }
 
+    /// Bind the actual `args` of a call to these formal arguments, in
+    /// a new scope.
+    ///
+    /// If this has a rest argument, it gets an `ArgList` of the
+    /// positional arguments and the named arguments that don't match
+    /// any other argument.
     pub fn eval<'a>(
         &self,
         scope: &'a dyn Scope,
         args: &css::CallArgs,
+    ) -> Result<ScopeImpl<'a>, Error> {
+        self.bind(scope, args, true)
+    }
+
+    /// Like `eval`, but extra arguments are ignored rather than an
+    /// error, since builtin functions does not declare all the
+    /// arguments they accept.
+    pub fn eval_builtin<'a>(
+        &self,
+        scope: &'a dyn Scope,
+        args: &css::CallArgs,
+    ) -> Result<ScopeImpl<'a>, Error> {
+        self.bind(scope, args, false)
+    }
+
+    fn bind<'a>(
+        &self,
+        scope: &'a dyn Scope,
+        args: &css::CallArgs,
+        strict: bool,
     ) -> Result<ScopeImpl<'a>, Error> {
         let mut argscope = ScopeImpl::sub(scope);
-        let n = self.0.len();
-        for (i, &(ref name, ref default)) in self.0.iter().enumerate() {
-            if let Some(value) = args
-                .iter()
-                .find(|&&(ref k, ref _v)| k.as_ref() == Some(name))
-                .map(|&(ref _k, ref v)| v)
-            {
-                argscope.define(name, value);
-            } else if self.1 && i + 1 == n && args.len() > n {
-                let args = args
-                    .iter()
-                    .skip(i)
-                    .map(|&(_, ref v)| v.clone())
-                    .collect();
-                argscope.define(
-                    name,
-                    &css::Value::List(args, ListSeparator::Comma, false),
-                );
-            } else {
-                match args.get(i) {
-                    Some(&(None, ref v)) => argscope.define(name, v),
-                    _ => {
+        let positional = args
+            .iter()
+            .filter(|(name, _)| name.is_none())
+            .map(|(_, value)| value)
+            .collect::<Vec<_>>();
+        let mut named = args
+            .iter()
+            .filter_map(|(name, value)| name.as_ref().map(|n| (n, value)))
+            .collect::<Vec<_>>();
+        let n_single = self.0.len() - if self.1 { 1 } else { 0 };
+        if strict && !self.1 && positional.len() > n_single {
+            return Err(too_many_args(n_single, positional.len()));
+        }
+        for (i, (name, default)) in self.0.iter().enumerate() {
+            if self.1 && i == n_single {
+                break;
+            }
+            let by_name = named.iter().position(|(n, _)| *n == name);
+            let by_name = by_name.map(|pos| named.remove(pos).1);
+            match (positional.get(i).copied(), by_name) {
+                (Some(_), Some(_)) => {
+                    return Err(Error::S(format!(
+                        "Argument ${} was passed both by position and by \
+                         name.",
+                        name.replace('_', "-")
+                    )));
+                }
+                (Some(value), None) | (None, Some(value)) => {
+                    argscope.define(name, value)
+                }
+                (None, None) => match default {
+                    Some(default) => {
                         let v = default.do_evaluate(&argscope, true)?;
                         argscope.define(name, &v)
                     }
-                };
+                    None => {
+                        return Err(Error::S(format!(
+                            "Missing argument ${}.",
+                            name.replace('_', "-")
+                        )));
+                    }
+                },
             }
         }
+        if self.1 {
+            let rest = ArgList::new(
+                positional[n_single.min(positional.len())..]
+                    .iter()
+                    .map(|&v| v.clone())
+                    .collect(),
+                named
+                    .drain(..)
+                    .map(|(n, v)| (n.clone(), v.clone()))
+                    .collect(),
+            );
+            argscope.define(&self.0[n_single].0, &css::Value::ArgList(rest));
+        }
+        if strict && !named.is_empty() {
+            let names = named
+                .iter()
+                .map(|(name, _)| format!("${}", name.replace('_', "-")))
+                .collect::<Vec<_>>();
+            return Err(Error::S(if names.len() == 1 {
+                format!("No argument named {}.", names[0])
+            } else {
+                format!(
+                    "No arguments named {} or {}.",
+                    names[..names.len() - 1].join(", "),
+                    names[names.len() - 1],
+                )
+            }));
+        }
         Ok(argscope)
     }
 }
 
+fn too_many_args(allowed: usize, passed: usize) -> Error {
+    let was = if passed == 1 { "was" } else { "were" };
+    Error::S(match allowed {
+        0 => format!("No arguments allowed, but {} {} passed.", passed, was),
+        1 => {
+            format!("Only 1 argument allowed, but {} {} passed.", passed, was)
+        }
+        n => format!(
+            "Only {} arguments allowed, but {} {} passed.",
+            n, passed, was
+        ),
+    })
+}
+
 impl Default for FormalArgs {
     fn default() -> Self {
         FormalArgs::new(vec![], false)
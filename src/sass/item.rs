@@ -1,5 +1,7 @@
 use crate::functions::SassFunction;
-use crate::sass::{CallArgs, FormalArgs, SassString, Value};
+use crate::sass::{
+    AtRootQuery, CallArgs, FormalArgs, SassString, SupportsCondition, Value,
+};
 use crate::selectors::Selectors;
 
 /// Every sass file is a sequence of sass items.
@@ -7,6 +9,9 @@ use crate::selectors::Selectors;
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Item {
     Import(Vec<Value>, Value),
+    /// A `@use` directive, with the url of the used module and the
+    /// namespace given by `as`, if any.
+    Use(Value, Option<String>),
     VariableDeclaration {
         name: String,
         val: Value,
@@ -14,6 +19,7 @@ pub enum Item {
         global: bool,
     },
     AtRoot {
+        query: AtRootQuery,
         selectors: Selectors,
         body: Vec<Item>,
     },
@@ -22,6 +28,11 @@ pub enum Item {
         args: Value,
         body: Option<Vec<Item>>,
     },
+    /// A `@supports` rule, with its condition and body.
+    Supports {
+        condition: SupportsCondition,
+        body: Vec<Item>,
+    },
     Error(Value),
 
     MixinDeclaration {
@@ -32,9 +43,12 @@ pub enum Item {
     MixinCall {
         name: String,
         args: CallArgs,
+        /// The arguments declared by `using` for the body.
+        body_args: FormalArgs,
         body: Vec<Item>,
     },
-    Content,
+    /// An `@content` statement, with arguments for the body.
+    Content(CallArgs),
 
     FunctionDeclaration {
         name: String,
@@ -1,11 +1,16 @@
+mod at_root_query;
+mod calc;
 mod call_args;
 mod formal_args;
 mod item;
 mod string;
+mod supports;
 mod value;
 
+pub use self::at_root_query::AtRootQuery;
 pub use self::call_args::CallArgs;
 pub use self::formal_args::FormalArgs;
 pub use self::item::Item;
 pub use self::string::{SassString, StringPart};
+pub use self::supports::SupportsCondition;
 pub use self::value::Value;
@@ -4,13 +4,13 @@ use crate::value::Quotes;
 use crate::variablescope::Scope;
 use std::fmt;
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct SassString {
     parts: Vec<StringPart>,
     quotes: Quotes,
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub enum StringPart {
     Raw(String),
     Interpolation(Value),
@@ -127,6 +127,13 @@ impl SassString {
             quotes: if t { Quotes::None } else { quotes },
         })
     }
+    /// True if this string contains any interpolation.
+    pub fn is_interpolated(&self) -> bool {
+        self.parts.iter().any(|p| match p {
+            StringPart::Interpolation(_) => true,
+            _ => false,
+        })
+    }
     pub fn is_unquoted(&self) -> bool {
         self.quotes == Quotes::None
     }
@@ -146,7 +153,16 @@ impl SassString {
         }
     }
     pub fn append(&mut self, other: &Self) {
-        self.parts.extend_from_slice(&other.parts);
+        let mut parts = other.parts.iter();
+        if let (
+            Some(StringPart::Raw(ref mut last)),
+            Some(StringPart::Raw(s)),
+        ) = (self.parts.last_mut(), other.parts.first())
+        {
+            last.push_str(s);
+            parts.next();
+        }
+        self.parts.extend(parts.cloned());
     }
 }
 
@@ -0,0 +1,97 @@
+use crate::error::Error;
+use crate::output::Format;
+use crate::sass::{SassString, Value};
+use crate::variablescope::Scope;
+
+/// The condition of a `@supports` rule, like `not (display: grid)`.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SupportsCondition {
+    /// A negated condition, `not (...)`.
+    Not(Box<SupportsCondition>),
+    /// Conditions that must all be true, `(...) and (...)`.
+    And(Vec<SupportsCondition>),
+    /// Conditions where any may be true, `(...) or (...)`.
+    Or(Vec<SupportsCondition>),
+    /// A declaration, like `(display: grid)`.
+    ///
+    /// Both the name and the value are sass expressions.
+    Declaration(Value, Value),
+    /// A function, like `selector(a > b)`, with its raw arguments.
+    Function(String, SassString),
+    /// A condition given by interpolation, like `#{$query}`.
+    Interpolation(Value),
+    /// Anything else in parenthesis.
+    Anything(SassString),
+}
+
+impl SupportsCondition {
+    /// Evaluate this condition to css.
+    pub fn evaluate(&self, scope: &dyn Scope) -> Result<String, Error> {
+        match self {
+            SupportsCondition::Not(cond) => {
+                Ok(format!("not {}", cond.evaluate_in_parens(None, scope)?))
+            }
+            SupportsCondition::And(conds) => {
+                evaluate_operation(conds, "and", scope)
+            }
+            SupportsCondition::Or(conds) => {
+                evaluate_operation(conds, "or", scope)
+            }
+            SupportsCondition::Declaration(name, value) => Ok(format!(
+                "({}: {})",
+                to_css(name, scope)?,
+                to_css(value, scope)?,
+            )),
+            SupportsCondition::Function(name, args) => {
+                Ok(format!("{}({})", name, args.evaluate(scope)?.0))
+            }
+            SupportsCondition::Interpolation(value) => {
+                Ok(to_css(value, scope)?.to_string())
+            }
+            SupportsCondition::Anything(contents) => {
+                Ok(format!("({})", contents.evaluate(scope)?.0))
+            }
+        }
+    }
+
+    /// Evaluate this condition as an operand of `operator`, or of
+    /// `not` if `operator` is `None`, with parenthesis as needed.
+    fn evaluate_in_parens(
+        &self,
+        operator: Option<&str>,
+        scope: &dyn Scope,
+    ) -> Result<String, Error> {
+        let needs_parens = match self {
+            SupportsCondition::Not(_) => true,
+            SupportsCondition::And(_) => operator != Some("and"),
+            SupportsCondition::Or(_) => operator != Some("or"),
+            _ => false,
+        };
+        let css = self.evaluate(scope)?;
+        Ok(if needs_parens {
+            format!("({})", css)
+        } else {
+            css
+        })
+    }
+}
+
+fn evaluate_operation(
+    conds: &[SupportsCondition],
+    operator: &str,
+    scope: &dyn Scope,
+) -> Result<String, Error> {
+    Ok(conds
+        .iter()
+        .map(|c| c.evaluate_in_parens(Some(operator), scope))
+        .collect::<Result<Vec<_>, _>>()?
+        .join(&format!(" {} ", operator)))
+}
+
+fn to_css(value: &Value, scope: &dyn Scope) -> Result<String, Error> {
+    let value = value.evaluate(scope)?;
+    Ok(match value {
+        crate::css::Value::Literal(s, _) => s,
+        value => value.format(Format::default()).to_string(),
+    })
+}
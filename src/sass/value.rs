@@ -1,22 +1,28 @@
 use crate::css;
 use crate::error::Error;
 use crate::functions::get_builtin_function;
-use crate::ordermap::OrderMap;
-use crate::sass::{CallArgs, SassString};
-use crate::value::{ListSeparator, Number, Operator, Quotes, Rgba, Unit};
+use crate::ordermap::{MapKey, OrderMap};
+use crate::sass::{calc, CallArgs, SassString};
+use crate::value::{
+    CalcName, ListSeparator, Number, Operator, Quotes, Rgba, Unit,
+};
 use crate::variablescope::Scope;
 use num_rational::Rational;
 use num_traits::Zero;
+use std::hash::{Hash, Hasher};
 
 /// A sass value.
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Value {
     /// A special kind of escape.  Only really used for !important.
     Bang(String),
     /// A call has a name and an argument (which may be multi).
     Call(SassString, CallArgs),
+    /// A css calculation, such as `calc(100% - $gutter)`.
+    Calc(CalcName, Vec<Value>),
     Literal(SassString),
-    /// A comma- or space separated list of values, with or without brackets.
+    /// A comma-, space- or slash-separated list of values, with or
+    /// without brackets.
     List(Vec<Value>, ListSeparator, bool, bool),
     /// A Numeric value is a rational value with a Unit (which may be
     /// Unit::None) and flags.
@@ -45,6 +51,15 @@ pub enum Value {
     UnicodeRange(String),
 }
 
+impl MapKey for Value {
+    fn hash_key<H: Hasher>(&self, state: &mut H) {
+        self.hash(state)
+    }
+    fn eq_key(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
 impl Value {
     pub fn scalar(v: isize) -> Self {
         Value::Numeric(Number::from(v), Unit::None)
@@ -125,6 +140,17 @@ impl Value {
             Value::Variable(ref name) => {
                 Ok(scope.get(name)?.into_calculated())
             }
+            Value::List(ref v, ListSeparator::Slash, false, _) => {
+                let items = v
+                    .iter()
+                    .map(|v| v.do_evaluate(scope, false))
+                    .collect::<Result<Vec<_>, Error>>()?;
+                if arithmetic {
+                    divide(items)
+                } else {
+                    Ok(css::Value::List(items, ListSeparator::Slash, false))
+                }
+            }
             Value::List(ref v, ref s, b, needs_requote) => {
                 let items = v
                     .iter()
@@ -155,6 +181,7 @@ impl Value {
                     Ok(css::Value::Call(name, args))
                 }
             }
+            Value::Calc(name, ref args) => calc::evaluate(name, args, scope),
             Value::Numeric(ref num, ref unit) => {
                 let mut num = num.clone();
                 if arithmetic {
@@ -191,7 +218,8 @@ impl Value {
                         (aa, b)
                     }
                 };
-                Ok(op.eval(a.clone(), b.clone()).unwrap_or_else(|| {
+                let (a, b) = (slash_as_division(a)?, slash_as_division(b)?);
+                Ok(op.eval(a.clone(), b.clone())?.unwrap_or_else(|| {
                     css::Value::BinOp(
                         Box::new(a),
                         s1,
@@ -244,3 +272,42 @@ impl Value {
         }
     }
 }
+
+/// A slash-separated list of numbers, such as `12px/1.5` in a
+/// variable, is kept as is until the value is used in arithmetic,
+/// where it is a division.
+fn slash_as_division(value: css::Value) -> Result<css::Value, Error> {
+    match value {
+        css::Value::List(v, ListSeparator::Slash, false)
+            if v.iter().all(|v| match v {
+                css::Value::Numeric(..) => true,
+                _ => false,
+            }) =>
+        {
+            divide(v)
+        }
+        value => Ok(value),
+    }
+}
+
+/// Divide the first of `values` by the rest of them, in order.
+///
+/// Values that can't be divided (e.g. strings) are kept as a slash
+/// between them.
+fn divide(values: Vec<css::Value>) -> Result<css::Value, Error> {
+    let mut values = values.into_iter().map(css::Value::into_calculated);
+    let first = values.next().unwrap_or(css::Value::Null);
+    values.try_fold(first, |a, b| {
+        Ok(Operator::Div
+            .eval(a.clone(), b.clone())?
+            .unwrap_or_else(|| {
+                css::Value::BinOp(
+                    Box::new(a),
+                    false,
+                    Operator::Div,
+                    false,
+                    Box::new(b),
+                )
+            }))
+    })
+}
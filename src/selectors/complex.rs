@@ -0,0 +1,557 @@
+//! Complex selectors as sequences of compound selectors and
+//! combinators, and the superselector relation between them.
+//!
+//! The algorithms here follow the ones of dart-sass, which is the
+//! reference implementation of the `sass:selector` functions.
+use super::{Selector, SelectorPart, Selectors};
+use crate::sass::SassString;
+
+/// A component of a complex selector.
+///
+/// The descendant combinator is implicit between two adjacent
+/// compound selectors, only the other combinators are explicit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Component {
+    Compound(Vec<SelectorPart>),
+    Combinator(u8),
+}
+
+impl Component {
+    pub(crate) fn compound(&self) -> Option<&[SelectorPart]> {
+        match *self {
+            Component::Compound(ref c) => Some(c),
+            Component::Combinator(_) => None,
+        }
+    }
+}
+
+impl Selector {
+    /// Split this selector into compound selectors and combinators.
+    pub(crate) fn components(&self) -> Vec<Component> {
+        let mut result = Vec::new();
+        let mut compound = Vec::new();
+        for part in &self.0 {
+            match *part {
+                SelectorPart::Descendant => (),
+                SelectorPart::RelOp(op) => {
+                    if !compound.is_empty() {
+                        result.push(Component::Compound(compound));
+                        compound = Vec::new();
+                    }
+                    result.push(Component::Combinator(op));
+                    continue;
+                }
+                ref simple => {
+                    compound.push(simple.clone());
+                    continue;
+                }
+            }
+            if !compound.is_empty() {
+                result.push(Component::Compound(compound));
+                compound = Vec::new();
+            }
+        }
+        if !compound.is_empty() {
+            result.push(Component::Compound(compound));
+        }
+        result
+    }
+
+    /// Create a selector from compound selectors and combinators.
+    pub(crate) fn from_components(components: &[Component]) -> Selector {
+        let mut result = Vec::new();
+        let mut after_compound = false;
+        for component in components {
+            match *component {
+                Component::Compound(ref c) => {
+                    if after_compound {
+                        result.push(SelectorPart::Descendant);
+                    }
+                    result.extend(c.iter().cloned());
+                    after_compound = true;
+                }
+                Component::Combinator(op) => {
+                    result.push(SelectorPart::RelOp(op));
+                    after_compound = false;
+                }
+            }
+        }
+        Selector(result)
+    }
+}
+
+impl SelectorPart {
+    /// True for pseudo-elements, including the css2 pseudo-elements
+    /// that are written with a single colon.
+    pub(crate) fn is_pseudo_element(&self) -> bool {
+        match *self {
+            SelectorPart::PseudoElement { .. } => true,
+            SelectorPart::Pseudo { ref name, .. } => {
+                match name.single_raw() {
+                    Some("after") | Some("before") | Some("first-line")
+                    | Some("first-letter") => true,
+                    _ => false,
+                }
+            }
+            _ => false,
+        }
+    }
+
+    /// The name of a pseudo-class or pseudo-element, without any
+    /// vendor prefix.
+    pub(crate) fn normalized_name(&self) -> Option<&str> {
+        match *self {
+            SelectorPart::Pseudo { ref name, .. }
+            | SelectorPart::PseudoElement { ref name, .. } => {
+                name.single_raw().map(unprefixed)
+            }
+            _ => None,
+        }
+    }
+
+    /// The selector argument of a pseudo selector, such as `.foo` in
+    /// `:not(.foo)` or `:nth-child(2n of .foo)`.
+    pub(crate) fn selector_arg(&self) -> Option<&Selectors> {
+        match *self {
+            SelectorPart::Pseudo { ref selector, .. }
+            | SelectorPart::PseudoElement { ref selector, .. } => {
+                selector.as_ref()
+            }
+            _ => None,
+        }
+    }
+
+    /// The plain argument of a pseudo selector, such as `2n` in
+    /// `:nth-child(2n of .foo)`.
+    fn plain_arg(&self) -> Option<&SassString> {
+        match *self {
+            SelectorPart::Pseudo { ref arg, .. }
+            | SelectorPart::PseudoElement { ref arg, .. } => arg.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn is_host(&self) -> bool {
+        match self.normalized_name() {
+            Some("host") | Some("host-context") => !self.is_pseudo_element(),
+            _ => false,
+        }
+    }
+}
+
+impl Selectors {
+    /// Split each selector into its components.
+    pub(crate) fn complexes(&self) -> Vec<Vec<Component>> {
+        self.s.iter().map(Selector::components).collect()
+    }
+}
+
+/// Check if every selector in `list2` matches a subset of the
+/// elements matched by some selector in `list1`.
+pub(crate) fn list_is_superselector(
+    list1: &[Vec<Component>],
+    list2: &[Vec<Component>],
+) -> bool {
+    list2
+        .iter()
+        .all(|c2| list1.iter().any(|c1| complex_is_superselector(c1, c2)))
+}
+
+/// Check if `complex1` matches every element that `complex2` matches.
+pub(crate) fn complex_is_superselector(
+    complex1: &[Component],
+    complex2: &[Component],
+) -> bool {
+    // Selectors with trailing combinators are neither superselectors
+    // nor subselectors.
+    if complex1.last().and_then(Component::compound).is_none()
+        || complex2.last().and_then(Component::compound).is_none()
+    {
+        return false;
+    }
+    let (mut i1, mut i2) = (0, 0);
+    loop {
+        let remaining1 = complex1.len() - i1;
+        let remaining2 = complex2.len() - i2;
+        if remaining1 == 0 || remaining2 == 0 || remaining1 > remaining2 {
+            return false;
+        }
+        let compound1 = match complex1[i1].compound() {
+            Some(c) => c,
+            None => return false,
+        };
+        if complex2[i2].compound().is_none() {
+            return false;
+        }
+        if remaining1 == 1 {
+            let last2 = complex2.len() - 1;
+            return compound_is_superselector(
+                compound1,
+                complex2[last2].compound().unwrap_or_default(),
+                &complex2[i2..last2],
+            );
+        }
+
+        // Find the first index where `complex2[i2..after]` is a
+        // subselector of `compound1`.  Don't consume all of complex2,
+        // since the rest of complex1 needs something to match.
+        let mut after = i2 + 1;
+        while after < complex2.len() {
+            if let Some(compound2) = complex2[after - 1].compound() {
+                if compound_is_superselector(
+                    compound1,
+                    compound2,
+                    complex2.get(i2 + 1..after - 1).unwrap_or_default(),
+                ) {
+                    break;
+                }
+            }
+            after += 1;
+        }
+        if after == complex2.len() {
+            return false;
+        }
+
+        match (&complex1[i1 + 1], &complex2[after]) {
+            (Component::Combinator(op1), Component::Combinator(op2)) => {
+                // `a ~ b` is a superselector of `a + b`, but otherwise
+                // the combinators must match.
+                if *op1 == b'~' {
+                    if *op2 == b'>' {
+                        return false;
+                    }
+                } else if op1 != op2 {
+                    return false;
+                }
+                // When only the last compound of complex1 remains, it
+                // must be reached from the matched one in the same way.
+                // `a > c` is not a superselector of `a > b > c` or
+                // `a > b c`, even though `c` is a superselector of
+                // `b > c` and `b c`.  But `a ~ c` is a superselector of
+                // `a ~ b + c`, since that is also a sibling.
+                if remaining1 == 3 {
+                    let rest = &complex2[after + 1..];
+                    let siblings = rest.iter().all(|c| match *c {
+                        Component::Combinator(op) => op == b'~' || op == b'+',
+                        Component::Compound(_) => true,
+                    }) && !is_descendant_after(rest);
+                    if (*op1 == b'~' && !siblings)
+                        || (*op1 != b'~' && rest.len() > 1)
+                    {
+                        return false;
+                    }
+                }
+                i1 += 2;
+                i2 = after + 1;
+            }
+            (Component::Combinator(_), _) => return false,
+            (_, Component::Combinator(op2)) => {
+                if *op2 != b'>' {
+                    return false;
+                }
+                i1 += 1;
+                i2 = after + 1;
+            }
+            _ => {
+                i1 += 1;
+                i2 = after;
+            }
+        }
+    }
+}
+
+/// True if any two adjacent compound selectors in `complex` are
+/// joined by the (implicit) descendant combinator.
+fn is_descendant_after(complex: &[Component]) -> bool {
+    complex
+        .windows(2)
+        .any(|w| w[0].compound().is_some() && w[1].compound().is_some())
+}
+
+/// Check if `compound1` matches every element that `compound2`
+/// matches, given that `compound2` is preceded by `parents`.
+pub(crate) fn compound_is_superselector(
+    compound1: &[SelectorPart],
+    compound2: &[SelectorPart],
+    parents: &[Component],
+) -> bool {
+    // A pseudo-element changes the target of a compound selector, so
+    // if either selector has one, both must have the same, and the
+    // parts before and after it are compared separately.
+    let pe1 = compound1.iter().position(SelectorPart::is_pseudo_element);
+    let pe2 = compound2.iter().position(SelectorPart::is_pseudo_element);
+    match (pe1, pe2) {
+        (Some(i1), Some(i2)) => {
+            simple_is_superselector(&compound1[i1], &compound2[i2])
+                && compound_parts_is_superselector(
+                    &compound1[..i1],
+                    &compound2[..i2],
+                    parents,
+                )
+                && compound_parts_is_superselector(
+                    &compound1[i1 + 1..],
+                    &compound2[i2 + 1..],
+                    parents,
+                )
+        }
+        (None, None) => {
+            compound_parts_is_superselector(compound1, compound2, parents)
+        }
+        _ => false,
+    }
+}
+
+fn compound_parts_is_superselector(
+    compound1: &[SelectorPart],
+    compound2: &[SelectorPart],
+    parents: &[Component],
+) -> bool {
+    let universal = [SelectorPart::Type {
+        ns: Some("*".into()),
+        name: "*".into(),
+    }];
+    let compound2 = if compound2.is_empty() {
+        &universal[..]
+    } else {
+        compound2
+    };
+    compound1.iter().all(|simple1| {
+        if simple1.selector_arg().is_some() {
+            selector_pseudo_is_superselector(simple1, compound2, parents)
+        } else {
+            compound2
+                .iter()
+                .any(|simple2| simple_is_superselector(simple1, simple2))
+        }
+    })
+}
+
+/// Check if `simple1` matches every element that `simple2` matches.
+pub(crate) fn simple_is_superselector(
+    simple1: &SelectorPart,
+    simple2: &SelectorPart,
+) -> bool {
+    if simple1 == simple2 {
+        return true;
+    }
+    match *simple1 {
+        SelectorPart::Type { ref ns, .. } if simple1.is_universal() => {
+            match (ns_str(ns), simple2) {
+                (Some("*"), _) => true,
+                (ns1, SelectorPart::Type { ref ns, .. }) => ns1 == ns_str(ns),
+                (None, _) => true,
+                _ => pseudo_contains_superselector(simple1, simple2),
+            }
+        }
+        SelectorPart::Type {
+            ns: ref ns1,
+            name: ref name1,
+        } => {
+            pseudo_contains_superselector(simple1, simple2)
+                || match *simple2 {
+                    SelectorPart::Type {
+                        ns: ref ns2,
+                        name: ref name2,
+                    } => {
+                        name1 == name2
+                            && (ns_str(ns1) == Some("*")
+                                || ns_str(ns1) == ns_str(ns2))
+                    }
+                    _ => false,
+                }
+        }
+        SelectorPart::Pseudo { .. } | SelectorPart::PseudoElement { .. }
+            if simple1.selector_arg().is_some() =>
+        {
+            if simple1.is_pseudo_element()
+                && simple2.is_pseudo_element()
+                && simple1.normalized_name() == Some("slotted")
+                && same_pseudo_name(simple1, simple2)
+            {
+                match (simple1.selector_arg(), simple2.selector_arg()) {
+                    (Some(arg1), Some(arg2)) => list_is_superselector(
+                        &arg1.complexes(),
+                        &arg2.complexes(),
+                    ),
+                    _ => false,
+                }
+            } else {
+                compound_is_superselector(
+                    std::slice::from_ref(simple1),
+                    std::slice::from_ref(simple2),
+                    &[],
+                )
+            }
+        }
+        _ => pseudo_contains_superselector(simple1, simple2),
+    }
+}
+
+/// Check if `simple2` is a pseudo selector that only matches elements
+/// matched by `simple1`, such as `:is(.a.b, .a.c)` for `.a`.
+fn pseudo_contains_superselector(
+    simple1: &SelectorPart,
+    simple2: &SelectorPart,
+) -> bool {
+    if let SelectorPart::Pseudo {
+        selector: Some(ref list),
+        ..
+    } = *simple2
+    {
+        match simple2.normalized_name() {
+            Some("is")
+            | Some("matches")
+            | Some("where")
+            | Some("any")
+            | Some("nth-child")
+            | Some("nth-last-child") => {
+                list.complexes().iter().all(|complex| {
+                    complex
+                        .last()
+                        .and_then(Component::compound)
+                        .map(|c| {
+                            c.iter()
+                                .any(|s| simple_is_superselector(simple1, s))
+                        })
+                        .unwrap_or(false)
+                })
+            }
+            _ => false,
+        }
+    } else {
+        false
+    }
+}
+
+/// Check if the selector pseudo `pseudo1` matches every element that
+/// `compound2` matches, given that `compound2` is preceded by
+/// `parents`.
+fn selector_pseudo_is_superselector(
+    pseudo1: &SelectorPart,
+    compound2: &[SelectorPart],
+    parents: &[Component],
+) -> bool {
+    let list1 = match pseudo1.selector_arg() {
+        Some(arg) => arg.complexes(),
+        None => return false,
+    };
+    let args2 = || pseudo_args(compound2, pseudo1);
+    match pseudo1.normalized_name() {
+        Some("is") | Some("matches") | Some("any") | Some("where") => {
+            args2().any(|list2| list_is_superselector(&list1, &list2))
+                || list1.iter().any(|complex1| {
+                    let mut complex2 = parents.to_vec();
+                    complex2.push(Component::Compound(compound2.to_vec()));
+                    complex_is_superselector(complex1, &complex2)
+                })
+        }
+        Some("has") | Some("host") | Some("host-context")
+        | Some("slotted") => {
+            args2().any(|list2| list_is_superselector(&list1, &list2))
+        }
+        Some("not") => list1.iter().all(|complex| {
+            let last = complex.last().and_then(Component::compound);
+            compound2.iter().any(|simple2| match *simple2 {
+                SelectorPart::Type { .. } if !simple2.is_universal() => last
+                    .map(|c| {
+                        c.iter().any(|simple1| {
+                            is_type(simple1) && simple1 != simple2
+                        })
+                    })
+                    .unwrap_or(false),
+                SelectorPart::Id(_) => last
+                    .map(|c| {
+                        c.iter().any(|simple1| {
+                            is_id(simple1) && simple1 != simple2
+                        })
+                    })
+                    .unwrap_or(false),
+                SelectorPart::Pseudo { .. }
+                    if same_pseudo_name(pseudo1, simple2) =>
+                {
+                    simple2
+                        .selector_arg()
+                        .map(|list2| {
+                            list_is_superselector(
+                                &list2.complexes(),
+                                std::slice::from_ref(complex),
+                            )
+                        })
+                        .unwrap_or(false)
+                }
+                _ => false,
+            })
+        }),
+        Some("current") => args2().any(|list2| list1 == list2),
+        Some("nth-child") | Some("nth-last-child") => {
+            compound2.iter().any(|pseudo2| {
+                same_pseudo_name(pseudo1, pseudo2)
+                    && pseudo1.plain_arg() == pseudo2.plain_arg()
+                    && pseudo2
+                        .selector_arg()
+                        .map(|list2| {
+                            list_is_superselector(&list1, &list2.complexes())
+                        })
+                        .unwrap_or(false)
+            })
+        }
+        _ => false,
+    }
+}
+
+/// The selector arguments of the pseudo selectors in `compound` that
+/// are of the same kind and name as `pseudo`.
+fn pseudo_args<'a>(
+    compound: &'a [SelectorPart],
+    pseudo: &'a SelectorPart,
+) -> impl Iterator<Item = Vec<Vec<Component>>> + 'a {
+    compound
+        .iter()
+        .filter(move |s| same_pseudo_name(pseudo, s))
+        .filter_map(|s| s.selector_arg())
+        .map(Selectors::complexes)
+}
+
+fn same_pseudo_name(a: &SelectorPart, b: &SelectorPart) -> bool {
+    match (a, b) {
+        (
+            SelectorPart::Pseudo { name: ref a, .. },
+            SelectorPart::Pseudo { name: ref b, .. },
+        )
+        | (
+            SelectorPart::PseudoElement { name: ref a, .. },
+            SelectorPart::PseudoElement { name: ref b, .. },
+        ) => a == b,
+        _ => false,
+    }
+}
+
+/// A pseudo selector name without any vendor prefix.
+pub(crate) fn unprefixed(name: &str) -> &str {
+    if name.starts_with('-') {
+        match name[1..].find('-') {
+            Some(i) => &name[i + 2..],
+            None => name,
+        }
+    } else {
+        name
+    }
+}
+
+fn is_type(simple: &SelectorPart) -> bool {
+    match simple {
+        SelectorPart::Type { .. } => !simple.is_universal(),
+        _ => false,
+    }
+}
+
+pub(crate) fn is_id(simple: &SelectorPart) -> bool {
+    match simple {
+        SelectorPart::Id(_) => true,
+        _ => false,
+    }
+}
+
+pub(crate) fn ns_str(ns: &Option<SassString>) -> Option<&str> {
+    ns.as_ref().map(|ns| ns.single_raw().unwrap_or_default())
+}
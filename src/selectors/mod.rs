@@ -0,0 +1,762 @@
+//! This module contains types for the selectors of a rule.
+//!
+//! Basically, in a rule like `p.foo, .foo p { some: thing; }` there
+//! is a `Selectors` object which contains two `Selector` objects, one
+//! for `p.foo` and one for `.foo p`.
+//!
+//! Each `Selector` is a sequence of `SelectorPart`s, that are either
+//! simple selectors (type, class, id, attribute, pseudo-class, ...)
+//! or combinators.  A run of simple selectors without combinators
+//! between them is a compound selector, e.g. `p.foo:hover`.
+use self::complex::{list_is_superselector, Component};
+use self::unify::{extend_list, unify_complex};
+use crate::css::Value;
+use crate::error::Error;
+use crate::sass::SassString;
+use crate::value::{ListSeparator, Quotes};
+use crate::variablescope::Scope;
+use std::fmt;
+use std::io::Write;
+
+mod complex;
+mod specificity;
+mod unify;
+
+pub(crate) use self::complex::unprefixed;
+pub use self::specificity::Specificity;
+
+/// A full set of selectors
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Selectors {
+    pub s: Vec<Selector>,
+    backref: Selector,
+}
+
+impl Selectors {
+    /// Create a root (empty) selector.
+    pub fn root() -> Self {
+        Selectors::new(vec![Selector::root()])
+    }
+    pub fn new(s: Vec<Selector>) -> Self {
+        Selectors {
+            s,
+            backref: Selector::root(),
+        }
+    }
+    pub fn one(&self) -> Selector {
+        self.s.first().cloned().unwrap_or_else(Selector::root)
+    }
+    /// Create the full selector for when self is used inside a parent selector.
+    ///
+    /// Fails if a parent selector suffix (as in `&-foo`) can't be
+    /// applied to the parent.
+    pub fn inside(&self, parent: &Self) -> Result<Self, Error> {
+        let mut result = Vec::new();
+        for p in &parent.s {
+            for s in &self.s {
+                result.push(p.join(s, &parent.backref)?);
+            }
+        }
+        Ok(Selectors {
+            s: result,
+            backref: parent.backref.clone(),
+        })
+    }
+    pub fn with_backref(self, context: Selector) -> Result<Self, Error> {
+        self.inside(&Selectors {
+            s: vec![Selector::root()],
+            backref: context,
+        })
+    }
+    /// Create a sass `Value` representing this set of selectors.
+    pub fn to_value(&self) -> Value {
+        let content = self
+            .s
+            .iter()
+            .map(|s: &Selector| {
+                Value::List(
+                    format!("{}", s)
+                        .split_whitespace()
+                        .map(|p| Value::Literal(p.to_string(), Quotes::None))
+                        .collect(),
+                    ListSeparator::Space,
+                    false,
+                )
+            })
+            .collect::<Vec<_>>();
+        let sep = if content.len() == 1 {
+            ListSeparator::Space
+        } else {
+            ListSeparator::Comma
+        };
+        Value::List(content, sep, false)
+    }
+    pub fn eval(&self, scope: &dyn Scope) -> Result<Selectors, Error> {
+        let s = Selectors::new(
+            self.s
+                .iter()
+                .map(|s| s.eval(scope))
+                .collect::<Result<Vec<_>, Error>>()?,
+        );
+        if !self.is_interpolated() {
+            return Ok(s);
+        }
+        // Interpolations may contain any selector syntax, including
+        // combinators and selector separators (i.e. ","), so the
+        // interpolated selectors are parsed into their final parts here.
+        use crate::parser::selectors::selectors;
+        Ok(selectors(format!("{} ", s).as_bytes())?.1)
+    }
+    fn is_interpolated(&self) -> bool {
+        self.s
+            .iter()
+            .any(|s| s.0.iter().any(SelectorPart::is_interpolated))
+    }
+
+    /// True if this matches every element that `sub` matches.
+    pub fn is_superselector(&self, sub: &Selectors) -> bool {
+        list_is_superselector(&self.complexes(), &sub.complexes())
+    }
+
+    /// Create selectors that match only elements that are matched by
+    /// both self and `other`.
+    ///
+    /// Returns `None` if no element can match both.
+    pub fn unify(&self, other: &Selectors) -> Option<Selectors> {
+        let mut result = Vec::new();
+        for c1 in self.complexes() {
+            for c2 in other.complexes() {
+                for unified in unify_complex(&c1, &c2) {
+                    if !result.contains(&unified) {
+                        result.push(unified);
+                    }
+                }
+            }
+        }
+        if result.is_empty() {
+            None
+        } else {
+            Some(Selectors::from_complexes(&result))
+        }
+    }
+
+    /// Extend self as with `@extend`, so that `extender` matches
+    /// wherever `extendee` does.
+    ///
+    /// The `extendee` must consist of compound selectors.
+    pub fn extend(
+        &self,
+        extendee: &Selectors,
+        extender: &Selectors,
+    ) -> Result<Selectors, Error> {
+        self.do_extend(extendee, extender, false)
+    }
+
+    /// Replace `original` with `replacement` everywhere in self.
+    ///
+    /// The `original` must consist of compound selectors.
+    pub fn replace(
+        &self,
+        original: &Selectors,
+        replacement: &Selectors,
+    ) -> Result<Selectors, Error> {
+        self.do_extend(original, replacement, true)
+    }
+
+    fn do_extend(
+        &self,
+        extendee: &Selectors,
+        extender: &Selectors,
+        replace: bool,
+    ) -> Result<Selectors, Error> {
+        let targets = extendee
+            .s
+            .iter()
+            .map(|s| match s.components().as_slice() {
+                [Component::Compound(c)] => Ok(c.clone()),
+                _ => Err(Error::S(format!(
+                    "Can't extend complex selector {}.",
+                    s
+                ))),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Selectors::from_complexes(&extend_list(
+            &self.complexes(),
+            &targets,
+            &extender.complexes(),
+            replace,
+        )))
+    }
+
+    fn from_complexes(complexes: &[Vec<Component>]) -> Selectors {
+        Selectors::new(
+            complexes
+                .iter()
+                .map(|c| Selector::from_components(c))
+                .collect(),
+        )
+    }
+}
+
+/// A css (or sass) selector.
+///
+/// A selector does not contain `,`.  If it does, it is a `Selectors`,
+/// where each of the parts separated by the comma is a `Selector`.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Selector(pub Vec<SelectorPart>);
+
+impl Selector {
+    pub fn root() -> Self {
+        Selector(vec![])
+    }
+
+    /// The simple selectors of this selector, if it is a compound
+    /// selector.
+    pub fn simple_selectors(&self) -> Option<Vec<SelectorPart>> {
+        match self.components().as_slice() {
+            [Component::Compound(c)] => Some(c.clone()),
+            _ => None,
+        }
+    }
+
+    fn join(
+        &self,
+        other: &Selector,
+        alt_context: &Selector,
+    ) -> Result<Selector, Error> {
+        if other.0.iter().any(|p| match p {
+            SelectorPart::BackRef(_) => true,
+            _ => false,
+        }) {
+            let parent = if self.0.is_empty() { alt_context } else { self };
+            let mut result = Vec::new();
+            for part in &other.0 {
+                match *part {
+                    SelectorPart::BackRef(None) => {
+                        result.extend(parent.0.iter().cloned())
+                    }
+                    SelectorPart::BackRef(Some(ref suffix)) => {
+                        result.extend(parent.with_suffix(suffix)?.0)
+                    }
+                    ref part => result.push(part.clone()),
+                }
+            }
+            Ok(Selector(result))
+        } else {
+            let mut result = self.0.clone();
+            if !result.is_empty()
+                && !other.0.first().map(|p| p.is_operator()).unwrap_or(false)
+            {
+                result.push(SelectorPart::Descendant);
+            }
+            result.extend(other.0.iter().cloned());
+            Ok(Selector(result))
+        }
+    }
+
+    /// This selector, with `suffix` appended to the name of its last
+    /// simple selector.
+    fn with_suffix(&self, suffix: &SassString) -> Result<Selector, Error> {
+        let mut result = self.0.clone();
+        match result.last_mut() {
+            Some(SelectorPart::Type { ref mut name, .. })
+                if name.single_raw() != Some("*") =>
+            {
+                name.append(suffix)
+            }
+            Some(SelectorPart::Class(ref mut name))
+            | Some(SelectorPart::Id(ref mut name))
+            | Some(SelectorPart::Placeholder(ref mut name)) => {
+                name.append(suffix)
+            }
+            None => {
+                return Err(Error::S(
+                    "Top-level selectors may not contain the parent \
+                     selector \"&\"."
+                        .into(),
+                ))
+            }
+            Some(SelectorPart::Descendant) | Some(SelectorPart::RelOp(_)) => {
+                return Err(Error::S(format!(
+                    "Parent \"{}\" is incompatible with this selector.",
+                    self
+                )))
+            }
+            Some(last) => {
+                return Err(Error::S(format!(
+                    "Selector \"{}\" can't have a suffix.",
+                    last
+                )))
+            }
+        }
+        Ok(Selector(result))
+    }
+
+    fn eval(&self, scope: &dyn Scope) -> Result<Selector, Error> {
+        self.0
+            .iter()
+            .map(|sp| sp.eval(scope))
+            .collect::<Result<_, _>>()
+            .map(Selector)
+    }
+}
+
+/// A selector consist of a sequence of these parts.
+///
+/// Before evaluation, the names may contain interpolation, that can
+/// hide any selector syntax.  Such selectors are parsed again after
+/// evaluation, so an evaluated selector has its final parts.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SelectorPart {
+    /// A type selector (element name), e.g. `p`, or the universal
+    /// selector `*`, with an optional namespace (as in `svg|rect`).
+    Type {
+        ns: Option<SassString>,
+        name: SassString,
+    },
+    /// A class selector, e.g. `.foo` (the name is `foo`).
+    Class(SassString),
+    /// An id selector, e.g. `#foo` (the name is `foo`).
+    Id(SassString),
+    /// A sass placeholder selector, e.g. `%foo` (the name is `foo`).
+    Placeholder(SassString),
+    /// A keyframe selector percentage, e.g. `50%` (the value is `50`).
+    Percentage(SassString),
+    /// The empty relational operator.
+    ///
+    /// The thing after this is a descendant of the thing before this.
+    Descendant,
+    /// A relational operator; `>`, `+`, `~`.
+    RelOp(u8),
+    /// An attribute selector, e.g. `[href^="http"]` or `[xlink|href]`.
+    ///
+    /// The `op` is empty for a selector without value.
+    Attribute {
+        ns: Option<SassString>,
+        name: SassString,
+        op: String,
+        val: SassString,
+        modifier: Option<char>,
+    },
+    /// A css3 pseudo-element (::foo)
+    PseudoElement {
+        name: SassString,
+        arg: Option<SassString>,
+        selector: Option<Selectors>,
+    },
+    /// A pseudo-class or a css2 pseudo-element (:foo)
+    ///
+    /// The argument in parenthesis is either a plain `arg` (as in
+    /// `:lang(en)`), a `selector` (as in `:not(.foo)`), or both (as in
+    /// `:nth-child(2n+1 of .foo)`).
+    Pseudo {
+        name: SassString,
+        arg: Option<SassString>,
+        selector: Option<Selectors>,
+    },
+    /// A sass backref (`&`), to be replaced with outer selector.
+    ///
+    /// The backref may have a suffix, as in `&-foo` or `&__elem`,
+    /// that is appended to the last simple selector of the outer
+    /// selector.
+    BackRef(Option<SassString>),
+}
+
+impl SelectorPart {
+    /// Create a type selector without namespace.
+    pub fn type_sel(name: &str) -> Self {
+        SelectorPart::Type {
+            ns: None,
+            name: name.into(),
+        }
+    }
+    /// True for the universal selector, `*` (in any namespace).
+    pub fn is_universal(&self) -> bool {
+        match *self {
+            SelectorPart::Type { ref name, .. } => {
+                name.single_raw() == Some("*")
+            }
+            _ => false,
+        }
+    }
+    fn is_operator(&self) -> bool {
+        match *self {
+            SelectorPart::Descendant | SelectorPart::RelOp(_) => true,
+            SelectorPart::Type { .. }
+            | SelectorPart::Class(_)
+            | SelectorPart::Id(_)
+            | SelectorPart::Placeholder(_)
+            | SelectorPart::Percentage(_)
+            | SelectorPart::Attribute { .. }
+            | SelectorPart::PseudoElement { .. }
+            | SelectorPart::Pseudo { .. }
+            | SelectorPart::BackRef(_) => false,
+        }
+    }
+
+    fn is_interpolated(&self) -> bool {
+        match *self {
+            SelectorPart::Type { ref ns, ref name } => {
+                name.is_interpolated()
+                    || ns
+                        .as_ref()
+                        .map(|ns| ns.is_interpolated())
+                        .unwrap_or(false)
+            }
+            SelectorPart::Class(ref name)
+            | SelectorPart::Id(ref name)
+            | SelectorPart::Placeholder(ref name)
+            | SelectorPart::Percentage(ref name) => name.is_interpolated(),
+            SelectorPart::Attribute {
+                ref ns,
+                ref name,
+                ref val,
+                ..
+            } => {
+                name.is_interpolated()
+                    || val.is_interpolated()
+                    || ns
+                        .as_ref()
+                        .map(|ns| ns.is_interpolated())
+                        .unwrap_or(false)
+            }
+            SelectorPart::PseudoElement {
+                ref name,
+                ref arg,
+                ref selector,
+            }
+            | SelectorPart::Pseudo {
+                ref name,
+                ref arg,
+                ref selector,
+            } => {
+                name.is_interpolated()
+                    || arg
+                        .as_ref()
+                        .map(|a| a.is_interpolated())
+                        .unwrap_or(false)
+                    || selector
+                        .as_ref()
+                        .map(|s| s.is_interpolated())
+                        .unwrap_or(false)
+            }
+            SelectorPart::BackRef(ref suffix) => suffix
+                .as_ref()
+                .map(|s| s.is_interpolated())
+                .unwrap_or(false),
+            SelectorPart::Descendant | SelectorPart::RelOp(_) => false,
+        }
+    }
+
+    fn eval(&self, scope: &dyn Scope) -> Result<SelectorPart, Error> {
+        match *self {
+            SelectorPart::Attribute {
+                ref ns,
+                ref name,
+                ref op,
+                ref val,
+                ref modifier,
+            } => Ok(SelectorPart::Attribute {
+                ns: match ns {
+                    Some(ns) => Some(ns.evaluate2(scope)?),
+                    None => None,
+                },
+                name: name.evaluate2(scope)?,
+                op: op.clone(),
+                val: val.evaluate2(scope)?,
+                modifier: *modifier,
+            }),
+            SelectorPart::Type { ref ns, ref name } => {
+                Ok(SelectorPart::Type {
+                    ns: match ns {
+                        Some(ns) => Some(ns.evaluate2(scope)?),
+                        None => None,
+                    },
+                    name: name.evaluate2(scope)?,
+                })
+            }
+            SelectorPart::Class(ref v) => {
+                Ok(SelectorPart::Class(v.evaluate2(scope)?))
+            }
+            SelectorPart::Id(ref v) => {
+                Ok(SelectorPart::Id(v.evaluate2(scope)?))
+            }
+            SelectorPart::Placeholder(ref v) => {
+                Ok(SelectorPart::Placeholder(v.evaluate2(scope)?))
+            }
+            SelectorPart::Percentage(ref v) => {
+                Ok(SelectorPart::Percentage(v.evaluate2(scope)?))
+            }
+            SelectorPart::Pseudo {
+                ref name,
+                ref arg,
+                ref selector,
+            } => Ok(SelectorPart::Pseudo {
+                name: name.evaluate2(scope)?,
+                arg: match arg {
+                    Some(a) => Some(a.evaluate2(scope)?),
+                    None => None,
+                },
+                selector: match selector {
+                    Some(s) => Some(s.eval(scope)?),
+                    None => None,
+                },
+            }),
+            SelectorPart::PseudoElement {
+                ref name,
+                ref arg,
+                ref selector,
+            } => Ok(SelectorPart::PseudoElement {
+                name: name.evaluate2(scope)?,
+                arg: match arg {
+                    Some(a) => Some(a.evaluate2(scope)?),
+                    None => None,
+                },
+                selector: match selector {
+                    Some(s) => Some(s.eval(scope)?),
+                    None => None,
+                },
+            }),
+            SelectorPart::BackRef(Some(ref suffix)) => {
+                Ok(SelectorPart::BackRef(Some(suffix.evaluate2(scope)?)))
+            }
+            ref sp => Ok(sp.clone()),
+        }
+    }
+}
+
+// TODO:  This shoule probably be on Formatted<Selectors> instead.
+impl fmt::Display for Selectors {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        if let Some((first, rest)) = self.s.split_first() {
+            first.fmt(out)?;
+            let separator = if out.alternate() { "," } else { ", " };
+            for item in rest {
+                out.write_str(separator)?;
+                item.fmt(out)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Selector {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        // Note: There should be smarter whitespace-handling here, avoiding
+        // the need to clean up afterwards.
+        let mut buf = vec![];
+        for p in &self.0 {
+            if out.alternate() {
+                write!(&mut buf, "{:#}", p).map_err(|_| fmt::Error)?;
+            } else {
+                write!(&mut buf, "{}", p).map_err(|_| fmt::Error)?;
+            }
+        }
+        while buf.last() == Some(&b' ') {
+            buf.pop();
+        }
+        while buf.first() == Some(&b' ') {
+            buf.remove(0);
+        }
+        let buf = String::from_utf8(buf).map_err(|_| fmt::Error)?;
+        out.write_str(&buf.replace("  ", " "))
+    }
+}
+
+impl fmt::Display for SelectorPart {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            SelectorPart::Type { ref ns, ref name } => {
+                if let Some(ref ns) = *ns {
+                    write!(out, "{}|", ns)?;
+                }
+                write!(out, "{}", name)
+            }
+            SelectorPart::Class(ref s) => write!(out, ".{}", s),
+            SelectorPart::Id(ref s) => write!(out, "#{}", s),
+            SelectorPart::Placeholder(ref s) => write!(out, "%{}", s),
+            SelectorPart::Percentage(ref s) => write!(out, "{}%", s),
+            SelectorPart::Descendant => write!(out, " "),
+            SelectorPart::RelOp(ref c) => {
+                if out.alternate() && *c != b'~' {
+                    write!(out, "{}", *c as char)
+                } else {
+                    write!(out, " {} ", *c as char)
+                }
+            }
+            SelectorPart::Attribute {
+                ref ns,
+                ref name,
+                ref op,
+                ref val,
+                ref modifier,
+            } => {
+                out.write_str("[")?;
+                if let Some(ref ns) = *ns {
+                    write!(out, "{}|", ns)?;
+                }
+                write!(
+                    out,
+                    "{}{}{}{}]",
+                    name,
+                    op,
+                    val,
+                    modifier.map(|m| format!(" {}", m)).unwrap_or_default()
+                )
+            }
+            SelectorPart::PseudoElement {
+                ref name,
+                ref arg,
+                ref selector,
+            } => {
+                write!(out, "::{}", name)?;
+                write_pseudo_args(out, arg, selector)
+            }
+            SelectorPart::Pseudo {
+                ref name,
+                ref arg,
+                ref selector,
+            } => {
+                write!(out, ":{}", name)?;
+                write_pseudo_args(out, arg, selector)
+            }
+            SelectorPart::BackRef(ref suffix) => {
+                out.write_str("&")?;
+                if let Some(ref suffix) = *suffix {
+                    write!(out, "{}", suffix)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+fn write_pseudo_args(
+    out: &mut fmt::Formatter,
+    arg: &Option<SassString>,
+    selector: &Option<Selectors>,
+) -> fmt::Result {
+    match (arg, selector) {
+        (Some(arg), Some(selector)) if out.alternate() => {
+            write!(out, "({} of {:#})", arg, selector)
+        }
+        (Some(arg), Some(selector)) => {
+            write!(out, "({} of {})", arg, selector)
+        }
+        (Some(arg), None) => write!(out, "({})", arg),
+        (None, Some(selector)) if out.alternate() => {
+            write!(out, "({:#})", selector)
+        }
+        (None, Some(selector)) => write!(out, "({})", selector),
+        (None, None) => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn root_join() {
+        let s = Selector(vec![SelectorPart::type_sel("foo")]);
+        assert_eq!(Selector::root().join(&s, &Selector::root()).unwrap(), s)
+    }
+
+    #[test]
+    fn simple_join() {
+        let s = Selector(vec![SelectorPart::type_sel("foo")]).join(
+            &Selector(vec![SelectorPart::Class("bar".into())]),
+            &Selector::root(),
+        );
+        assert_eq!(format!("{}", s.unwrap()), "foo .bar")
+    }
+
+    #[test]
+    fn eval_interpolated() {
+        use crate::parser::selectors::selectors;
+        use crate::variablescope::GlobalScope;
+        let mut scope = GlobalScope::new(Default::default());
+        scope.define("x", &Value::Literal("b, .c > d".into(), Quotes::None));
+        let (_, s) = selectors(b"a.#{$x} ").unwrap();
+        let s = s.eval(&scope).unwrap();
+        assert_eq!(
+            s.s,
+            vec![
+                Selector(vec![
+                    SelectorPart::type_sel("a"),
+                    SelectorPart::Class("b".into()),
+                ]),
+                Selector(vec![
+                    SelectorPart::Class("c".into()),
+                    SelectorPart::RelOp(b'>'),
+                    SelectorPart::type_sel("d"),
+                ]),
+            ]
+        )
+    }
+
+    #[test]
+    fn backref_join() {
+        let s = Selector(vec![SelectorPart::type_sel("foo")]).join(
+            &Selector(vec![
+                SelectorPart::BackRef(None),
+                SelectorPart::Class("bar".into()),
+            ]),
+            &Selector::root(),
+        );
+        assert_eq!(format!("{}", s.unwrap()), "foo.bar")
+    }
+
+    fn nested(parent: &str, child: &str) -> Result<String, Error> {
+        use crate::parser::selectors::selectors;
+        let parent = selectors(format!("{} ", parent).as_bytes()).unwrap().1;
+        let child = selectors(format!("{} ", child).as_bytes()).unwrap().1;
+        child.inside(&parent).map(|s| s.to_string())
+    }
+
+    #[test]
+    fn suffix() {
+        assert_eq!(nested(".block", "&__elem").unwrap(), ".block__elem");
+        assert_eq!(
+            nested(".block__elem", "&--mod").unwrap(),
+            ".block__elem--mod"
+        );
+    }
+    #[test]
+    fn suffix_is_structural() {
+        use crate::parser::selectors::selectors;
+        let parent = selectors(b".a ").unwrap().1;
+        let child = selectors(b"&-b ").unwrap().1;
+        assert_eq!(
+            child.inside(&parent).unwrap().s,
+            vec![Selector(vec![SelectorPart::Class("a-b".into())])]
+        );
+    }
+    #[test]
+    fn suffix_multiple_parents() {
+        assert_eq!(nested(".a, .b p", "&-x").unwrap(), ".a-x, .b p-x");
+    }
+    #[test]
+    fn suffix_and_more() {
+        assert_eq!(nested("div", "&-x.y + &").unwrap(), "div-x.y + div");
+    }
+    #[test]
+    fn suffix_on_pseudo() {
+        match nested("a:hover", "&-x") {
+            Err(Error::S(msg)) => {
+                assert_eq!(msg, "Selector \":hover\" can't have a suffix.")
+            }
+            r => panic!("Unexpected result {:?}", r),
+        }
+    }
+    #[test]
+    fn suffix_on_combinator() {
+        match nested("a >", "&-x") {
+            Err(Error::S(msg)) => assert_eq!(
+                msg,
+                "Parent \"a >\" is incompatible with this selector."
+            ),
+            r => panic!("Unexpected result {:?}", r),
+        }
+    }
+}
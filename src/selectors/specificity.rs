@@ -0,0 +1,133 @@
+//! Specificity of selectors, as defined in Selectors Level 4.
+//!
+//! See <https://www.w3.org/TR/selectors-4/#specificity-rules>.
+use super::{Selector, SelectorPart, Selectors};
+
+/// A specificity is the count of id selectors, of class-like
+/// selectors (classes, attributes and pseudo-classes), and of type
+/// selectors and pseudo-elements.
+pub type Specificity = (u32, u32, u32);
+
+impl Selectors {
+    /// The specificity of the most specific selector in this list.
+    pub fn specificity(&self) -> Specificity {
+        self.s
+            .iter()
+            .map(Selector::specificity)
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+impl Selector {
+    /// The specificity of this selector.
+    ///
+    /// The universal selector and combinators do not count.
+    /// `:is()`, `:not()` and `:has()` count as their most specific
+    /// argument, `:where()` counts as nothing, and `:nth-child(An+B
+    /// of S)` counts as a pseudo-class plus the most specific selector
+    /// in `S`.  Placeholder selectors count as classes.  Keyframe
+    /// percentages are not selectors of elements and do not count.
+    pub fn specificity(&self) -> Specificity {
+        self.0
+            .iter()
+            .map(SelectorPart::specificity)
+            .fold((0, 0, 0), add)
+    }
+}
+
+impl SelectorPart {
+    fn specificity(&self) -> Specificity {
+        match *self {
+            SelectorPart::Type { .. } if self.is_universal() => (0, 0, 0),
+            SelectorPart::Type { .. } => (0, 0, 1),
+            SelectorPart::Id(_) => (1, 0, 0),
+            SelectorPart::Class(_)
+            | SelectorPart::Placeholder(_)
+            | SelectorPart::Attribute { .. } => (0, 1, 0),
+            SelectorPart::Pseudo { ref selector, .. }
+            | SelectorPart::PseudoElement { ref selector, .. } => {
+                let own = if self.is_pseudo_element() {
+                    (0, 0, 1)
+                } else {
+                    (0, 1, 0)
+                };
+                let inner = match (self.normalized_name(), selector) {
+                    (_, None) => return own,
+                    (Some("where"), Some(_)) => return (0, 0, 0),
+                    (Some("is"), Some(list))
+                    | (Some("matches"), Some(list))
+                    | (Some("any"), Some(list))
+                    | (Some("not"), Some(list))
+                    | (Some("has"), Some(list)) => return list.specificity(),
+                    (Some("host"), Some(list))
+                    | (Some("host-context"), Some(list))
+                    | (Some("slotted"), Some(list))
+                    | (Some("nth-child"), Some(list))
+                    | (Some("nth-last-child"), Some(list)) => {
+                        list.specificity()
+                    }
+                    (_, Some(_)) => (0, 0, 0),
+                };
+                add(own, inner)
+            }
+            SelectorPart::Descendant
+            | SelectorPart::RelOp(_)
+            | SelectorPart::Percentage(_)
+            | SelectorPart::BackRef(_) => (0, 0, 0),
+        }
+    }
+}
+
+fn add(a: Specificity, b: Specificity) -> Specificity {
+    (a.0 + b.0, a.1 + b.1, a.2 + b.2)
+}
+
+#[cfg(test)]
+mod test {
+    use super::Specificity;
+    use crate::parser::selectors::selectors;
+
+    fn specificity(s: &str) -> Specificity {
+        selectors(format!("{} ", s).as_bytes())
+            .unwrap()
+            .1
+            .specificity()
+    }
+
+    #[test]
+    fn simple() {
+        assert_eq!(specificity("*"), (0, 0, 0));
+        assert_eq!(specificity("li"), (0, 0, 1));
+        assert_eq!(specificity(".a"), (0, 1, 0));
+        assert_eq!(specificity("#a"), (1, 0, 0));
+    }
+    #[test]
+    fn complex() {
+        assert_eq!(specificity("ul li.a > a[href]:hover"), (0, 3, 3));
+        assert_eq!(specificity("#nav .item::before"), (1, 1, 1));
+        assert_eq!(specificity("p:first-line"), (0, 0, 2));
+    }
+    #[test]
+    fn list_uses_max() {
+        assert_eq!(specificity("a, #b, .c.d"), (1, 0, 0));
+    }
+    #[test]
+    fn is_and_not() {
+        assert_eq!(specificity(":is(a, #b)"), (1, 0, 0));
+        assert_eq!(specificity("a:not(.b, .c.d)"), (0, 2, 1));
+    }
+    #[test]
+    fn where_is_zero() {
+        assert_eq!(specificity("a:where(#b, .c)"), (0, 0, 1));
+    }
+    #[test]
+    fn keyframe_percentage() {
+        assert_eq!(specificity("12.5%"), (0, 0, 0));
+    }
+    #[test]
+    fn nth_child() {
+        assert_eq!(specificity("li:nth-child(2n+1)"), (0, 1, 1));
+        assert_eq!(specificity(":nth-child(2n+1 of li.a, #b)"), (1, 1, 0));
+    }
+}
@@ -0,0 +1,419 @@
+//! Unification and extension of selectors.
+//!
+//! Unifying two selectors creates a selector that matches only
+//! elements that are matched by both of them.  Extending replaces
+//! parts of a selector by the unification of the extender with the
+//! rest of the compound selector it is in.
+use super::complex::{complex_is_superselector, is_id, ns_str, Component};
+use super::SelectorPart;
+
+/// Unify two complex selectors.
+///
+/// The result is a list of complex selectors, that together matches
+/// the elements matched by both `complex1` and `complex2`.  It is
+/// empty if no element can match both.
+pub(crate) fn unify_complex(
+    complex1: &[Component],
+    complex2: &[Component],
+) -> Vec<Vec<Component>> {
+    let (base1, parents1) = match split_base(complex1) {
+        Some(split) => split,
+        None => return vec![],
+    };
+    let (base2, parents2) = match split_base(complex2) {
+        Some(split) => split,
+        None => return vec![],
+    };
+    match unify_compound(base1, base2) {
+        Some(base) => with_base(weave(parents1, parents2), &base),
+        None => vec![],
+    }
+}
+
+/// Unify two compound selectors.
+///
+/// Returns `None` if no element can match both.
+pub(crate) fn unify_compound(
+    compound1: &[SelectorPart],
+    compound2: &[SelectorPart],
+) -> Option<Vec<SelectorPart>> {
+    let mut result = compound1.to_vec();
+    for simple in compound2 {
+        result = unify_simple(simple, &result)?;
+    }
+    Some(result)
+}
+
+/// Extend every selector in `list` that contains any of the compound
+/// selectors in `targets` with each of the `extenders`.
+///
+/// If `replace` is true, the matching selectors are replaced rather
+/// than kept alongside their extensions.
+pub(crate) fn extend_list(
+    list: &[Vec<Component>],
+    targets: &[Vec<SelectorPart>],
+    extenders: &[Vec<Component>],
+    replace: bool,
+) -> Vec<Vec<Component>> {
+    let mut result = Vec::new();
+    for complex in list {
+        for extended in extend_complex(complex, targets, extenders, replace) {
+            if !result.contains(&extended) {
+                result.push(extended);
+            }
+        }
+    }
+    result
+}
+
+fn extend_complex(
+    complex: &[Component],
+    targets: &[Vec<SelectorPart>],
+    extenders: &[Vec<Component>],
+    replace: bool,
+) -> Vec<Vec<Component>> {
+    let mut result: Vec<Vec<Component>> = vec![vec![]];
+    for component in complex {
+        let compound = match component.compound() {
+            Some(compound) => compound,
+            None => {
+                for prefix in &mut result {
+                    prefix.push(component.clone());
+                }
+                continue;
+            }
+        };
+        let mut next = Vec::new();
+        for prefix in &result {
+            let mut extended = Vec::new();
+            for rest in targets.iter().filter_map(|t| without(compound, t)) {
+                for (base, parents) in
+                    extenders.iter().filter_map(|e| split_base(e))
+                {
+                    if let Some(unified) = unify_compound(&rest, base) {
+                        extended.extend(with_base(
+                            weave(prefix, parents),
+                            &unified,
+                        ));
+                    }
+                }
+            }
+            if !replace || extended.is_empty() {
+                let mut original = prefix.clone();
+                original.push(component.clone());
+                next.push(original);
+            }
+            next.extend(extended);
+        }
+        next.dedup();
+        result = next;
+    }
+    result
+}
+
+/// Combine two sequences of parent selectors, i.e. the part of a
+/// complex selector before its last compound selector, in the ways
+/// that match elements matched by both of them.
+fn weave(
+    parents1: &[Component],
+    parents2: &[Component],
+) -> Vec<Vec<Component>> {
+    if parents1.is_empty() {
+        return vec![parents2.to_vec()];
+    }
+    if parents2.is_empty() {
+        return vec![parents1.to_vec()];
+    }
+    match (split_combinator(parents1), split_combinator(parents2)) {
+        (None, None) => {
+            if complex_is_superselector(parents1, parents2) {
+                vec![parents2.to_vec()]
+            } else if complex_is_superselector(parents2, parents1) {
+                vec![parents1.to_vec()]
+            } else {
+                vec![
+                    [parents1, parents2].concat(),
+                    [parents2, parents1].concat(),
+                ]
+            }
+        }
+        (Some((c1, op1, rest1)), None) => {
+            with_combinator(weave(rest1, parents2), c1, op1)
+        }
+        (None, Some((c2, op2, rest2))) => {
+            with_combinator(weave(parents1, rest2), c2, op2)
+        }
+        (Some((c1, op1, rest1)), Some((c2, op2, rest2))) => {
+            match (op1, op2) {
+                (b'>', b'>') | (b'+', b'+') => match unify_compound(c1, c2) {
+                    Some(unified) => {
+                        with_combinator(weave(rest1, rest2), &unified, op1)
+                    }
+                    None => vec![],
+                },
+                (b'~', b'~') => {
+                    let mut result =
+                        with_combinator(weave(rest1, parents2), c1, op1);
+                    result.extend(with_combinator(
+                        weave(parents1, rest2),
+                        c2,
+                        op2,
+                    ));
+                    if let Some(unified) = unify_compound(c1, c2) {
+                        result.extend(with_combinator(
+                            weave(rest1, rest2),
+                            &unified,
+                            op1,
+                        ));
+                    }
+                    result
+                }
+                (b'~', b'+') | (b'+', b'~') => {
+                    // The general sibling comes before the adjacent one,
+                    // unless they are the same element.
+                    let (following, adjacent, adjacent_rest) = if op1 == b'+'
+                    {
+                        (parents2, c1, rest1)
+                    } else {
+                        (parents1, c2, rest2)
+                    };
+                    let mut result = with_combinator(
+                        weave(following, adjacent_rest),
+                        adjacent,
+                        b'+',
+                    );
+                    if let Some(unified) = unify_compound(c1, c2) {
+                        result.extend(with_combinator(
+                            weave(rest1, rest2),
+                            &unified,
+                            b'+',
+                        ));
+                    }
+                    result
+                }
+                (b'>', b'~') | (b'>', b'+') => {
+                    with_combinator(weave(parents1, rest2), c2, op2)
+                }
+                (b'~', b'>') | (b'+', b'>') => {
+                    with_combinator(weave(rest1, parents2), c1, op1)
+                }
+                _ => vec![],
+            }
+        }
+    }
+}
+
+/// Unify a simple selector with a compound selector.
+fn unify_simple(
+    simple: &SelectorPart,
+    compound: &[SelectorPart],
+) -> Option<Vec<SelectorPart>> {
+    match *simple {
+        SelectorPart::Type { ref ns, .. } => match compound.split_first() {
+            Some((first @ SelectorPart::Type { .. }, rest)) => {
+                let mut result = vec![unify_type(simple, first)?];
+                result.extend(rest.iter().cloned());
+                Some(result)
+            }
+            _ if simple.is_universal() => {
+                if compound.len() == 1 && compound[0].is_host() {
+                    None
+                } else if ns.is_some() && ns_str(ns) != Some("*") {
+                    Some(prefixed(simple, compound))
+                } else if !compound.is_empty() {
+                    Some(compound.to_vec())
+                } else {
+                    Some(vec![simple.clone()])
+                }
+            }
+            _ => Some(prefixed(simple, compound)),
+        },
+        SelectorPart::Id(_) => {
+            if compound.iter().any(|s| is_id(s) && s != simple) {
+                None
+            } else {
+                unify_generic(simple, compound)
+            }
+        }
+        SelectorPart::Pseudo { .. } | SelectorPart::PseudoElement { .. } => {
+            if compound.len() == 1 && compound[0].is_universal() {
+                return unify_simple(
+                    &compound[0],
+                    std::slice::from_ref(simple),
+                );
+            }
+            if compound.contains(simple) {
+                return Some(compound.to_vec());
+            }
+            // A compound selector can contain only one pseudo-element,
+            // and pseudo-classes go before it.
+            let mut result = Vec::with_capacity(compound.len() + 1);
+            let mut added = false;
+            for s in compound {
+                if s.is_pseudo_element() && !added {
+                    if simple.is_pseudo_element() {
+                        return None;
+                    }
+                    result.push(simple.clone());
+                    added = true;
+                }
+                result.push(s.clone());
+            }
+            if !added {
+                result.push(simple.clone());
+            }
+            Some(result)
+        }
+        _ => unify_generic(simple, compound),
+    }
+}
+
+fn unify_generic(
+    simple: &SelectorPart,
+    compound: &[SelectorPart],
+) -> Option<Vec<SelectorPart>> {
+    if compound.len() == 1
+        && (compound[0].is_universal() || compound[0].is_host())
+    {
+        return unify_simple(&compound[0], std::slice::from_ref(simple));
+    }
+    if compound.contains(simple) {
+        return Some(compound.to_vec());
+    }
+    // Pseudo selectors always go last.
+    let pos = compound
+        .iter()
+        .position(|s| match s {
+            SelectorPart::Pseudo { .. }
+            | SelectorPart::PseudoElement { .. } => true,
+            _ => false,
+        })
+        .unwrap_or(compound.len());
+    let mut result = compound.to_vec();
+    result.insert(pos, simple.clone());
+    Some(result)
+}
+
+/// Unify two type selectors, either of which may be universal.
+fn unify_type(
+    simple1: &SelectorPart,
+    simple2: &SelectorPart,
+) -> Option<SelectorPart> {
+    match (simple1, simple2) {
+        (
+            SelectorPart::Type {
+                ns: ns1,
+                name: name1,
+            },
+            SelectorPart::Type {
+                ns: ns2,
+                name: name2,
+            },
+        ) => {
+            let ns = if ns_str(ns1) == ns_str(ns2) || ns_str(ns2) == Some("*")
+            {
+                ns1
+            } else if ns_str(ns1) == Some("*") {
+                ns2
+            } else {
+                return None;
+            };
+            let name = if name1 == name2 || simple2.is_universal() {
+                name1
+            } else if simple1.is_universal() {
+                name2
+            } else {
+                return None;
+            };
+            Some(SelectorPart::Type {
+                ns: ns.clone(),
+                name: name.clone(),
+            })
+        }
+        _ => None,
+    }
+}
+
+/// The rest of `compound` if it contains all parts of `target`.
+fn without(
+    compound: &[SelectorPart],
+    target: &[SelectorPart],
+) -> Option<Vec<SelectorPart>> {
+    if target.iter().all(|t| compound.contains(t)) {
+        Some(
+            compound
+                .iter()
+                .filter(|s| !target.contains(s))
+                .cloned()
+                .collect(),
+        )
+    } else {
+        None
+    }
+}
+
+/// Split a complex selector into its last compound and its parents.
+fn split_base(
+    complex: &[Component],
+) -> Option<(&[SelectorPart], &[Component])> {
+    let (last, parents) = complex.split_last()?;
+    if parents.first().and_then(Component::compound).is_none()
+        && !parents.is_empty()
+    {
+        // A leading combinator.
+        return None;
+    }
+    Some((last.compound()?, parents))
+}
+
+/// Split parents that end with a combinator into the compound before
+/// the combinator, the combinator and the rest.
+fn split_combinator(
+    parents: &[Component],
+) -> Option<(&[SelectorPart], u8, &[Component])> {
+    let (op, rest) = parents.split_last()?;
+    let (c, rest) = rest.split_last()?;
+    match (c, op) {
+        (Component::Compound(c), Component::Combinator(op)) => {
+            Some((c, *op, rest))
+        }
+        _ => None,
+    }
+}
+
+fn with_combinator(
+    prefixes: Vec<Vec<Component>>,
+    compound: &[SelectorPart],
+    op: u8,
+) -> Vec<Vec<Component>> {
+    prefixes
+        .into_iter()
+        .map(|mut p| {
+            p.push(Component::Compound(compound.to_vec()));
+            p.push(Component::Combinator(op));
+            p
+        })
+        .collect()
+}
+
+fn with_base(
+    prefixes: Vec<Vec<Component>>,
+    base: &[SelectorPart],
+) -> Vec<Vec<Component>> {
+    prefixes
+        .into_iter()
+        .map(|mut p| {
+            p.push(Component::Compound(base.to_vec()));
+            p
+        })
+        .collect()
+}
+
+fn prefixed(
+    simple: &SelectorPart,
+    compound: &[SelectorPart],
+) -> Vec<SelectorPart> {
+    let mut result = vec![simple.clone()];
+    result.extend(compound.iter().cloned());
+    result
+}
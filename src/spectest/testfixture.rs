@@ -76,10 +76,8 @@ impl TestFixture {
                 if let Some(precision) = precision {
                     writeln!(
                         rs,
-                        "    let format = rsass::output::Format {{ \
-                         style: rsass::output::Style::Expanded, \
-                         precision: {} \
-                         }};",
+                        "    let format = rsass::output::Format::new(\
+                         rsass::output::Style::Expanded, {});",
                         precision,
                     )?;
                 }
@@ -116,10 +114,10 @@ impl TestFixture {
         match &self.expectation {
             ExpectedError(_) => Some("Error tests not supported yet"),
             ExpectedCSS(ref expected) => {
-                let format = Format {
-                    style: Style::Expanded,
-                    precision: self.options.precision.unwrap_or(6) as usize,
-                };
+                let format = Format::new(
+                    Style::Expanded,
+                    self.options.precision.unwrap_or(6) as usize,
+                );
                 match rsass(&self.input, format) {
                     Ok(ref actual) => {
                         if expected == actual {
@@ -0,0 +1,21 @@
+use std::fmt;
+
+/// The name of a css calculation function.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CalcName {
+    Calc,
+    Min,
+    Max,
+    Clamp,
+}
+
+impl fmt::Display for CalcName {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        out.write_str(match *self {
+            CalcName::Calc => "calc",
+            CalcName::Min => "min",
+            CalcName::Max => "max",
+            CalcName::Clamp => "clamp",
+        })
+    }
+}
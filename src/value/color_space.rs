@@ -0,0 +1,624 @@
+//! Colors in CSS Color Level 4 color spaces.
+//!
+//! A color in a wide-gamut or perceptual space can't be represented
+//! by the clamped sRGB channels of `Rgba`, so such colors keep their
+//! original channels in a `SpaceColor`.
+//! Conversions between spaces are done in `f64` through CIE XYZ (D65).
+use crate::output::{Format, Formatted};
+use crate::value::Number;
+use num_rational::Rational;
+use num_traits::{One, Zero};
+use std::fmt;
+
+/// A color space supported by `color.to-space` and friends.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum ColorSpace {
+    /// The legacy rgb space, with channels 0 .. 255.
+    Rgb,
+    Srgb,
+    SrgbLinear,
+    DisplayP3,
+    Lab,
+    Lch,
+    Oklab,
+    Oklch,
+    Xyz,
+}
+
+impl ColorSpace {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_ref() {
+            "rgb" => Some(ColorSpace::Rgb),
+            "srgb" => Some(ColorSpace::Srgb),
+            "srgb-linear" => Some(ColorSpace::SrgbLinear),
+            "display-p3" => Some(ColorSpace::DisplayP3),
+            "lab" => Some(ColorSpace::Lab),
+            "lch" => Some(ColorSpace::Lch),
+            "oklab" => Some(ColorSpace::Oklab),
+            "oklch" => Some(ColorSpace::Oklch),
+            "xyz" | "xyz-d65" => Some(ColorSpace::Xyz),
+            _ => None,
+        }
+    }
+    pub fn name(self) -> &'static str {
+        match self {
+            ColorSpace::Rgb => "rgb",
+            ColorSpace::Srgb => "srgb",
+            ColorSpace::SrgbLinear => "srgb-linear",
+            ColorSpace::DisplayP3 => "display-p3",
+            ColorSpace::Lab => "lab",
+            ColorSpace::Lch => "lch",
+            ColorSpace::Oklab => "oklab",
+            ColorSpace::Oklch => "oklch",
+            ColorSpace::Xyz => "xyz",
+        }
+    }
+    pub fn channel_names(self) -> [&'static str; 3] {
+        match self {
+            ColorSpace::Rgb
+            | ColorSpace::Srgb
+            | ColorSpace::SrgbLinear
+            | ColorSpace::DisplayP3 => ["red", "green", "blue"],
+            ColorSpace::Lab | ColorSpace::Oklab => ["lightness", "a", "b"],
+            ColorSpace::Lch | ColorSpace::Oklch => {
+                ["lightness", "chroma", "hue"]
+            }
+            ColorSpace::Xyz => ["x", "y", "z"],
+        }
+    }
+    /// True for spaces with a hue channel (the third channel).
+    pub fn is_polar(self) -> bool {
+        self == ColorSpace::Lch || self == ColorSpace::Oklch
+    }
+    /// True for spaces where every channel has a bounded range.
+    pub fn is_bounded(self) -> bool {
+        match self {
+            ColorSpace::Rgb
+            | ColorSpace::Srgb
+            | ColorSpace::SrgbLinear
+            | ColorSpace::DisplayP3 => true,
+            _ => false,
+        }
+    }
+    /// The value that 100% means for each channel.
+    pub fn percent_reference(self) -> [f64; 3] {
+        match self {
+            ColorSpace::Rgb => [255., 255., 255.],
+            ColorSpace::Lab => [100., 125., 125.],
+            ColorSpace::Lch => [100., 150., 360.],
+            ColorSpace::Oklab => [1., 0.4, 0.4],
+            ColorSpace::Oklch => [1., 0.4, 360.],
+            _ => [1., 1., 1.],
+        }
+    }
+
+    fn to_xyz(self, c: [f64; 3]) -> [f64; 3] {
+        match self {
+            ColorSpace::Rgb => ColorSpace::Srgb.to_xyz([
+                c[0] / 255.,
+                c[1] / 255.,
+                c[2] / 255.,
+            ]),
+            ColorSpace::Srgb => {
+                mul(&LINEAR_SRGB_TO_XYZ, map(c, srgb_to_linear))
+            }
+            ColorSpace::SrgbLinear => mul(&LINEAR_SRGB_TO_XYZ, c),
+            ColorSpace::DisplayP3 => {
+                mul(&LINEAR_P3_TO_XYZ, map(c, srgb_to_linear))
+            }
+            ColorSpace::Lab => mul(&D50_TO_D65, lab_to_xyz_d50(c)),
+            ColorSpace::Lch => ColorSpace::Lab.to_xyz(lch_to_lab(c)),
+            ColorSpace::Oklab => {
+                let lms = map(mul(&OKLAB_TO_LMS, c), |v| v * v * v);
+                mul(&LMS_TO_XYZ, lms)
+            }
+            ColorSpace::Oklch => ColorSpace::Oklab.to_xyz(lch_to_lab(c)),
+            ColorSpace::Xyz => c,
+        }
+    }
+
+    fn channels_from_xyz(self, xyz: [f64; 3]) -> [f64; 3] {
+        match self {
+            ColorSpace::Rgb => {
+                map(ColorSpace::Srgb.channels_from_xyz(xyz), |v| v * 255.)
+            }
+            ColorSpace::Srgb => {
+                map(mul(&XYZ_TO_LINEAR_SRGB, xyz), linear_to_srgb)
+            }
+            ColorSpace::SrgbLinear => mul(&XYZ_TO_LINEAR_SRGB, xyz),
+            ColorSpace::DisplayP3 => {
+                map(mul(&XYZ_TO_LINEAR_P3, xyz), linear_to_srgb)
+            }
+            ColorSpace::Lab => xyz_d50_to_lab(mul(&D65_TO_D50, xyz)),
+            ColorSpace::Lch => {
+                lab_to_lch(ColorSpace::Lab.channels_from_xyz(xyz))
+            }
+            ColorSpace::Oklab => {
+                let lms = map(mul(&XYZ_TO_LMS, xyz), f64::cbrt);
+                mul(&LMS_TO_OKLAB, lms)
+            }
+            ColorSpace::Oklch => {
+                lab_to_lch(ColorSpace::Oklab.channels_from_xyz(xyz))
+            }
+            ColorSpace::Xyz => xyz,
+        }
+    }
+}
+
+impl fmt::Display for ColorSpace {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        out.write_str(self.name())
+    }
+}
+
+/// A color with channels in a specific color space.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct SpaceColor {
+    pub space: ColorSpace,
+    pub channels: [Rational; 3],
+    pub alpha: Rational,
+}
+
+impl SpaceColor {
+    pub fn new(
+        space: ColorSpace,
+        channels: [f64; 3],
+        alpha: Rational,
+    ) -> Self {
+        SpaceColor {
+            space,
+            channels: [
+                precise(channels[0]),
+                precise(channels[1]),
+                precise(channels[2]),
+            ],
+            alpha,
+        }
+    }
+    pub fn channels_f64(&self) -> [f64; 3] {
+        map_r(&self.channels, to_f64)
+    }
+    /// Convert this color to another color space.
+    pub fn to_space(&self, space: ColorSpace) -> SpaceColor {
+        if space == self.space {
+            return self.clone();
+        }
+        let xyz = self.space.to_xyz(self.channels_f64());
+        SpaceColor::new(space, space.channels_from_xyz(xyz), self.alpha)
+    }
+    /// True if this color is inside the gamut of its own space.
+    pub fn is_in_gamut(&self) -> bool {
+        if !self.space.is_bounded() {
+            return true;
+        }
+        let max = self.space.percent_reference()[0];
+        let eps = max * 1e-5;
+        self.channels_f64()
+            .iter()
+            .all(|&c| c > -eps && c < max + eps)
+    }
+    /// Clamp all channels to the gamut of the space.
+    pub fn clip(&self) -> SpaceColor {
+        if !self.space.is_bounded() {
+            return self.clone();
+        }
+        let max = self.space.percent_reference()[0];
+        SpaceColor::new(
+            self.space,
+            map(self.channels_f64(), |c| c.max(0.).min(max)),
+            self.alpha,
+        )
+    }
+    /// Map this color into the gamut of its space, using the css color 4
+    /// gamut mapping algorithm (reducing chroma in oklch until the
+    /// clipped color is not noticeably different).
+    pub fn to_gamut(&self) -> SpaceColor {
+        const JND: f64 = 0.02;
+        const EPSILON: f64 = 0.0001;
+        if self.is_in_gamut() {
+            return self.clone();
+        }
+        let origin = self.to_space(ColorSpace::Oklch).channels_f64();
+        if origin[0] >= 1. {
+            return SpaceColor::new(
+                ColorSpace::Oklch,
+                [1., 0., 0.],
+                self.alpha,
+            )
+            .to_space(self.space);
+        } else if origin[0] <= 0. {
+            return SpaceColor::new(
+                ColorSpace::Oklch,
+                [0., 0., 0.],
+                self.alpha,
+            )
+            .to_space(self.space);
+        }
+        let with_chroma = |chroma: f64| {
+            SpaceColor::new(
+                ColorSpace::Oklch,
+                [origin[0], chroma, origin[2]],
+                self.alpha,
+            )
+        };
+        let (mut min, mut max) = (0., origin[1]);
+        let mut min_in_gamut = true;
+        let mut clipped = self.clip();
+        if delta_eok(&clipped, self) < JND {
+            return clipped;
+        }
+        while max - min > EPSILON {
+            let chroma = (min + max) / 2.;
+            let current = with_chroma(chroma).to_space(self.space);
+            if min_in_gamut && current.is_in_gamut() {
+                min = chroma;
+                continue;
+            }
+            clipped = current.clip();
+            let e = delta_eok(&clipped, &current);
+            if e < JND {
+                if JND - e < EPSILON {
+                    return clipped;
+                }
+                min_in_gamut = false;
+                min = chroma;
+            } else {
+                max = chroma;
+            }
+        }
+        clipped
+    }
+    /// Mix this color with another in a given space.
+    ///
+    /// The weight is the part of this color, 0 .. 1.
+    /// Channels are premultiplied by alpha, and hues are interpolated
+    /// along the shorter arc.
+    pub fn mix(
+        &self,
+        other: &SpaceColor,
+        weight: f64,
+        space: ColorSpace,
+    ) -> SpaceColor {
+        let (a, b) = (self.to_space(space), other.to_space(space));
+        let (aa, ba) = (to_f64(a.alpha), to_f64(b.alpha));
+        let alpha = aa * weight + ba * (1. - weight);
+        let (ac, bc) = (a.channels_f64(), b.channels_f64());
+        let mut channels = [0.; 3];
+        for i in 0..3 {
+            channels[i] = if space.is_polar() && i == 2 {
+                let mut diff = bc[2] - ac[2];
+                if diff > 180. {
+                    diff -= 360.;
+                } else if diff < -180. {
+                    diff += 360.;
+                }
+                positive_degrees(ac[2] + diff * (1. - weight))
+            } else if alpha == 0. {
+                ac[i] * weight + bc[i] * (1. - weight)
+            } else {
+                (ac[i] * aa * weight + bc[i] * ba * (1. - weight)) / alpha
+            };
+        }
+        SpaceColor::new(space, channels, from_f64(alpha))
+    }
+}
+
+impl SpaceColor {
+    pub fn format(&self, format: Format) -> Formatted<'_, SpaceColor> {
+        Formatted {
+            value: self,
+            format,
+        }
+    }
+}
+
+impl<'a> fmt::Display for Formatted<'a, SpaceColor> {
+    fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
+        let color = self.value;
+        let num =
+            |v: Rational| Number::from(v).format(self.format).to_string();
+        let [c0, c1, c2] = color.channels;
+        match color.space {
+            ColorSpace::Lab
+            | ColorSpace::Lch
+            | ColorSpace::Oklab
+            | ColorSpace::Oklch => {
+                let lightness = if color.space == ColorSpace::Oklab
+                    || color.space == ColorSpace::Oklch
+                {
+                    c0 * 100
+                } else {
+                    c0
+                };
+                let hue = if color.space.is_polar() { "deg" } else { "" };
+                write!(
+                    out,
+                    "{}({}% {} {}{}",
+                    color.space,
+                    num(lightness),
+                    num(c1),
+                    num(c2),
+                    hue,
+                )?;
+            }
+            ColorSpace::Rgb => {
+                let srgb = color.to_space(ColorSpace::Srgb);
+                let [r, g, b] = srgb.channels;
+                write!(out, "color(srgb {} {} {}", num(r), num(g), num(b))?;
+            }
+            space => {
+                write!(
+                    out,
+                    "color({} {} {} {}",
+                    space,
+                    num(c0),
+                    num(c1),
+                    num(c2),
+                )?;
+            }
+        }
+        if color.alpha < Rational::one() {
+            write!(out, " / {}", num(color.alpha))?;
+        }
+        out.write_str(")")
+    }
+}
+
+/// The euclidean distance between two colors in oklab.
+fn delta_eok(a: &SpaceColor, b: &SpaceColor) -> f64 {
+    let a = a.to_space(ColorSpace::Oklab).channels_f64();
+    let b = b.to_space(ColorSpace::Oklab).channels_f64();
+    ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2))
+        .sqrt()
+}
+
+/// Convert a rational to a float.
+pub fn to_f64(v: Rational) -> f64 {
+    *v.numer() as f64 / *v.denom() as f64
+}
+
+/// Convert a float to a rational with a small denominator.
+///
+/// This is good enough for rgb channels, that are eventually rounded
+/// to bytes anyway, and keeps later rational arithmetic on them from
+/// overflowing.
+pub fn from_f64(v: f64) -> Rational {
+    Rational::approximate_float(v).unwrap_or_else(Rational::zero)
+}
+
+/// Convert a float to a rational with ten decimals of precision.
+fn precise(v: f64) -> Rational {
+    const SCALE: f64 = 1e10;
+    if v.is_finite() && v.abs() < 1e8 {
+        Rational::new((v * SCALE).round() as isize, SCALE as isize)
+    } else {
+        from_f64(v)
+    }
+}
+
+fn map<F: Fn(f64) -> f64>(c: [f64; 3], f: F) -> [f64; 3] {
+    [f(c[0]), f(c[1]), f(c[2])]
+}
+
+fn map_r<F: Fn(Rational) -> f64>(c: &[Rational; 3], f: F) -> [f64; 3] {
+    [f(c[0]), f(c[1]), f(c[2])]
+}
+
+fn mul(m: &[[f64; 3]; 3], c: [f64; 3]) -> [f64; 3] {
+    let row = |r: &[f64; 3]| r[0] * c[0] + r[1] * c[1] + r[2] * c[2];
+    [row(&m[0]), row(&m[1]), row(&m[2])]
+}
+
+fn srgb_to_linear(c: f64) -> f64 {
+    let abs = c.abs();
+    if abs <= 0.04045 {
+        c / 12.92
+    } else {
+        c.signum() * ((abs + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(c: f64) -> f64 {
+    let abs = c.abs();
+    if abs <= 0.0031308 {
+        c * 12.92
+    } else {
+        c.signum() * (1.055 * abs.powf(1. / 2.4) - 0.055)
+    }
+}
+
+const D50_WHITE: [f64; 3] =
+    [0.3457 / 0.3585, 1., (1. - 0.3457 - 0.3585) / 0.3585];
+const LAB_EPSILON: f64 = 216. / 24389.;
+const LAB_KAPPA: f64 = 24389. / 27.;
+
+fn lab_to_xyz_d50(c: [f64; 3]) -> [f64; 3] {
+    let fy = (c[0] + 16.) / 116.;
+    let fx = c[1] / 500. + fy;
+    let fz = fy - c[2] / 200.;
+    let inv = |f: f64| {
+        if f.powi(3) > LAB_EPSILON {
+            f.powi(3)
+        } else {
+            (116. * f - 16.) / LAB_KAPPA
+        }
+    };
+    let y = if c[0] > LAB_KAPPA * LAB_EPSILON {
+        fy.powi(3)
+    } else {
+        c[0] / LAB_KAPPA
+    };
+    [inv(fx) * D50_WHITE[0], y, inv(fz) * D50_WHITE[2]]
+}
+
+fn xyz_d50_to_lab(xyz: [f64; 3]) -> [f64; 3] {
+    let f = |t: f64| {
+        if t > LAB_EPSILON {
+            t.cbrt()
+        } else {
+            (LAB_KAPPA * t + 16.) / 116.
+        }
+    };
+    let fx = f(xyz[0] / D50_WHITE[0]);
+    let fy = f(xyz[1] / D50_WHITE[1]);
+    let fz = f(xyz[2] / D50_WHITE[2]);
+    [116. * fy - 16., 500. * (fx - fy), 200. * (fy - fz)]
+}
+
+fn lch_to_lab(c: [f64; 3]) -> [f64; 3] {
+    let h = c[2].to_radians();
+    [c[0], c[1] * h.cos(), c[1] * h.sin()]
+}
+
+/// Normalize an angle in degrees to the range `0 .. 360`.
+fn positive_degrees(angle: f64) -> f64 {
+    let angle = angle % 360.;
+    if angle < 0. {
+        angle + 360.
+    } else {
+        angle
+    }
+}
+
+fn lab_to_lch(c: [f64; 3]) -> [f64; 3] {
+    let hue = positive_degrees(c[2].atan2(c[1]).to_degrees());
+    [c[0], c[1].hypot(c[2]), hue]
+}
+
+const LINEAR_SRGB_TO_XYZ: [[f64; 3]; 3] = [
+    [0.41239079926595934, 0.357584339383878, 0.1804807884018343],
+    [0.21263900587151027, 0.715168678767756, 0.07219231536073371],
+    [0.01933081871559182, 0.11919477979462598, 0.9505321522496607],
+];
+const XYZ_TO_LINEAR_SRGB: [[f64; 3]; 3] = [
+    [3.2409699419045226, -1.537383177570094, -0.4986107602930034],
+    [-0.9692436362808796, 1.8759675015077202, 0.04155505740717559],
+    [
+        0.05563007969699366,
+        -0.20397695888897652,
+        1.0569715142428786,
+    ],
+];
+const LINEAR_P3_TO_XYZ: [[f64; 3]; 3] = [
+    [0.4865709486482162, 0.26566769316909306, 0.1982172852343625],
+    [0.2289745640697488, 0.6917385218365064, 0.079286914093745],
+    [0.0, 0.04511338185890264, 1.043944368900976],
+];
+const XYZ_TO_LINEAR_P3: [[f64; 3]; 3] = [
+    [2.493496911941425, -0.9313836179191239, -0.40271078445071684],
+    [
+        -0.8294889695615747,
+        1.7626640603183463,
+        0.023624685841943577,
+    ],
+    [
+        0.03584583024378447,
+        -0.07617238926804182,
+        0.9568845240076872,
+    ],
+];
+const D65_TO_D50: [[f64; 3]; 3] = [
+    [
+        1.0479298208405488,
+        0.022946793341019088,
+        -0.05019222954313557,
+    ],
+    [
+        0.029627815688159344,
+        0.990434484573249,
+        -0.01707382502938514,
+    ],
+    [
+        -0.009243058152591178,
+        0.015055144896577895,
+        0.7518742899580008,
+    ],
+];
+const D50_TO_D65: [[f64; 3]; 3] = [
+    [
+        0.9554734527042182,
+        -0.023098536874261423,
+        0.0632593086610217,
+    ],
+    [
+        -0.028369706963208136,
+        1.0099954580058226,
+        0.021041398966943008,
+    ],
+    [
+        0.012314001688319899,
+        -0.020507696433477912,
+        1.3303659366080753,
+    ],
+];
+const XYZ_TO_LMS: [[f64; 3]; 3] = [
+    [0.819022437996703, 0.3619062600528904, -0.1288737815209879],
+    [0.0329836539323885, 0.9292868615863434, 0.0361446663506424],
+    [0.0481771893596242, 0.2642395317527308, 0.6335478284694309],
+];
+const LMS_TO_XYZ: [[f64; 3]; 3] = [
+    [1.2268798758459243, -0.5578149944602171, 0.2813910456659647],
+    [-0.0405757452148008, 1.112286803280317, -0.0717110580655164],
+    [-0.0763729366746601, -0.4214933324022432, 1.5869240198367816],
+];
+const LMS_TO_OKLAB: [[f64; 3]; 3] = [
+    [0.210454268309314, 0.7936177747023054, -0.0040720430116193],
+    [1.9779985324311684, -2.42859224204858, 0.450593709617411],
+    [0.0259040424655478, 0.7827717124575296, -0.8086757549230774],
+];
+const OKLAB_TO_LMS: [[f64; 3]; 3] = [
+    [1.0, 0.3963377773761749, 0.2158037573099136],
+    [1.0, -0.1055613458156586, -0.0638541728258133],
+    [1.0, -0.0894841775298119, -1.2914855480194092],
+];
+
+#[cfg(test)]
+mod test {
+    use super::{ColorSpace, SpaceColor};
+    use num_rational::Rational;
+    use num_traits::One;
+
+    fn rounded(c: &SpaceColor, space: ColorSpace) -> [i64; 3] {
+        let c = c.to_space(space).channels_f64();
+        [
+            (c[0] * 1000.).round() as i64,
+            (c[1] * 1000.).round() as i64,
+            (c[2] * 1000.).round() as i64,
+        ]
+    }
+
+    #[test]
+    fn red_to_oklch() {
+        let red =
+            SpaceColor::new(ColorSpace::Rgb, [255., 0., 0.], Rational::one());
+        assert_eq!(rounded(&red, ColorSpace::Oklch), [628, 258, 29234]);
+    }
+    #[test]
+    fn red_to_lab() {
+        let red =
+            SpaceColor::new(ColorSpace::Srgb, [1., 0., 0.], Rational::one());
+        assert_eq!(rounded(&red, ColorSpace::Lab), [54291, 80805, 69891]);
+    }
+    #[test]
+    fn round_trip_p3() {
+        let c = SpaceColor::new(
+            ColorSpace::DisplayP3,
+            [1., 0.5, 0.],
+            Rational::one(),
+        );
+        let back =
+            c.to_space(ColorSpace::Lch).to_space(ColorSpace::DisplayP3);
+        assert_eq!(rounded(&back, ColorSpace::DisplayP3), [1000, 500, 0]);
+    }
+    #[test]
+    fn p3_red_out_of_srgb_gamut() {
+        let c = SpaceColor::new(
+            ColorSpace::DisplayP3,
+            [1., 0., 0.],
+            Rational::one(),
+        );
+        assert!(c.is_in_gamut());
+        assert!(!c.to_space(ColorSpace::Srgb).is_in_gamut());
+        assert!(c.to_space(ColorSpace::Srgb).to_gamut().is_in_gamut());
+    }
+}
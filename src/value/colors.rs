@@ -1,7 +1,10 @@
 //! Color names from <https://www.w3.org/TR/css3-color/>
 #![allow(clippy::unreadable_literal)]
 
-use crate::output::{Format, Formatted};
+use super::color_space::{from_f64, ColorSpace, SpaceColor};
+#[cfg(test)]
+use crate::output::Style;
+use crate::output::{ColorFormat, Format, Formatted};
 use crate::value::Number;
 use lazy_static::lazy_static;
 use num_rational::Rational;
@@ -10,12 +13,18 @@ use std::collections::BTreeMap;
 use std::fmt::{self, Display};
 use std::ops::{Add, Div, Sub};
 
-#[derive(Clone, Debug, Eq, Ord, PartialEq, PartialOrd)]
+/// A color, as red, green, blue and alpha channels.
+///
+/// A color created in another color space, like `oklch` or
+/// `display-p3`, also keeps its original channels, since the rgb
+/// channels are clipped to the srgb gamut.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub struct Rgba {
     pub red: Rational,
     pub green: Rational,
     pub blue: Rational,
     pub alpha: Rational,
+    space: Option<Box<SpaceColor>>,
 }
 
 impl Rgba {
@@ -27,6 +36,7 @@ impl Rgba {
             green: cap(g, &ff),
             blue: cap(b, &ff),
             alpha: cap(a, &one),
+            space: None,
         }
     }
     pub fn from_rgb(r: u8, g: u8, b: u8) -> Self {
@@ -35,6 +45,7 @@ impl Rgba {
             green: Rational::from_integer(g as isize),
             blue: Rational::from_integer(b as isize),
             alpha: Rational::one(),
+            space: None,
         }
     }
     pub fn from_rgba(r: u8, g: u8, b: u8, a: u8) -> Self {
@@ -43,6 +54,7 @@ impl Rgba {
             green: Rational::from_integer(g as isize),
             blue: Rational::from_integer(b as isize),
             alpha: Rational::from_integer(a as isize) / 255,
+            space: None,
         }
     }
     pub fn from_hsla(
@@ -81,6 +93,83 @@ impl Rgba {
             )
         }
     }
+    /// Create a color from hue (in turns), whiteness and blackness
+    /// (0 .. 1) and alpha.
+    ///
+    /// If whiteness and blackness adds up to more than 1, they are
+    /// scaled to give a shade of gray.
+    pub fn from_hwba(
+        hue: Rational,
+        white: Rational,
+        black: Rational,
+        a: Rational,
+    ) -> Self {
+        let white = cap(white, &Rational::one());
+        let black = cap(black, &Rational::one());
+        let wb = white + black;
+        if wb >= Rational::one() {
+            let gray = white / wb * 255;
+            Rgba::new(gray, gray, gray, a)
+        } else {
+            let pure =
+                Rgba::from_hsla(hue, Rational::one(), Rational::new(1, 2), a);
+            let channel = |c: Rational| c * (-wb + 1) + white * 255;
+            Rgba::new(
+                channel(pure.red),
+                channel(pure.green),
+                channel(pure.blue),
+                a,
+            )
+        }
+    }
+    /// Create a color from a color in any space.
+    ///
+    /// The rgb channels are clipped to the srgb gamut, but the
+    /// original color is kept.
+    pub fn from_space(color: SpaceColor) -> Self {
+        let srgb = color.to_space(ColorSpace::Srgb).clip().channels_f64();
+        let channel = |c: f64| from_f64(c * 255.);
+        let mut rgba = Rgba::new(
+            channel(srgb[0]),
+            channel(srgb[1]),
+            channel(srgb[2]),
+            color.alpha,
+        );
+        if color.space != ColorSpace::Rgb {
+            rgba.space = Some(Box::new(color));
+        }
+        rgba
+    }
+    /// The original color, if this color was created in a color
+    /// space other than legacy rgb.
+    pub fn space(&self) -> Option<&SpaceColor> {
+        self.space.as_ref().map(|c| &**c)
+    }
+    /// Get this color with another alpha, keeping its color space.
+    pub fn with_alpha(&self, alpha: Rational) -> Self {
+        let alpha = cap(alpha, &Rational::one());
+        Rgba {
+            alpha,
+            space: self.space.as_ref().map(|c| {
+                Box::new(SpaceColor {
+                    alpha,
+                    ..(**c).clone()
+                })
+            }),
+            ..self.clone()
+        }
+    }
+    /// Get this color as a color in its original space.
+    pub fn to_space_color(&self) -> SpaceColor {
+        match self.space {
+            Some(ref color) => (**color).clone(),
+            None => SpaceColor {
+                space: ColorSpace::Rgb,
+                channels: [self.red, self.green, self.blue],
+                alpha: self.alpha,
+            },
+        }
+    }
     pub fn name(&self) -> Option<&'static str> {
         if self.alpha >= Rational::one() {
             let (r, g, b, _a) = self.to_bytes();
@@ -136,6 +225,13 @@ impl Rgba {
             (h, s, mm / 2, self.alpha)
         }
     }
+    /// Convert rgb (0 .. 255) to hue (degrees) / whiteness (0 .. 1) /
+    /// blackness (0 .. 1)
+    pub fn to_hwba(&self) -> (Rational, Rational, Rational, Rational) {
+        let (h, _s, _l, alpha) = self.to_hsla();
+        let (max, min, _) = max_min_largest(self.red, self.green, self.blue);
+        (h, min / 255, -max / 255 + 1, alpha)
+    }
     pub fn format(&self, format: Format) -> Formatted<Rgba> {
         Formatted {
             value: self,
@@ -222,27 +318,73 @@ impl<'a> Sub<&'a Rgba> for &'a Rgba {
 
 impl<'a> Display for Formatted<'a, Rgba> {
     fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
-        // The byte-version of alpha is not used here.
-        let (r, g, b, _a) = self.value.to_bytes();
+        if let Some(ref color) = self.value.space {
+            if !color.to_space(ColorSpace::Srgb).is_in_gamut() {
+                return color.format(self.format).fmt(out);
+            }
+        }
+        let (r, g, b, byte_a) = self.value.to_bytes();
         let a = self.value.alpha;
-        if a >= Rational::one() {
-            // E.g. #ff00cc can be written #f0c in css.
-            // 0xff / 0x11 = 0xf.
-            let short = r % 0x11 == 0 && g % 0x11 == 0 && b % 0x11 == 0;
+        let colors = self.format.colors();
+        let compressed = self.format.is_compressed();
+        // E.g. #ff00cc can be written #f0c in css.
+        // 0xff / 0x11 = 0xf.
+        let short = r % 0x11 == 0 && g % 0x11 == 0 && b % 0x11 == 0;
+        let use_short = match colors {
+            ColorFormat::Shortest => true,
+            ColorFormat::Auto => compressed,
+            ColorFormat::Hex | ColorFormat::Rgb => false,
+        } && short;
+        if colors == ColorFormat::Rgb {
+            if a >= Rational::one() {
+                write!(out, "rgb({} {} {})", r, g, b)
+            } else {
+                let a = Number::from(a * 100);
+                write!(
+                    out,
+                    "rgb({} {} {} / {}%)",
+                    r,
+                    g,
+                    b,
+                    a.format(self.format)
+                )
+            }
+        } else if a >= Rational::one() {
             let hex_len = if short { 4 } else { 7 };
             if let Some(name) = self.value.name() {
-                if !(self.format.is_compressed() && name.len() > hex_len) {
+                let use_name = match colors {
+                    ColorFormat::Auto => {
+                        !(compressed && name.len() > hex_len)
+                    }
+                    ColorFormat::Shortest => name.len() < hex_len,
+                    ColorFormat::Hex | ColorFormat::Rgb => false,
+                };
+                if use_name {
                     return name.fmt(out);
                 }
             }
-            if self.format.is_compressed() && short {
+            if use_short {
                 write!(out, "#{:x}{:x}{:x}", r / 0x11, g / 0x11, b / 0x11)
             } else {
                 write!(out, "#{:02x}{:02x}{:02x}", r, g, b)
             }
-        } else if self.format.is_compressed() && self.value.all_zero() {
+        } else if self.value.all_zero()
+            && match colors {
+                ColorFormat::Auto => compressed,
+                ColorFormat::Shortest => true,
+                ColorFormat::Hex | ColorFormat::Rgb => false,
+            }
+        {
             write!(out, "transparent")
-        } else if self.format.is_compressed() {
+        } else if self.format.hex_alpha() {
+            if use_short && byte_a % 0x11 == 0 {
+                let (r, g, b, a) =
+                    (r / 0x11, g / 0x11, b / 0x11, byte_a / 0x11);
+                write!(out, "#{:x}{:x}{:x}{:x}", r, g, b, a)
+            } else {
+                write!(out, "#{:02x}{:02x}{:02x}{:02x}", r, g, b, byte_a)
+            }
+        } else if compressed {
             // Note: libsass does not use the format for the alpha like this.
             let a = Number::from(a);
             write!(out, "rgba({},{},{},{})", r, g, b, a.format(self.format))
@@ -287,6 +429,27 @@ fn get_red_by_name() {
     assert_eq!(Some(Rgba::from_rgb(255, 0, 0)), Rgba::from_name("red"));
 }
 
+#[test]
+fn hwb_round_trip() {
+    let color = Rgba::from_rgb(0x99, 0x33, 0x66);
+    let (h, w, b, a) = color.to_hwba();
+    assert_eq!(
+        Rgba::from_hwba(h / 360, w, b, a).to_bytes(),
+        (0x99, 0x33, 0x66, 255)
+    );
+}
+
+#[test]
+fn hwb_gray() {
+    let gray = Rgba::from_hwba(
+        Rational::zero(),
+        Rational::new(3, 5),
+        Rational::new(3, 5),
+        Rational::one(),
+    );
+    assert_eq!(gray.to_bytes(), (128, 128, 128, 255));
+}
+
 #[test]
 fn get_none_by_name() {
     assert_eq!(None, Rgba::from_name("xyzzy"));
@@ -460,3 +623,74 @@ lazy_static! {
         ("yellowgreen", 0x9acd32),
     ]);
 }
+
+#[cfg(test)]
+fn fmt_color(color: Rgba, colors: ColorFormat, style: Style) -> String {
+    let format = Format::new(style, 6).with_colors(colors);
+    color.format(format).to_string()
+}
+
+#[test]
+fn format_auto() {
+    let white = Rgba::from_rgb(255, 255, 255);
+    assert_eq!(
+        fmt_color(white.clone(), ColorFormat::Auto, Style::Expanded),
+        "white"
+    );
+    assert_eq!(
+        fmt_color(white, ColorFormat::Auto, Style::Compressed),
+        "#fff"
+    );
+}
+
+#[test]
+fn format_shortest() {
+    for style in &[Style::Expanded, Style::Compressed] {
+        let fmt = |c| fmt_color(c, ColorFormat::Shortest, *style);
+        assert_eq!(fmt(Rgba::from_rgb(255, 255, 255)), "#fff");
+        assert_eq!(fmt(Rgba::from_rgb(255, 0, 0)), "red");
+        assert_eq!(fmt(Rgba::from_rgb(0x12, 0x34, 0x56)), "#123456");
+        assert_eq!(fmt(Rgba::from_rgba(0, 0, 0, 0)), "transparent");
+    }
+}
+
+#[test]
+fn format_hex() {
+    for style in &[Style::Expanded, Style::Compressed] {
+        let fmt = |c| fmt_color(c, ColorFormat::Hex, *style);
+        assert_eq!(fmt(Rgba::from_rgb(255, 255, 255)), "#ffffff");
+        assert_eq!(fmt(Rgba::from_rgb(255, 0, 0)), "#ff0000");
+    }
+}
+
+#[test]
+fn format_rgb() {
+    for style in &[Style::Expanded, Style::Compressed] {
+        let fmt = |c| fmt_color(c, ColorFormat::Rgb, *style);
+        assert_eq!(fmt(Rgba::from_rgb(255, 0, 0)), "rgb(255 0 0)");
+        assert_eq!(
+            fmt(Rgba::new(
+                Rational::zero(),
+                Rational::zero(),
+                Rational::zero(),
+                Rational::new(1, 2)
+            )),
+            "rgb(0 0 0 / 50%)"
+        );
+    }
+}
+
+#[test]
+fn format_hex_alpha() {
+    let format = |colors, style| {
+        Format::new(style, 6)
+            .with_colors(colors)
+            .with_hex_alpha(true)
+    };
+    let color = Rgba::from_rgba(0x11, 0x22, 0x33, 0x44);
+    let fmt = |colors, style| color.format(format(colors, style)).to_string();
+    assert_eq!(fmt(ColorFormat::Auto, Style::Expanded), "#11223344");
+    assert_eq!(fmt(ColorFormat::Auto, Style::Compressed), "#1234");
+    assert_eq!(fmt(ColorFormat::Shortest, Style::Expanded), "#1234");
+    assert_eq!(fmt(ColorFormat::Hex, Style::Compressed), "#11223344");
+}
@@ -1,7 +1,8 @@
-/// The difference between a comma-separated and a
-/// whitespace-separated list.
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+/// The difference between a comma-separated, a whitespace-separated
+/// and a slash-separated list.
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub enum ListSeparator {
     Comma,
     Space,
+    Slash,
 }
@@ -1,3 +1,5 @@
+mod calc_name;
+mod color_space;
 mod colors;
 mod list_separator;
 mod number;
@@ -5,9 +7,13 @@ mod operator;
 mod quotes;
 mod unit;
 
+pub use self::calc_name::CalcName;
+pub(crate) use self::color_space::{from_f64, to_f64};
+pub use self::color_space::{ColorSpace, SpaceColor};
 pub use self::colors::Rgba;
 pub use self::list_separator::ListSeparator;
 pub use self::number::Number;
+pub(crate) use self::operator::equal_values;
 pub use self::operator::Operator;
 pub use self::quotes::Quotes;
 pub use self::unit::Unit;
@@ -1,6 +1,7 @@
 use crate::output::{Format, Formatted};
 use num_rational::Rational;
-use num_traits::{One, Signed, Zero};
+use num_traits::{Signed, Zero};
+use std::convert::TryFrom;
 use std::fmt::{self, Write};
 use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
 
@@ -9,7 +10,7 @@ use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
 /// Only the actual numeric value is included, not any unit, but flags
 /// to show a leading plus sign and/or leading zero (for values
 /// between -1 and 1) is included.
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Number {
     pub value: Rational,
     pub plus_sign: bool,
@@ -26,8 +27,13 @@ impl Number {
         }
     }
     /// Returns true if the number is an integer.
+    ///
+    /// As in dart-sass, a number that differs from an integer by
+    /// less than `10^-11` is considered an integer, so rounding
+    /// errors from earlier calculations are ignored.
     pub fn is_integer(&self) -> bool {
-        self.value.is_integer()
+        let diff = (self.value - self.value.round()).abs();
+        (*diff.numer() as f64) / (*diff.denom() as f64) < 1e-11
     }
     /// Converts to an integer, rounding towards zero.
     pub fn to_integer(&self) -> isize {
@@ -102,50 +108,98 @@ impl Zero for Number {
 
 impl<'a> fmt::Display for Formatted<'a, Number> {
     fn fmt(&self, out: &mut fmt::Formatter) -> fmt::Result {
-        let t = self.value.value.to_integer();
+        let value = &self.value.value;
+        // If the value is too large for the requested precision,
+        // fall back to as many decimals as possible.
+        let (precision, whole, fract) = (0..=self.format.precision)
+            .rev()
+            .find_map(|p| rounded_parts(value, p).map(|(w, f)| (p, w, f)))
+            .unwrap_or((0, 0, 0));
+        let plus_sign = self.value.plus_sign && !value.is_negative();
+        if value.is_negative() && (whole != 0 || fract != 0) {
+            out.write_char('-')?;
+        } else if plus_sign {
+            out.write_char('+')?;
+        }
         let skip_zero = self.format.is_compressed() || !self.value.lead_zero;
-        if t == 0 {
-            if self.value.value.is_negative() {
-                out.write_str("-")?;
-                if !skip_zero {
-                    out.write_str("0")?;
-                }
-            } else if self.value.plus_sign {
-                out.write_str("+0")?;
-            } else if self.value.value.is_zero() || !skip_zero {
-                out.write_char('0')?;
-            }
-        } else {
-            if self.value.plus_sign && !t.is_negative() {
-                out.write_char('+')?;
-            }
-            write!(out, "{}", t)?;
+        if whole != 0 || fract == 0 || !skip_zero || plus_sign {
+            write!(out, "{}", whole)?;
         }
-        let mut f = self.value.value.fract().abs();
-        if !f.is_zero() {
-            out.write_char('.')?;
-            for i in 0..(self.format.precision - 1) {
-                f = f * 10;
-                let digit = f.to_integer();
-                f = f.fract();
-                if (f + Rational::new(1, 10)
-                    .pow((self.format.precision - i) as i32))
-                    >= Rational::one()
-                {
-                    assert!(digit < 9);
-                    write!(out, "{}", digit + 1)?;
-                    return Ok(());
-                } else {
-                    write!(out, "{}", digit)?;
-                }
-                if f.is_zero() {
-                    return Ok(());
-                }
-            }
-            if !f.is_zero() {
-                write!(out, "{}", (f * 10).round().to_integer())?;
-            }
+        if fract != 0 {
+            let fract = format!("{:0width$}", fract, width = precision);
+            write!(out, ".{}", fract.trim_end_matches('0'))?;
         }
         Ok(())
     }
 }
+
+/// Round the absolute value of `value` to `precision` decimals.
+///
+/// The result is the integer part and the decimals, the latter as an
+/// integer scaled by `10^precision`.
+/// Returns None if the scaled value is too large to represent.
+fn rounded_parts(value: &Rational, precision: usize) -> Option<(u128, u128)> {
+    let scale = 10u128.checked_pow(u32::try_from(precision).ok()?)?;
+    let numer = u128::try_from(value.numer().checked_abs()?).ok()?;
+    let denom = u128::try_from(value.denom().checked_abs()?).ok()?;
+    let scaled = (numer.checked_mul(scale)?.checked_mul(2)? + denom)
+        / denom.checked_mul(2)?;
+    Some((scaled / scale, scaled % scale))
+}
+
+#[cfg(test)]
+mod test {
+    use super::Number;
+    use crate::output::{Format, Style};
+    use num_rational::Rational;
+
+    #[test]
+    fn rounding_carries_to_integer() {
+        let n = Number::from(Rational::new(99_999_999, 100_000_000));
+        assert_eq!(fmt(&n, Format::default()), "1")
+    }
+    #[test]
+    fn rounding_carries_to_decimals() {
+        let n = Number::from(Rational::new(1_999_999_999, 1_000_000_000));
+        assert_eq!(fmt(&n, Format::default()), "2")
+    }
+    #[test]
+    fn rounding_negative() {
+        let n = Number::from(Rational::new(-1_999_999, 1_000_000));
+        assert_eq!(fmt(&n, Format::default()), "-1.999999")
+    }
+    #[test]
+    fn rounding_to_zero_has_no_sign() {
+        let n = Number::from(Rational::new(-1, 100_000_000));
+        assert_eq!(fmt(&n, Format::default()), "0")
+    }
+    #[test]
+    fn dart_sass_precision() {
+        let n = Number::from(Rational::new(1, 3));
+        assert_eq!(
+            fmt(&n, Format::dart_sass(Style::Expanded)),
+            "0.3333333333"
+        )
+    }
+    #[test]
+    fn compressed_skips_lead_zero() {
+        let n = Number::from(Rational::new(-1, 2));
+        assert_eq!(fmt(&n, Format::dart_sass(Style::Compressed)), "-.5")
+    }
+    #[test]
+    #[cfg(target_pointer_width = "64")] // too large for a 32-bit rational
+    fn almost_integer() {
+        let n =
+            Number::from(Rational::new(2_999_999_999_999, 1_000_000_000_000));
+        assert!(n.is_integer())
+    }
+    #[test]
+    fn not_integer() {
+        let n = Number::from(Rational::new(2_999_999_999, 1_000_000_000));
+        assert!(!n.is_integer())
+    }
+
+    fn fmt(n: &Number, format: Format) -> String {
+        n.format(format).to_string()
+    }
+}
@@ -1,9 +1,12 @@
 use crate::css::Value;
+use crate::error::Error;
 use crate::value::{ListSeparator, Quotes, Unit};
-use num_traits::Zero;
+use num_rational::Rational;
+use num_traits::{One, Zero};
+use std::cmp::Ordering;
 use std::fmt;
 
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Operator {
     And,
     Or,
@@ -24,8 +27,12 @@ pub enum Operator {
 }
 
 impl Operator {
-    pub fn eval(&self, a: Value, b: Value) -> Option<Value> {
-        match *self {
+    /// Apply this operator to two values.
+    ///
+    /// Returns None if the operation should be kept as is in the
+    /// output, and an error if it is invalid.
+    pub fn eval(&self, a: Value, b: Value) -> Result<Option<Value>, Error> {
+        Ok(match *self {
             Operator::And => Some(Value::bool(a.is_true() && b.is_true())),
             Operator::Or => {
                 if a.is_true() {
@@ -36,10 +43,18 @@ impl Operator {
             }
             Operator::Equal => Some(Value::bool(equal_values(&a, &b))),
             Operator::NotEqual => Some(Value::bool(!equal_values(&a, &b))),
-            Operator::Greater => Some(Value::bool(a > b)),
-            Operator::GreaterE => Some(Value::bool(a >= b)),
-            Operator::Lesser => Some(Value::bool(a < b)),
-            Operator::LesserE => Some(Value::bool(a <= b)),
+            Operator::Greater => {
+                Some(Value::bool(self.compare(&a, &b)? == Ordering::Greater))
+            }
+            Operator::GreaterE => {
+                Some(Value::bool(self.compare(&a, &b)? != Ordering::Less))
+            }
+            Operator::Lesser => {
+                Some(Value::bool(self.compare(&a, &b)? == Ordering::Less))
+            }
+            Operator::LesserE => {
+                Some(Value::bool(self.compare(&a, &b)? != Ordering::Greater))
+            }
             Operator::Plus => match (a, b) {
                 (Value::Color(a, _), Value::Numeric(bn, Unit::None, _)) => {
                     let bn = bn.value;
@@ -191,6 +206,34 @@ impl Operator {
                 _ => None,
             },
             Operator::Not => panic!("not is a unary operator only"),
+        })
+    }
+
+    /// Compare two numbers for this (comparison) operator.
+    ///
+    /// Compatible units are converted, and a unitless number can be
+    /// compared to any number.
+    fn compare(&self, a: &Value, b: &Value) -> Result<Ordering, Error> {
+        match (a, b) {
+            (Value::Numeric(av, au, _), Value::Numeric(bv, bu, _)) => {
+                let scale = if *au == Unit::None || *bu == Unit::None {
+                    Rational::one()
+                } else {
+                    bu.scale_to(au).ok_or_else(|| {
+                        Error::S(format!(
+                            "Incompatible units {} and {}.",
+                            bu, au
+                        ))
+                    })?
+                };
+                Ok(av.value.cmp(&(bv.value * scale)))
+            }
+            _ => Err(Error::S(format!(
+                "Undefined operation \"{} {} {}\".",
+                a.format(Default::default()),
+                self,
+                b.format(Default::default()),
+            ))),
         }
     }
 }
@@ -227,9 +270,35 @@ impl fmt::Display for Operator {
 ///
 /// Make this a separate function, so the rust == operator is still
 /// the strict derived version, for unit tests etc.
-fn equal_values(a: &Value, b: &Value) -> bool {
+pub(crate) fn equal_values(a: &Value, b: &Value) -> bool {
     match (a, b) {
         (&Value::Literal(ref a, _), &Value::Literal(ref b, _)) => a == b,
+        (
+            &Value::Numeric(ref av, ref au, _),
+            &Value::Numeric(ref bv, ref bu, _),
+        ) => match bu.scale_to(au) {
+            Some(scale) => av.value == bv.value * scale,
+            None => false,
+        },
+        (&Value::Color(ref a, _), &Value::Color(ref b, _)) => a == b,
+        (
+            &Value::List(ref av, ref asep, abr),
+            &Value::List(ref bv, ref bsep, bbr),
+        ) => {
+            asep == bsep
+                && abr == bbr
+                && av.len() == bv.len()
+                && av.iter().zip(bv).all(|(a, b)| equal_values(a, b))
+        }
+        (&Value::Map(ref a), &Value::Map(ref b)) => {
+            // Maps are equal regardless of the order of their keys.
+            a.len() == b.len()
+                && a.iter().all(|&(ref k, ref av)| {
+                    b.get(k).map_or(false, |bv| equal_values(av, bv))
+                })
+        }
+        (&Value::ArgList(ref a), b) => equal_values(&a.to_list(), b),
+        (a, &Value::ArgList(ref b)) => equal_values(a, &b.to_list()),
         (a, b) => a == b,
     }
 }
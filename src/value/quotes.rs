@@ -1,7 +1,7 @@
 use std::fmt::{self, Write};
 
 /// A literal value can be double-quoted, single-quoted or not quoted.
-#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Quotes {
     Double,
     Single,
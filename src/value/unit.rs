@@ -7,7 +7,7 @@ use std::fmt;
 /// Units in css.
 ///
 /// As defined in <https://www.w3.org/TR/css3-values/>
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Unit {
     // Distance units, <length> type
     Em,
@@ -78,6 +78,8 @@ impl Unit {
         }
     }
 
+    /// The size of this unit, in a canonical unit of its dimension.
+    ///
     /// Some of these are exact and correct, others are more arbitrary.
     /// When comparing 10cm to 4in, these factors will give correct results.
     /// When comparing rems to vw, who can say?
@@ -95,9 +97,9 @@ impl Unit {
             Unit::Pc => Rational::new(254, 60),
             Unit::Px => Rational::new(254, 960),
 
-            Unit::Deg => Rational::new(360, 1),
-            Unit::Grad => Rational::new(400, 1),
-            Unit::Rad => Rational::new(62_832, 10_000), // approximate
+            Unit::Deg => Rational::new(1, 360),
+            Unit::Grad => Rational::new(1, 400),
+            Unit::Rad => Rational::new(100_000_000, 628_318_531), // approx.
             Unit::Turn => Rational::one(),
 
             Unit::S => Rational::one(),
@@ -106,8 +108,8 @@ impl Unit {
             Unit::Hz => Rational::one(),
             Unit::Khz => Rational::new(1000, 1),
 
-            Unit::Dpi => Rational::new(96, 1),
-            Unit::Dpcm => Rational::new(9600, 254),
+            Unit::Dpi => Rational::new(1, 96),
+            Unit::Dpcm => Rational::new(254, 9600),
             Unit::Dppx => Rational::one(),
 
             Unit::Percent => Rational::new(1, 100),
@@ -115,6 +117,56 @@ impl Unit {
             Unit::None => Rational::one(),
         }
     }
+
+    /// Get the factor to convert a value in this unit to `other`.
+    ///
+    /// Returns None if the units are not convertible.  Note that
+    /// relative lengths (e.g. `em` or `vw`) are only convertible to
+    /// themselves, and that no unit is convertible to `Unit::None`.
+    pub fn scale_to(&self, other: &Unit) -> Option<Rational> {
+        if self == other {
+            Some(Rational::one())
+        } else if self.is_absolute()
+            && other.is_absolute()
+            && self.dimension() == other.dimension()
+        {
+            Some(self.scale_factor() / other.scale_factor())
+        } else {
+            None
+        }
+    }
+
+    /// Get the factor to convert a number in this unit to `other`,
+    /// when comparing or combining numbers.
+    ///
+    /// This is like `scale_to`, except that a unitless number is
+    /// compatible with any number.
+    pub fn compatible_scale(&self, other: &Unit) -> Option<Rational> {
+        if *self == Unit::None || *other == Unit::None {
+            Some(Rational::one())
+        } else {
+            self.scale_to(other)
+        }
+    }
+
+    /// True if this unit has a fixed size relative to other units
+    /// of the same dimension.
+    pub fn is_absolute(&self) -> bool {
+        match *self {
+            Unit::Em
+            | Unit::Ex
+            | Unit::Ch
+            | Unit::Rem
+            | Unit::Vw
+            | Unit::Vh
+            | Unit::Vmin
+            | Unit::Vmax
+            | Unit::Percent
+            | Unit::Fr
+            | Unit::None => false,
+            _ => true,
+        }
+    }
 }
 
 impl fmt::Display for Unit {
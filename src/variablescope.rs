@@ -2,7 +2,9 @@
 
 use crate::css::{self, Value};
 use crate::error::Error;
-use crate::functions::{get_builtin_function, SassFunction};
+use crate::functions::{
+    get_builtin_function, get_builtin_variable, SassFunction,
+};
 use crate::output::Format;
 use crate::sass::{self, Item};
 use crate::selectors::Selectors;
@@ -175,6 +177,33 @@ pub trait Scope {
         Ok(None)
     }
     fn get_selectors(&self) -> &Selectors;
+
+    /// Get the at-rules enclosing the current position, outermost
+    /// first.
+    ///
+    /// The default implementation has no enclosing at-rules.
+    fn get_at_rules(&self) -> Vec<&AtRuleFrame> {
+        vec![]
+    }
+
+    /// Get a random number from the generator of the compilation.
+    ///
+    /// The numbers are deterministic for a given seed, see
+    /// [`GlobalScope::set_seed`], and no number is repeated until
+    /// all 2^64 values are used.
+    ///
+    /// The default implementation counts the numbers in a global
+    /// variable with a name that can't be used in sass, so the
+    /// numbers are the same as for a `GlobalScope` with seed 0.
+    fn next_random(&self) -> u64 {
+        let name = "%random-count";
+        let n = match self.get_global_or_none(name) {
+            Some(Value::Numeric(ref n, ..)) => n.value.to_integer() + 1,
+            _ => 1,
+        };
+        self.define_global(name, &Value::scalar(n));
+        mix_bits((n as u64).wrapping_mul(RANDOM_STEP))
+    }
 }
 
 pub struct ScopeImpl<'a> {
@@ -183,6 +212,7 @@ pub struct ScopeImpl<'a> {
     mixins: BTreeMap<String, (sass::FormalArgs, Vec<Item>)>,
     functions: BTreeMap<String, SassFunction>,
     selectors: Option<Selectors>,
+    at_rule: Option<AtRuleFrame>,
 }
 
 impl<'a> Scope for ScopeImpl<'a> {
@@ -260,6 +290,14 @@ impl<'a> Scope for ScopeImpl<'a> {
             .as_ref()
             .unwrap_or_else(|| self.parent.get_selectors())
     }
+    fn get_at_rules(&self) -> Vec<&AtRuleFrame> {
+        let mut result = self.parent.get_at_rules();
+        result.extend(self.at_rule.as_ref());
+        result
+    }
+    fn next_random(&self) -> u64 {
+        self.parent.next_random()
+    }
 }
 
 impl<'a> ScopeImpl<'a> {
@@ -270,6 +308,7 @@ impl<'a> ScopeImpl<'a> {
             mixins: BTreeMap::new(),
             functions: BTreeMap::new(),
             selectors: None,
+            at_rule: None,
         }
     }
     pub fn sub_selectors(
@@ -282,10 +321,70 @@ impl<'a> ScopeImpl<'a> {
             mixins: BTreeMap::new(),
             functions: BTreeMap::new(),
             selectors: Some(selectors),
+            at_rule: None,
+        }
+    }
+    /// Take the css that broke out of the at-rule of this scope.
+    pub fn take_escaped(&self) -> Vec<u8> {
+        self.at_rule
+            .as_ref()
+            .map(AtRuleFrame::take_escaped)
+            .unwrap_or_default()
+    }
+    /// Create a scope for the body of an at-rule, like `@media
+    /// screen`.
+    ///
+    /// The `indent` is where the at-rule is written in the output.
+    /// If `selectors` is given, it replaces the selectors of enclosing
+    /// style rules in the body.
+    pub fn sub_at_rule(
+        parent: &'a dyn Scope,
+        name: &str,
+        args: Value,
+        selectors: Option<Selectors>,
+        indent: usize,
+    ) -> Self {
+        ScopeImpl {
+            parent,
+            variables: BTreeMap::new(),
+            mixins: BTreeMap::new(),
+            functions: BTreeMap::new(),
+            selectors,
+            at_rule: Some(AtRuleFrame {
+                name: name.to_lowercase(),
+                args,
+                indent,
+                escaped: Mutex::new(Vec::new()),
+            }),
         }
     }
 }
 
+/// An at-rule enclosing the current position in the output.
+///
+/// Css that breaks out of the at-rule by `@at-root` is collected
+/// here, to be written after the at-rule itself.
+pub struct AtRuleFrame {
+    /// The lowercase name, without the `@`.
+    pub name: String,
+    /// The evaluated arguments, or null.
+    pub args: Value,
+    /// The indentation of the at-rule in the output.
+    pub indent: usize,
+    escaped: Mutex<Vec<u8>>,
+}
+
+impl AtRuleFrame {
+    /// Add css to be written after this at-rule.
+    pub fn escape(&self, css: &[u8]) {
+        self.escaped.lock().unwrap().extend_from_slice(css);
+    }
+    /// Take the css to be written after this at-rule.
+    pub fn take_escaped(&self) -> Vec<u8> {
+        self.escaped.lock().unwrap().drain(..).collect()
+    }
+}
+
 /// A `Scope` that can be created without allready having a scope as a
 /// parameter is a `GlobalScope`.
 ///
@@ -297,6 +396,7 @@ pub struct GlobalScope {
     mixins: BTreeMap<String, (sass::FormalArgs, Vec<Item>)>,
     functions: BTreeMap<String, SassFunction>,
     selectors: Selectors,
+    random_state: Mutex<u64>,
 }
 
 impl GlobalScope {
@@ -308,8 +408,18 @@ impl GlobalScope {
             mixins: BTreeMap::new(),
             functions: BTreeMap::new(),
             selectors: Selectors::root(),
+            random_state: Mutex::new(0),
         }
     }
+
+    /// Set the seed for generated values, such as `random()` and
+    /// `unique-id()`.
+    ///
+    /// Compiling the same input with the same seed gives the same
+    /// output.  The default seed is zero.
+    pub fn set_seed(&mut self, seed: u64) {
+        *self.random_state.lock().unwrap() = seed;
+    }
 }
 
 impl Scope for GlobalScope {
@@ -340,7 +450,12 @@ impl Scope for GlobalScope {
     }
     fn get_global_or_none(&self, name: &str) -> Option<Value> {
         let name = name.replace('-', "_");
-        self.variables.lock().unwrap().get(&name).cloned()
+        self.variables
+            .lock()
+            .unwrap()
+            .get(&name)
+            .cloned()
+            .or_else(|| get_builtin_variable(&name))
     }
     fn define_mixin(
         &mut self,
@@ -375,6 +490,27 @@ impl Scope for GlobalScope {
     fn get_selectors(&self) -> &Selectors {
         &self.selectors
     }
+    fn next_random(&self) -> u64 {
+        let mut state = self.random_state.lock().unwrap();
+        *state = state.wrapping_add(RANDOM_STEP);
+        mix_bits(*state)
+    }
+}
+
+/// The step of the random state for each number, an odd number close
+/// to 2^64 divided by the golden ratio.
+const RANDOM_STEP: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// Scramble the bits of a number, so that consecutive numbers give
+/// unrelated-looking results.
+///
+/// Together with `RANDOM_STEP`, this is the splitmix64
+/// generator.  The function is a bijection, so different inputs give
+/// different outputs.
+fn mix_bits(mut z: u64) -> u64 {
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
 }
 
 #[cfg(test)]
@@ -430,7 +566,10 @@ pub mod test {
 
     #[test]
     fn div_slash_2() {
-        assert_eq!("500px", do_evaluate(&[("width", "1000px")], b"$width/2;"))
+        assert_eq!(
+            "1000px/2",
+            do_evaluate(&[("width", "1000px")], b"$width/2;")
+        )
     }
 
     #[test]
@@ -468,7 +607,7 @@ pub mod test {
 
     #[test]
     fn double_div_2() {
-        assert_eq!("15 / 3 / 5", do_evaluate(&[], b"15 / 3 / 5;"))
+        assert_eq!("15/3/5", do_evaluate(&[], b"15 / 3 / 5;"))
     }
 
     #[test]
@@ -476,6 +615,91 @@ pub mod test {
         assert_eq!("1", do_evaluate(&[], b"(15 / 3 / 5);"))
     }
 
+    #[test]
+    fn slash_in_variable() {
+        assert_eq!(
+            "12px/1.5 serif",
+            do_evaluate(&[("f", "12px/1.5")], b"$f serif;")
+        )
+    }
+
+    #[test]
+    fn slash_of_variables() {
+        assert_eq!(
+            "12px/1.5 serif",
+            do_evaluate(&[("a", "12px"), ("b", "1.5")], b"$a/$b serif;")
+        )
+    }
+
+    #[test]
+    fn slash_of_variables_in_calc() {
+        assert_eq!(
+            "8px",
+            do_evaluate(&[("a", "12px"), ("b", "1.5")], b"calc($a/$b);")
+        )
+    }
+
+    #[test]
+    fn slash_of_variables_in_math_div() {
+        assert_eq!(
+            "8px",
+            do_evaluate(&[("a", "12px"), ("b", "1.5")], b"math.div($a, $b);")
+        )
+    }
+
+    #[test]
+    fn slash_in_variable_arithmetic() {
+        assert_eq!("9px", do_evaluate(&[("f", "12px/1.5")], b"$f + 1;"))
+    }
+
+    #[test]
+    fn compare_converted_units() {
+        assert_eq!("true", do_evaluate(&[], b"1in > 90px;"))
+    }
+
+    #[test]
+    fn compare_unitless() {
+        assert_eq!("true", do_evaluate(&[], b"2 >= 2px;"))
+    }
+
+    #[test]
+    fn compare_incompatible_units() {
+        assert!(do_evaluate_or_error(&[], b"1px < 1s;").is_err())
+    }
+
+    #[test]
+    fn compare_non_number() {
+        assert!(do_evaluate_or_error(&[], b"1px < red;").is_err())
+    }
+
+    #[test]
+    fn equal_converted_units() {
+        assert_eq!("true", do_evaluate(&[], b"1in == 96px;"))
+    }
+
+    #[test]
+    fn equal_lists_converted_units() {
+        assert_eq!("true", do_evaluate(&[], b"(1in 2) == (96px 2);"))
+    }
+
+    #[test]
+    fn equal_maps_any_order() {
+        assert_eq!("true", do_evaluate(&[], b"(a: 1, b: 2) == (b: 2, a: 1);"))
+    }
+
+    #[test]
+    fn equal_maps_converted_units() {
+        assert_eq!("true", do_evaluate(&[], b"(a: 1in) == (a: 96px);"))
+    }
+
+    #[test]
+    fn unequal_maps() {
+        assert_eq!(
+            "false",
+            do_evaluate(&[], b"(a: 1, b: 2) == (a: 1, b: 3);")
+        )
+    }
+
     #[test]
     fn long_div_and_mul_sequence() {
         assert_eq!("3", do_evaluate(&[], b"(3 / 2 / 2 / 2 * 32 / 2 / 2);"))
@@ -483,12 +707,15 @@ pub mod test {
 
     #[test]
     fn double_div_4() {
-        assert_eq!("1", do_evaluate(&[], b"(15 / 3) / 5;"));
+        assert_eq!("5/5", do_evaluate(&[], b"(15 / 3) / 5;"));
     }
 
     #[test]
     fn double_div_5() {
-        assert_eq!("1", do_evaluate(&[("five", "5")], b"15 / 3 / $five;"))
+        assert_eq!(
+            "15/3/5",
+            do_evaluate(&[("five", "5")], b"15 / 3 / $five;")
+        )
     }
 
     #[test]
@@ -737,4 +964,13 @@ pub mod test {
             .format(scope.get_format())
             .to_string())
     }
+
+    /// Check that evaluating `expression` gives a bad arguments error
+    /// with `message`.
+    pub fn check_err(expression: &str, message: &str) {
+        match do_evaluate_or_error(&[], expression.as_bytes()) {
+            Err(Error::BadArguments(ref msg)) => assert_eq!(msg, message),
+            other => panic!("Expected error, got {:?}", other),
+        }
+    }
 }
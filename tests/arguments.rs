@@ -0,0 +1,139 @@
+//! Tests for argument lists, spread arguments and argument errors.
+use rsass::compile_scss;
+
+#[test]
+fn keywords_of_rest() {
+    check(
+        "@function f($a, $args...) {@return keywords($args)}\n\
+         .a {b: inspect(f(1, $c: 2, $d-e: 3))}\n",
+        ".a {\n  b: (c: 2, d-e: 3);\n}\n",
+    )
+}
+
+#[test]
+fn meta_keywords() {
+    check(
+        "@use \"sass:meta\";\n\
+         @function f($args...) {@return meta.keywords($args)}\n\
+         .a {b: inspect(f($c: 2))}\n",
+        ".a {\n  b: (c: 2);\n}\n",
+    )
+}
+
+#[test]
+fn keywords_of_map() {
+    check_err(
+        ".a {b: inspect(keywords((c: 2)))}\n",
+        "BadArguments(\"expected arglist, got unknown = (c: 2)\")",
+    )
+}
+
+#[test]
+fn spread_map_and_list() {
+    check(
+        "@mixin m($a, $b, $c: 0) {x: $a $b $c}\n\
+         .a {@include m((1, 2)..., (c: 3)...)}\n",
+        ".a {\n  x: 1 2 3;\n}\n",
+    )
+}
+
+#[test]
+fn rest_passed_through() {
+    check(
+        "@mixin inner($a, $b) {x: $a $b}\n\
+         @mixin outer($args...) {@include inner($args...)}\n\
+         .a {@include outer(1, $b: 2)}\n",
+        ".a {\n  x: 1 2;\n}\n",
+    )
+}
+
+#[test]
+fn single_rest_arg() {
+    check(
+        "@mixin m($r...) {r: $r}\n.a {@include m(2)}\n",
+        ".a {\n  r: 2;\n}\n",
+    )
+}
+
+#[test]
+fn single_rest_arg_spread() {
+    check(
+        "@mixin m($r...) {r: $r}\n.a {@include m((1, 2)...)}\n",
+        ".a {\n  r: 1, 2;\n}\n",
+    )
+}
+
+#[test]
+fn content_args() {
+    check(
+        "@mixin m {@content(1, $b: 2)}\n\
+         .a {@include m using ($a, $b, $c: 3) {x: $a $b $c}}\n",
+        ".a {\n  x: 1 2 3;\n}\n",
+    )
+}
+
+#[test]
+fn content_at_root() {
+    check(
+        "@mixin m {@content(b)}\n@include m using ($n) {.#{$n} {c: d}}\n",
+        ".b {\n  c: d;\n}\n",
+    )
+}
+
+#[test]
+fn error_missing() {
+    check_err(
+        "@mixin m($a, $b) {x: $a}\n.a {@include m($b: 1)}\n",
+        "Missing argument $a.",
+    )
+}
+
+#[test]
+fn error_unknown_named() {
+    check_err(
+        "@function f($a) {@return $a}\n.a {x: f(1, $b: 2)}\n",
+        "No argument named $b.",
+    )
+}
+
+#[test]
+fn error_too_many() {
+    check_err(
+        "@mixin m($a) {x: $a}\n.a {@include m(1, 2, 3)}\n",
+        "Only 1 argument allowed, but 3 were passed.",
+    )
+}
+
+#[test]
+fn error_both_position_and_name() {
+    check_err(
+        "@mixin m($a) {x: $a}\n.a {@include m(1, $a: 2)}\n",
+        "Argument $a was passed both by position and by name.",
+    )
+}
+
+#[test]
+fn error_content_without_using() {
+    check_err(
+        "@mixin m {@content(1)}\n.a {@include m {x: y}}\n",
+        "No arguments allowed, but 1 was passed.",
+    )
+}
+
+fn check(input: &str, expected: &str) {
+    assert_eq!(
+        compile_scss(input.as_bytes(), Default::default())
+            .and_then(|s| Ok(String::from_utf8(s)?))
+            .unwrap(),
+        expected
+    );
+}
+
+fn check_err(input: &str, expected: &str) {
+    assert_eq!(
+        compile_scss(input.as_bytes(), Default::default())
+            .unwrap_err()
+            .to_string(),
+        expected
+    );
+}
@@ -0,0 +1,99 @@
+//! Tests for `@at-root` with a `(with: ...)` or `(without: ...)` query.
+use rsass::compile_scss;
+
+#[test]
+fn without_media() {
+    check(
+        "@media print {\n  .foo {a: b}\n  \
+         @at-root (without: media) {.foo {c: d}}\n}\n",
+        "@media print {\n  .foo {\n    a: b;\n  }\n}\n\
+         .foo {\n  c: d;\n}\n",
+    )
+}
+
+#[test]
+fn without_media_keeps_rule() {
+    check(
+        ".a {\n  @media print {\n    b: c;\n    \
+         @at-root (without: media) {d: e}\n  }\n}\n",
+        "@media print {\n  .a {\n    b: c;\n  }\n}\n.a {\n  d: e;\n}\n",
+    )
+}
+
+#[test]
+fn with_rule() {
+    check(
+        ".a {\n  @media print {\n    @at-root (with: rule) {b: c}\n  }\n}\n",
+        ".a {\n  b: c;\n}\n",
+    )
+}
+
+#[test]
+fn without_all() {
+    check(
+        ".a {\n  @supports (color: red) {\n    \
+         @at-root (without: all) {.b {c: d}}\n  }\n}\n",
+        ".b {\n  c: d;\n}\n",
+    )
+}
+
+#[test]
+fn without_media_keeps_inner_supports() {
+    check(
+        "@media print {\n  @supports (color: red) {\n    \
+         @at-root (without: \"media\") {.foo {a: b}}\n  }\n}\n",
+        "@supports (color: red) {\n  .foo {\n    a: b;\n  }\n}\n",
+    )
+}
+
+#[test]
+fn with_all_stays() {
+    check(
+        "@media print {\n  .a {\n    \
+         @at-root (with: all) {.b {c: d}}\n  }\n}\n",
+        "@media print {\n  .a .b {\n    c: d;\n  }\n}\n",
+    )
+}
+
+#[test]
+fn default_keeps_media() {
+    check(
+        "@media print {\n  .a {\n    @at-root {.b {c: d}}\n  }\n}\n",
+        "@media print {\n  .b {\n    c: d;\n  }\n}\n",
+    )
+}
+
+#[test]
+fn without_rule_declarations() {
+    check_err(
+        ".a {\n  @at-root (without: rule) {x: y}\n}\n",
+        "Declarations may only be used within style rules",
+    )
+}
+
+#[test]
+fn without_rule_declarations_in_media() {
+    check_err(
+        "@media screen {\n  .a {\n    \
+         @at-root (without: rule) {x: y}\n  }\n}\n",
+        "Declarations may only be used within style rules",
+    )
+}
+
+fn check(input: &str, expected: &str) {
+    assert_eq!(
+        compile_scss(input.as_bytes(), Default::default())
+            .and_then(|s| Ok(String::from_utf8(s)?))
+            .unwrap(),
+        expected
+    );
+}
+
+fn check_err(input: &str, expected: &str) {
+    match compile_scss(input.as_bytes(), Default::default()) {
+        Err(err) => assert_eq!(format!("{}", err), expected),
+        Ok(css) => {
+            panic!("Unexpected success: {:?}", String::from_utf8_lossy(&css))
+        }
+    }
+}
@@ -61,10 +61,10 @@ fn t15_arithmetic_and_lists_hijklmt_div_or_not() {
             m: 1/2, $stuff url(\"www.foo.com/blah.png\") blah blah;\n  \
             t: 1 + (2 + (3/4 + (4/5 6/7)));\n}",
         "div {\n  /* shouldn't perform the following division */\n  \
-         h: 15 / 3 / 5;\n  \
+         h: 15/3/5;\n  \
          /* should perform the following division now */\n  \
-         i: 1;\n  /* this too */\n  j: 1;\n  /* and this */\n  k: 5;\n  \
-         l: 1;\n  \
+         i: 1;\n  /* this too */\n  j: 5/5;\n  /* and this */\n  k: 15/3;\n  \
+         l: 15/5/3;\n  \
          m: 1/2, 1 2 3 url(\"www.foo.com/blah.png\") blah blah;\n  \
          t: 120.754/5 6/7;\n}\n",
     )
@@ -111,9 +111,9 @@ fn t15_arithmetic_and_lists() {
          arithmetic with lists */\n  \
          e: 15/10 2 3;\n  f: 123 4 5 6;\n  g: 1114/7 8 9 6;\n  \
          /* shouldn't perform the following division */\n  \
-         h: 15 / 3 / 5;\n  \
+         h: 15/3/5;\n  \
          /* should perform the following division now */\n  i: 1;\n  \
-         /* this too */\n  j: 1;\n  /* and this */\n  k: 5;\n  l: 1;\n  \
+         /* this too */\n  j: 5/5;\n  /* and this */\n  k: 15/3;\n  l: 15/5/3;\n  \
          m: 1/2, 1 2 3 url(\"www.foo.com/blah.png\") blah blah;\n  \
          n: 1 2 3, 1 2 3 4 5 6, 7 8 9;\n  \
          o: 9px;\n  p: 5px;\n  q: 2;\n  r: 64em;\n  s: 2.5em;\n  \
@@ -171,7 +171,7 @@ fn t15_arithmetic_and_lists() {
             p: 4 + 1px;\n  q: (20pt / 10pt);\n  r: 16em * 4;\n  \
             s: (5em / 2);\n  t: 1 + (2 + (3/4 + (4/5 6/7)));\n}",
         "div{a:3;b:3.75;c:1;d:300;e:15/10 2 3;f:123 4 5 6;g:1114/7 8 9 6;\
-         h:15 / 3 / 5;i:1;j:1;k:5;l:1;\
+         h:15/3/5;i:1;j:5/5;k:15/3;l:15/5/3;\
          m:1/2,1 2 3 url(\"www.foo.com/blah.png\") blah blah;\
          n:1 2 3,1 2 3 4 5 6,7 8 9;o:9px;p:5px;q:2;r:64em;s:2.5em;\
          t:12.754/5 6/7}\n",
@@ -299,10 +299,7 @@ fn t50_wrapped_pseudo_selectors() {
 }
 
 fn check(input: &[u8], expected: &str) {
-    let format = Format {
-        style: Style::Compressed,
-        precision: 5,
-    };
+    let format = Format::new(Style::Compressed, 5);
     assert_eq!(
         compile_scss(input, format)
             .and_then(|s| Ok(String::from_utf8(s)?))
@@ -937,7 +937,6 @@ mod single {
             #[allow(unused)]
             use super::rsass;
             #[test]
-            #[ignore] // wrong result
             fn and_comma() {
                 assert_eq!(
                     rsass(
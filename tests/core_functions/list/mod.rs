@@ -396,7 +396,6 @@ mod index {
             );
         }
         #[test]
-        #[ignore] // wrong result
         fn sass_equality() {
             assert_eq!(
                 rsass(
@@ -1597,7 +1596,6 @@ mod zip {
         #[allow(unused)]
         use super::rsass;
         #[test]
-        #[ignore] // unexepected error
         fn empty() {
             assert_eq!(
                 rsass(
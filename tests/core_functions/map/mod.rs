@@ -24,7 +24,6 @@ mod get {
         #[allow(unused)]
         use super::rsass;
         #[test]
-        #[ignore] // wrong result
         fn first() {
             assert_eq!(
                 rsass(
@@ -39,7 +38,6 @@ mod get {
             );
         }
         #[test]
-        #[ignore] // wrong result
         fn last() {
             assert_eq!(
                 rsass(
@@ -54,7 +52,6 @@ mod get {
             );
         }
         #[test]
-        #[ignore] // wrong result
         fn middle() {
             assert_eq!(
                 rsass(
@@ -167,7 +164,6 @@ mod has_key {
         #[allow(unused)]
         use super::rsass;
         #[test]
-        #[ignore] // wrong result
         fn first() {
             assert_eq!(
                 rsass(
@@ -182,7 +178,6 @@ mod has_key {
             );
         }
         #[test]
-        #[ignore] // wrong result
         fn last() {
             assert_eq!(
                 rsass(
@@ -197,7 +192,6 @@ mod has_key {
             );
         }
         #[test]
-        #[ignore] // wrong result
         fn middle() {
             assert_eq!(
                 rsass(
@@ -505,7 +499,6 @@ mod remove {
         #[allow(unused)]
         use super::rsass;
         #[test]
-        #[ignore] // wrong result
         fn first() {
             assert_eq!(
                 rsass(
@@ -520,7 +513,6 @@ mod remove {
             );
         }
         #[test]
-        #[ignore] // wrong result
         fn last() {
             assert_eq!(
                 rsass(
@@ -535,7 +527,6 @@ mod remove {
             );
         }
         #[test]
-        #[ignore] // wrong result
         fn middle() {
             assert_eq!(
                 rsass(
@@ -553,7 +544,6 @@ mod remove {
             #[allow(unused)]
             use super::rsass;
             #[test]
-            #[ignore] // wrong result
             fn all() {
                 assert_eq!(
         rsass(
@@ -568,7 +558,6 @@ mod remove {
     );
             }
             #[test]
-            #[ignore] // wrong result
             fn some() {
                 assert_eq!(
         rsass(
@@ -264,7 +264,6 @@ mod comparable {
         #[allow(unused)]
         use super::rsass;
         #[test]
-        #[ignore] // wrong result
         fn to_compatible() {
             assert_eq!(
                 rsass(
@@ -326,7 +325,6 @@ mod comparable {
         #[allow(unused)]
         use super::rsass;
         #[test]
-        #[ignore] // wrong result
         fn to_unit() {
             assert_eq!(
                 rsass(
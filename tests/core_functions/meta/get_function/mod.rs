@@ -23,7 +23,6 @@ mod different_module {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn defined() {
         assert_eq!(
             rsass(
@@ -39,7 +38,6 @@ mod different_module {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn named() {
         assert_eq!(
         rsass(
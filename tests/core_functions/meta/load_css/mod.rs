@@ -220,7 +220,6 @@ mod plain_css {
         #[allow(unused)]
         use super::rsass;
         #[test]
-        #[ignore] // unexepected error
         fn built_in() {
             assert_eq!(
                 rsass(
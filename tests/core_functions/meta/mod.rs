@@ -10,7 +10,6 @@ mod call {
         #[allow(unused)]
         use super::rsass;
         #[test]
-        #[ignore] // wrong result
         fn named() {
             assert_eq!(
         rsass(
@@ -57,7 +56,6 @@ mod call {
             #[allow(unused)]
             use super::rsass;
             #[test]
-            #[ignore] // wrong result
             fn combined() {
                 assert_eq!(
                     rsass(
@@ -74,7 +72,6 @@ mod call {
                 );
             }
             #[test]
-            #[ignore] // wrong result
             fn named() {
                 assert_eq!(
                     rsass(
@@ -187,7 +184,6 @@ mod content_exists {
         #[allow(unused)]
         use super::rsass;
         #[test]
-        #[ignore] // unexepected error
         fn through_content() {
             assert_eq!(
                 rsass(
@@ -252,7 +248,6 @@ mod content_exists {
             );
         }
         #[test]
-        #[ignore] // unexepected error
         fn non_empty() {
             assert_eq!(
                 rsass(
@@ -446,7 +441,6 @@ mod function_exists {
             );
         }
         #[test]
-        #[ignore] // wrong result
         fn defined() {
             assert_eq!(
                 rsass(
@@ -558,7 +552,6 @@ mod function_exists {
             );
         }
         #[test]
-        #[ignore] // wrong result
         fn undefined() {
             assert_eq!(
                 rsass(
@@ -607,7 +600,6 @@ mod function_exists {
         }
     }
     #[test]
-    #[ignore] // wrong result
     fn named() {
         assert_eq!(
             rsass(
@@ -904,7 +896,6 @@ mod global_variable_exists {
             );
         }
         #[test]
-        #[ignore] // wrong result
         fn undefined() {
             assert_eq!(
                 rsass(
@@ -2241,7 +2232,6 @@ mod keywords {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn named() {
         assert_eq!(
             rsass(
@@ -2415,7 +2405,6 @@ mod mixin_exists {
             );
         }
         #[test]
-        #[ignore] // wrong result
         fn undefined() {
             assert_eq!(
                 rsass(
@@ -2576,7 +2565,6 @@ mod module_functions {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn core_module() {
         assert_eq!(
         rsass(
@@ -2831,7 +2819,6 @@ mod module_variables {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn core_module() {
         assert_eq!(
             rsass(
@@ -3038,7 +3025,6 @@ mod type_of {
     #[allow(unused)]
     use super::rsass;
     #[test]
-    #[ignore] // wrong result
     fn arglist() {
         assert_eq!(
             rsass(
@@ -7,7 +7,6 @@ mod color {
     #[allow(unused)]
     use super::rsass;
     #[test]
-    #[ignore] // wrong result
     fn adjust() {
         assert_eq!(
             rsass(
@@ -23,7 +22,6 @@ mod color {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn alpha() {
         assert_eq!(
             rsass(
@@ -39,7 +37,6 @@ mod color {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn blue() {
         assert_eq!(
             rsass(
@@ -55,7 +52,6 @@ mod color {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn change() {
         assert_eq!(
             rsass(
@@ -71,7 +67,6 @@ mod color {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn complement() {
         assert_eq!(
             rsass(
@@ -93,7 +88,6 @@ mod color {
             #[allow(unused)]
             use super::rsass;
             #[test]
-            #[ignore] // wrong result
             fn multi_arg() {
                 assert_eq!(
                     rsass(
@@ -109,7 +103,6 @@ mod color {
                 );
             }
             #[test]
-            #[ignore] // wrong result
             fn one_arg() {
                 assert_eq!(
                     rsass(
@@ -126,7 +119,6 @@ mod color {
             }
         }
         #[test]
-        #[ignore] // wrong result
         fn grayscale() {
             assert_eq!(
                 rsass(
@@ -142,7 +134,6 @@ mod color {
             );
         }
         #[test]
-        #[ignore] // wrong result
         fn invert() {
             assert_eq!(
                 rsass(
@@ -158,7 +149,6 @@ mod color {
             );
         }
         #[test]
-        #[ignore] // wrong result
         fn opacity() {
             assert_eq!(
                 rsass(
@@ -203,7 +193,6 @@ mod color {
         // Ignoring "transparentize", error tests are not supported yet.
     }
     #[test]
-    #[ignore] // wrong result
     fn green() {
         assert_eq!(
             rsass(
@@ -219,7 +208,6 @@ mod color {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn hue() {
         assert_eq!(
             rsass(
@@ -235,7 +223,6 @@ mod color {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn ie_hex_str() {
         assert_eq!(
             rsass(
@@ -251,7 +238,6 @@ mod color {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn invert() {
         assert_eq!(
             rsass(
@@ -283,7 +269,6 @@ mod color {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn mix() {
         assert_eq!(
             rsass(
@@ -299,7 +284,6 @@ mod color {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn red() {
         assert_eq!(
             rsass(
@@ -315,7 +299,6 @@ mod color {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn saturation() {
         assert_eq!(
             rsass(
@@ -331,7 +314,6 @@ mod color {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn scale() {
         assert_eq!(
             rsass(
@@ -489,7 +471,6 @@ mod map {
         // Ignoring "map_values", error tests are not supported yet.
     }
     #[test]
-    #[ignore] // wrong result
     fn get() {
         assert_eq!(
             rsass(
@@ -505,7 +486,6 @@ mod map {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn has_key() {
         assert_eq!(
             rsass(
@@ -537,7 +517,6 @@ mod map {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn merge() {
         assert_eq!(
             rsass(
@@ -554,7 +533,6 @@ mod map {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn remove() {
         assert_eq!(
             rsass(
@@ -593,7 +571,6 @@ mod math {
     #[allow(unused)]
     use super::rsass;
     #[test]
-    #[ignore] // wrong result
     fn abs() {
         assert_eq!(
             rsass(
@@ -609,7 +586,6 @@ mod math {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn ceil() {
         assert_eq!(
             rsass(
@@ -625,7 +601,6 @@ mod math {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn compatible() {
         assert_eq!(
             rsass(
@@ -649,7 +624,6 @@ mod math {
         // Ignoring "unitless", error tests are not supported yet.
     }
     #[test]
-    #[ignore] // wrong result
     fn floor() {
         assert_eq!(
             rsass(
@@ -665,7 +639,6 @@ mod math {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn is_unitless() {
         assert_eq!(
             rsass(
@@ -681,7 +654,6 @@ mod math {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn max() {
         assert_eq!(
             rsass(
@@ -697,7 +669,6 @@ mod math {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn min() {
         assert_eq!(
             rsass(
@@ -713,7 +684,6 @@ mod math {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn percentage() {
         assert_eq!(
             rsass(
@@ -729,7 +699,6 @@ mod math {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn random() {
         assert_eq!(
             rsass(
@@ -745,7 +714,6 @@ mod math {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn round() {
         assert_eq!(
             rsass(
@@ -761,7 +729,6 @@ mod math {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn unit() {
         assert_eq!(
             rsass(
@@ -783,7 +750,6 @@ mod meta {
     #[allow(unused)]
     use super::rsass;
     #[test]
-    #[ignore] // wrong result
     fn call() {
         assert_eq!(
             rsass(
@@ -799,7 +765,6 @@ mod meta {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn content_exists() {
         assert_eq!(
             rsass(
@@ -820,7 +785,6 @@ mod meta {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn feature_exists() {
         assert_eq!(
             rsass(
@@ -836,7 +800,6 @@ mod meta {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn function_exists() {
         assert_eq!(
             rsass(
@@ -852,7 +815,6 @@ mod meta {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn get_function() {
         assert_eq!(
             rsass(
@@ -868,7 +830,6 @@ mod meta {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn global_variable_exists() {
         assert_eq!(
             rsass(
@@ -884,7 +845,6 @@ mod meta {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn inspect() {
         assert_eq!(
             rsass(
@@ -900,7 +860,6 @@ mod meta {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn keywords() {
         assert_eq!(
             rsass(
@@ -921,7 +880,6 @@ mod meta {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn mixin_exists() {
         assert_eq!(
             rsass(
@@ -937,7 +895,6 @@ mod meta {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn type_of() {
         assert_eq!(
             rsass(
@@ -953,7 +910,6 @@ mod meta {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn variable_exists() {
         assert_eq!(
             rsass(
@@ -975,7 +931,6 @@ mod selector {
     #[allow(unused)]
     use super::rsass;
     #[test]
-    #[ignore] // wrong result
     fn append() {
         assert_eq!(
             rsass(
@@ -1007,7 +962,6 @@ mod selector {
         // Ignoring "selector_unify", error tests are not supported yet.
     }
     #[test]
-    #[ignore] // wrong result
     fn extend() {
         assert_eq!(
             rsass(
@@ -1023,7 +977,6 @@ mod selector {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn is_superselector() {
         assert_eq!(
             rsass(
@@ -1039,7 +992,6 @@ mod selector {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn nest() {
         assert_eq!(
             rsass(
@@ -1055,7 +1007,6 @@ mod selector {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn parse() {
         assert_eq!(
             rsass(
@@ -1071,7 +1022,6 @@ mod selector {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn replace() {
         assert_eq!(
             rsass(
@@ -1087,7 +1037,6 @@ mod selector {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn simple_selectors() {
         assert_eq!(
             rsass(
@@ -1103,7 +1052,6 @@ mod selector {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn unify() {
         assert_eq!(
             rsass(
@@ -1137,7 +1085,6 @@ mod string {
         // Ignoring "str_slice", error tests are not supported yet.
     }
     #[test]
-    #[ignore] // wrong result
     fn index() {
         assert_eq!(
             rsass(
@@ -1153,7 +1100,6 @@ mod string {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn insert() {
         assert_eq!(
             rsass(
@@ -1169,7 +1115,6 @@ mod string {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn length() {
         assert_eq!(
             rsass(
@@ -1185,7 +1130,6 @@ mod string {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn quote() {
         assert_eq!(
             rsass(
@@ -1201,7 +1145,6 @@ mod string {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn slice() {
         assert_eq!(
             rsass(
@@ -1217,7 +1160,6 @@ mod string {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn to_upper_case() {
         assert_eq!(
             rsass(
@@ -1233,7 +1175,6 @@ mod string {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn unique_id() {
         assert_eq!(
             rsass(
@@ -1250,7 +1191,6 @@ mod string {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn unquote() {
         assert_eq!(
             rsass(
@@ -13,7 +13,6 @@ mod complex {
             #[allow(unused)]
             use super::rsass;
             #[test]
-            #[ignore] // wrong result
             fn first() {
                 assert_eq!(
                     rsass(
@@ -28,7 +27,6 @@ mod complex {
                 );
             }
             #[test]
-            #[ignore] // wrong result
             fn in_sub() {
                 assert_eq!(
                     rsass(
@@ -43,7 +41,6 @@ mod complex {
                 );
             }
             #[test]
-            #[ignore] // wrong result
             fn neither() {
                 assert_eq!(
                     rsass(
@@ -58,7 +55,6 @@ mod complex {
                 );
             }
             #[test]
-            #[ignore] // wrong result
             fn second() {
                 assert_eq!(
                     rsass(
@@ -80,7 +76,6 @@ mod complex {
                 #[allow(unused)]
                 use super::rsass;
                 #[test]
-                #[ignore] // wrong result
                 fn equal() {
                     assert_eq!(
                         rsass(
@@ -95,7 +90,6 @@ mod complex {
                     );
                 }
                 #[test]
-                #[ignore] // wrong result
                 fn subset() {
                     assert_eq!(
         rsass(
@@ -110,7 +104,6 @@ mod complex {
     );
                 }
                 #[test]
-                #[ignore] // wrong result
                 fn superset() {
                     assert_eq!(
         rsass(
@@ -126,7 +119,6 @@ mod complex {
                 }
             }
             #[test]
-            #[ignore] // wrong result
             fn in_sub() {
                 assert_eq!(
                     rsass(
@@ -141,7 +133,6 @@ mod complex {
                 );
             }
             #[test]
-            #[ignore] // wrong result
             fn in_super() {
                 assert_eq!(
                     rsass(
@@ -164,7 +155,6 @@ mod complex {
             #[allow(unused)]
             use super::rsass;
             #[test]
-            #[ignore] // wrong result
             fn first() {
                 assert_eq!(
                     rsass(
@@ -179,7 +169,6 @@ mod complex {
                 );
             }
             #[test]
-            #[ignore] // wrong result
             fn in_sub() {
                 assert_eq!(
                     rsass(
@@ -194,7 +183,6 @@ mod complex {
                 );
             }
             #[test]
-            #[ignore] // wrong result
             fn neither() {
                 assert_eq!(
                     rsass(
@@ -209,7 +197,6 @@ mod complex {
                 );
             }
             #[test]
-            #[ignore] // wrong result
             fn second() {
                 assert_eq!(
                     rsass(
@@ -231,7 +218,6 @@ mod complex {
                 #[allow(unused)]
                 use super::rsass;
                 #[test]
-                #[ignore] // wrong result
                 fn equal() {
                     assert_eq!(
                         rsass(
@@ -246,7 +232,6 @@ mod complex {
                     );
                 }
                 #[test]
-                #[ignore] // wrong result
                 fn subset() {
                     assert_eq!(
         rsass(
@@ -261,7 +246,6 @@ mod complex {
     );
                 }
                 #[test]
-                #[ignore] // wrong result
                 fn superset() {
                     assert_eq!(
         rsass(
@@ -277,7 +261,6 @@ mod complex {
                 }
             }
             #[test]
-            #[ignore] // wrong result
             fn in_sub() {
                 assert_eq!(
                     rsass(
@@ -292,7 +275,6 @@ mod complex {
                 );
             }
             #[test]
-            #[ignore] // wrong result
             fn in_super() {
                 assert_eq!(
                     rsass(
@@ -318,7 +300,6 @@ mod complex {
                 #[allow(unused)]
                 use super::rsass;
                 #[test]
-                #[ignore] // wrong result
                 fn first() {
                     assert_eq!(
                         rsass(
@@ -333,7 +314,6 @@ mod complex {
                     );
                 }
                 #[test]
-                #[ignore] // wrong result
                 fn neither() {
                     assert_eq!(
                         rsass(
@@ -348,7 +328,6 @@ mod complex {
                     );
                 }
                 #[test]
-                #[ignore] // wrong result
                 fn second() {
                     assert_eq!(
                         rsass(
@@ -364,7 +343,6 @@ mod complex {
                 }
             }
             #[test]
-            #[ignore] // wrong result
             fn sub() {
                 assert_eq!(
                     rsass(
@@ -379,7 +357,6 @@ mod complex {
                 );
             }
             #[test]
-            #[ignore] // wrong result
             fn test_super() {
                 assert_eq!(
                     rsass(
@@ -398,7 +375,6 @@ mod complex {
             #[allow(unused)]
             use super::rsass;
             #[test]
-            #[ignore] // wrong result
             fn in_sub() {
                 assert_eq!(
                     rsass(
@@ -413,7 +389,6 @@ mod complex {
                 );
             }
             #[test]
-            #[ignore] // wrong result
             fn match_first() {
                 assert_eq!(
                     rsass(
@@ -428,7 +403,6 @@ mod complex {
                 );
             }
             #[test]
-            #[ignore] // wrong result
             fn match_neither() {
                 assert_eq!(
                     rsass(
@@ -443,7 +417,6 @@ mod complex {
                 );
             }
             #[test]
-            #[ignore] // wrong result
             fn match_second() {
                 assert_eq!(
                     rsass(
@@ -465,7 +438,6 @@ mod complex {
                 #[allow(unused)]
                 use super::rsass;
                 #[test]
-                #[ignore] // wrong result
                 fn equal() {
                     assert_eq!(
                         rsass(
@@ -480,7 +452,6 @@ mod complex {
                     );
                 }
                 #[test]
-                #[ignore] // wrong result
                 fn subset() {
                     assert_eq!(
                         rsass(
@@ -495,7 +466,6 @@ mod complex {
                     );
                 }
                 #[test]
-                #[ignore] // wrong result
                 fn superset() {
                     assert_eq!(
                         rsass(
@@ -511,7 +481,6 @@ mod complex {
                 }
             }
             #[test]
-            #[ignore] // wrong result
             fn in_sub() {
                 assert_eq!(
                     rsass(
@@ -526,7 +495,6 @@ mod complex {
                 );
             }
             #[test]
-            #[ignore] // wrong result
             fn in_super() {
                 assert_eq!(
                     rsass(
@@ -552,7 +520,6 @@ mod complex {
                 #[allow(unused)]
                 use super::rsass;
                 #[test]
-                #[ignore] // wrong result
                 fn first() {
                     assert_eq!(
         rsass(
@@ -567,7 +534,6 @@ mod complex {
     );
                 }
                 #[test]
-                #[ignore] // wrong result
                 fn neither() {
                     assert_eq!(
         rsass(
@@ -582,7 +548,6 @@ mod complex {
     );
                 }
                 #[test]
-                #[ignore] // wrong result
                 fn second() {
                     assert_eq!(
         rsass(
@@ -598,7 +563,6 @@ mod complex {
                 }
             }
             #[test]
-            #[ignore] // wrong result
             fn sub() {
                 assert_eq!(
                     rsass(
@@ -613,7 +577,6 @@ mod complex {
                 );
             }
             #[test]
-            #[ignore] // wrong result
             fn test_super() {
                 assert_eq!(
                     rsass(
@@ -632,7 +595,6 @@ mod complex {
             #[allow(unused)]
             use super::rsass;
             #[test]
-            #[ignore] // wrong result
             fn first() {
                 assert_eq!(
                     rsass(
@@ -647,7 +609,6 @@ mod complex {
                 );
             }
             #[test]
-            #[ignore] // wrong result
             fn in_sub() {
                 assert_eq!(
                     rsass(
@@ -662,7 +623,6 @@ mod complex {
                 );
             }
             #[test]
-            #[ignore] // wrong result
             fn neither() {
                 assert_eq!(
                     rsass(
@@ -677,7 +637,6 @@ mod complex {
                 );
             }
             #[test]
-            #[ignore] // wrong result
             fn second() {
                 assert_eq!(
                     rsass(
@@ -699,7 +658,6 @@ mod complex {
                 #[allow(unused)]
                 use super::rsass;
                 #[test]
-                #[ignore] // wrong result
                 fn equal() {
                     assert_eq!(
                         rsass(
@@ -714,7 +672,6 @@ mod complex {
                     );
                 }
                 #[test]
-                #[ignore] // wrong result
                 fn subset() {
                     assert_eq!(
         rsass(
@@ -729,7 +686,6 @@ mod complex {
     );
                 }
                 #[test]
-                #[ignore] // wrong result
                 fn superset() {
                     assert_eq!(
         rsass(
@@ -745,7 +701,6 @@ mod complex {
                 }
             }
             #[test]
-            #[ignore] // wrong result
             fn in_sub() {
                 assert_eq!(
                     rsass(
@@ -760,7 +715,6 @@ mod complex {
                 );
             }
             #[test]
-            #[ignore] // wrong result
             fn in_super() {
                 assert_eq!(
                     rsass(
@@ -783,7 +737,6 @@ mod compound {
     #[allow(unused)]
     use super::rsass;
     #[test]
-    #[ignore] // wrong result
     fn different_order() {
         assert_eq!(
             rsass(
@@ -801,7 +754,6 @@ mod compound {
         #[allow(unused)]
         use super::rsass;
         #[test]
-        #[ignore] // wrong result
         fn absent() {
             assert_eq!(
                 rsass(
@@ -819,7 +771,6 @@ mod compound {
             #[allow(unused)]
             use super::rsass;
             #[test]
-            #[ignore] // wrong result
             fn after() {
                 assert_eq!(
                     rsass(
@@ -834,7 +785,6 @@ mod compound {
                 );
             }
             #[test]
-            #[ignore] // wrong result
             fn before() {
                 assert_eq!(
                     rsass(
@@ -849,7 +799,6 @@ mod compound {
                 );
             }
             #[test]
-            #[ignore] // wrong result
             fn first_letter() {
                 assert_eq!(
                     rsass(
@@ -864,7 +813,6 @@ mod compound {
                 );
             }
             #[test]
-            #[ignore] // wrong result
             fn first_line() {
                 assert_eq!(
                     rsass(
@@ -880,7 +828,6 @@ mod compound {
             }
         }
         #[test]
-        #[ignore] // wrong result
         fn different_order() {
             assert_eq!(
                 rsass(
@@ -895,7 +842,6 @@ mod compound {
             );
         }
         #[test]
-        #[ignore] // wrong result
         fn present() {
             assert_eq!(
                 rsass(
@@ -910,7 +856,6 @@ mod compound {
             );
         }
         #[test]
-        #[ignore] // wrong result
         fn same_order() {
             assert_eq!(
                 rsass(
@@ -926,7 +871,6 @@ mod compound {
         }
     }
     #[test]
-    #[ignore] // wrong result
     fn same_order() {
         assert_eq!(
             rsass(
@@ -941,7 +885,6 @@ mod compound {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn superset() {
         assert_eq!(
             rsass(
@@ -989,7 +932,6 @@ mod error {
 
 // From "sass-spec/spec/core_functions/selector/is_superselector/input.hrx"
 #[test]
-#[ignore] // wrong result
 fn input() {
     assert_eq!(
         rsass(
@@ -1014,7 +956,6 @@ mod list {
         #[allow(unused)]
         use super::rsass;
         #[test]
-        #[ignore] // wrong result
         fn match_one() {
             assert_eq!(
                 rsass(
@@ -1029,7 +970,6 @@ mod list {
             );
         }
         #[test]
-        #[ignore] // wrong result
         fn match_three() {
             assert_eq!(
                 rsass(
@@ -1044,7 +984,6 @@ mod list {
             );
         }
         #[test]
-        #[ignore] // wrong result
         fn match_two() {
             assert_eq!(
                 rsass(
@@ -1059,7 +998,6 @@ mod list {
             );
         }
         #[test]
-        #[ignore] // wrong result
         fn miss_one() {
             assert_eq!(
                 rsass(
@@ -1078,7 +1016,6 @@ mod list {
         #[allow(unused)]
         use super::rsass;
         #[test]
-        #[ignore] // wrong result
         fn both_satisfied_by_one_superselector() {
             assert_eq!(
                 rsass(
@@ -1096,7 +1033,6 @@ mod list {
             #[allow(unused)]
             use super::rsass;
             #[test]
-            #[ignore] // wrong result
             fn equal() {
                 assert_eq!(
                     rsass(
@@ -1111,7 +1047,6 @@ mod list {
                 );
             }
             #[test]
-            #[ignore] // wrong result
             fn subset() {
                 assert_eq!(
                     rsass(
@@ -1126,7 +1061,6 @@ mod list {
                 );
             }
             #[test]
-            #[ignore] // wrong result
             fn superset() {
                 assert_eq!(
                     rsass(
@@ -1142,7 +1076,6 @@ mod list {
             }
         }
         #[test]
-        #[ignore] // wrong result
         fn in_sub() {
             assert_eq!(
                 rsass(
@@ -1157,7 +1090,6 @@ mod list {
             );
         }
         #[test]
-        #[ignore] // wrong result
         fn in_super() {
             assert_eq!(
                 rsass(
@@ -1176,7 +1108,6 @@ mod list {
 
 // From "sass-spec/spec/core_functions/selector/is_superselector/named.hrx"
 #[test]
-#[ignore] // wrong result
 fn named() {
     assert_eq!(
         rsass(
@@ -7,7 +7,6 @@ mod attribute {
     #[allow(unused)]
     use super::rsass;
     #[test]
-    #[ignore] // wrong result
     fn equal() {
         assert_eq!(
             rsass(
@@ -25,7 +24,6 @@ mod attribute {
         #[allow(unused)]
         use super::rsass;
         #[test]
-        #[ignore] // wrong result
         fn name() {
             assert_eq!(
                 rsass(
@@ -40,7 +38,6 @@ mod attribute {
             );
         }
         #[test]
-        #[ignore] // wrong result
         fn operator() {
             assert_eq!(
                 rsass(
@@ -55,7 +52,6 @@ mod attribute {
             );
         }
         #[test]
-        #[ignore] // wrong result
         fn value() {
             assert_eq!(
                 rsass(
@@ -77,7 +73,6 @@ mod class {
     #[allow(unused)]
     use super::rsass;
     #[test]
-    #[ignore] // wrong result
     fn equal() {
         assert_eq!(
             rsass(
@@ -92,7 +87,6 @@ mod class {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn unequal() {
         assert_eq!(
             rsass(
@@ -113,7 +107,6 @@ mod id {
     #[allow(unused)]
     use super::rsass;
     #[test]
-    #[ignore] // wrong result
     fn equal() {
         assert_eq!(
             rsass(
@@ -128,7 +121,6 @@ mod id {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn unequal() {
         assert_eq!(
             rsass(
@@ -149,7 +141,6 @@ mod placeholder {
     #[allow(unused)]
     use super::rsass;
     #[test]
-    #[ignore] // wrong result
     fn equal() {
         assert_eq!(
             rsass(
@@ -164,7 +155,6 @@ mod placeholder {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn unequal() {
         assert_eq!(
             rsass(
@@ -187,7 +177,6 @@ mod test_type {
     #[allow(unused)]
     use super::rsass;
     #[test]
-    #[ignore] // wrong result
     fn and_universal() {
         assert_eq!(
             rsass(
@@ -202,7 +191,6 @@ mod test_type {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn equal() {
         assert_eq!(
             rsass(
@@ -223,7 +211,6 @@ mod test_type {
             #[allow(unused)]
             use super::rsass;
             #[test]
-            #[ignore] // wrong result
             fn and_empty() {
                 assert_eq!(
                     rsass(
@@ -238,7 +225,6 @@ mod test_type {
                 );
             }
             #[test]
-            #[ignore] // wrong result
             fn and_explicit() {
                 assert_eq!(
                     rsass(
@@ -253,7 +239,6 @@ mod test_type {
                 );
             }
             #[test]
-            #[ignore] // wrong result
             fn and_implicit() {
                 assert_eq!(
                     rsass(
@@ -268,7 +253,6 @@ mod test_type {
                 );
             }
             #[test]
-            #[ignore] // wrong result
             fn and_universal() {
                 assert_eq!(
                     rsass(
@@ -287,7 +271,6 @@ mod test_type {
             #[allow(unused)]
             use super::rsass;
             #[test]
-            #[ignore] // wrong result
             fn and_empty() {
                 assert_eq!(
                     rsass(
@@ -305,7 +288,6 @@ mod test_type {
                 #[allow(unused)]
                 use super::rsass;
                 #[test]
-                #[ignore] // wrong result
                 fn equal() {
                     assert_eq!(
                         rsass(
@@ -320,7 +302,6 @@ mod test_type {
                     );
                 }
                 #[test]
-                #[ignore] // wrong result
                 fn unequal() {
                     assert_eq!(
                         rsass(
@@ -336,7 +317,6 @@ mod test_type {
                 }
             }
             #[test]
-            #[ignore] // wrong result
             fn and_implicit() {
                 assert_eq!(
                     rsass(
@@ -351,7 +331,6 @@ mod test_type {
                 );
             }
             #[test]
-            #[ignore] // wrong result
             fn and_universal() {
                 assert_eq!(
                     rsass(
@@ -370,7 +349,6 @@ mod test_type {
             #[allow(unused)]
             use super::rsass;
             #[test]
-            #[ignore] // wrong result
             fn and_empty() {
                 assert_eq!(
                     rsass(
@@ -385,7 +363,6 @@ mod test_type {
                 );
             }
             #[test]
-            #[ignore] // wrong result
             fn and_explicit() {
                 assert_eq!(
                     rsass(
@@ -400,7 +377,6 @@ mod test_type {
                 );
             }
             #[test]
-            #[ignore] // wrong result
             fn and_implicit() {
                 assert_eq!(
                     rsass(
@@ -415,7 +391,6 @@ mod test_type {
                 );
             }
             #[test]
-            #[ignore] // wrong result
             fn and_universal() {
                 assert_eq!(
                     rsass(
@@ -432,7 +407,6 @@ mod test_type {
         }
     }
     #[test]
-    #[ignore] // wrong result
     fn unequal() {
         assert_eq!(
             rsass(
@@ -453,7 +427,6 @@ mod universal {
     #[allow(unused)]
     use super::rsass;
     #[test]
-    #[ignore] // wrong result
     fn and_class() {
         assert_eq!(
             rsass(
@@ -468,7 +441,6 @@ mod universal {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn and_type() {
         assert_eq!(
             rsass(
@@ -483,7 +455,6 @@ mod universal {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn equal() {
         assert_eq!(
             rsass(
@@ -504,7 +475,6 @@ mod universal {
             #[allow(unused)]
             use super::rsass;
             #[test]
-            #[ignore] // wrong result
             fn and_class() {
                 assert_eq!(
                     rsass(
@@ -522,7 +492,6 @@ mod universal {
                 #[allow(unused)]
                 use super::rsass;
                 #[test]
-                #[ignore] // wrong result
                 fn empty() {
                     assert_eq!(
                         rsass(
@@ -537,7 +506,6 @@ mod universal {
                     );
                 }
                 #[test]
-                #[ignore] // wrong result
                 fn explicit() {
                     assert_eq!(
                         rsass(
@@ -552,7 +520,6 @@ mod universal {
                     );
                 }
                 #[test]
-                #[ignore] // wrong result
                 fn implicit() {
                     assert_eq!(
                         rsass(
@@ -571,7 +538,6 @@ mod universal {
                 #[allow(unused)]
                 use super::rsass;
                 #[test]
-                #[ignore] // wrong result
                 fn empty() {
                     assert_eq!(
                         rsass(
@@ -586,7 +552,6 @@ mod universal {
                     );
                 }
                 #[test]
-                #[ignore] // wrong result
                 fn explicit() {
                     assert_eq!(
                         rsass(
@@ -601,7 +566,6 @@ mod universal {
                     );
                 }
                 #[test]
-                #[ignore] // wrong result
                 fn implicit() {
                     assert_eq!(
                         rsass(
@@ -616,7 +580,6 @@ mod universal {
                     );
                 }
                 #[test]
-                #[ignore] // wrong result
                 fn universal() {
                     assert_eq!(
                         rsass(
@@ -636,7 +599,6 @@ mod universal {
             #[allow(unused)]
             use super::rsass;
             #[test]
-            #[ignore] // wrong result
             fn and_class() {
                 assert_eq!(
                     rsass(
@@ -654,7 +616,6 @@ mod universal {
                 #[allow(unused)]
                 use super::rsass;
                 #[test]
-                #[ignore] // wrong result
                 fn empty() {
                     assert_eq!(
                         rsass(
@@ -672,7 +633,6 @@ mod universal {
                     #[allow(unused)]
                     use super::rsass;
                     #[test]
-                    #[ignore] // wrong result
                     fn equal() {
                         assert_eq!(
                             rsass(
@@ -687,7 +647,6 @@ mod universal {
                         );
                     }
                     #[test]
-                    #[ignore] // wrong result
                     fn unequal() {
                         assert_eq!(
                             rsass(
@@ -703,7 +662,6 @@ mod universal {
                     }
                 }
                 #[test]
-                #[ignore] // wrong result
                 fn implicit() {
                     assert_eq!(
                         rsass(
@@ -722,7 +680,6 @@ mod universal {
                 #[allow(unused)]
                 use super::rsass;
                 #[test]
-                #[ignore] // wrong result
                 fn empty() {
                     assert_eq!(
                         rsass(
@@ -740,7 +697,6 @@ mod universal {
                     #[allow(unused)]
                     use super::rsass;
                     #[test]
-                    #[ignore] // wrong result
                     fn equal() {
                         assert_eq!(
                             rsass(
@@ -755,7 +711,6 @@ mod universal {
                         );
                     }
                     #[test]
-                    #[ignore] // wrong result
                     fn unequal() {
                         assert_eq!(
                             rsass(
@@ -771,7 +726,6 @@ mod universal {
                     }
                 }
                 #[test]
-                #[ignore] // wrong result
                 fn implicit() {
                     assert_eq!(
                         rsass(
@@ -786,7 +740,6 @@ mod universal {
                     );
                 }
                 #[test]
-                #[ignore] // wrong result
                 fn universal() {
                     assert_eq!(
                         rsass(
@@ -806,7 +759,6 @@ mod universal {
             #[allow(unused)]
             use super::rsass;
             #[test]
-            #[ignore] // wrong result
             fn and_class() {
                 assert_eq!(
                     rsass(
@@ -824,7 +776,6 @@ mod universal {
                 #[allow(unused)]
                 use super::rsass;
                 #[test]
-                #[ignore] // wrong result
                 fn empty() {
                     assert_eq!(
                         rsass(
@@ -839,7 +790,6 @@ mod universal {
                     );
                 }
                 #[test]
-                #[ignore] // wrong result
                 fn explicit() {
                     assert_eq!(
                         rsass(
@@ -854,7 +804,6 @@ mod universal {
                     );
                 }
                 #[test]
-                #[ignore] // wrong result
                 fn implicit() {
                     assert_eq!(
                         rsass(
@@ -873,7 +822,6 @@ mod universal {
                 #[allow(unused)]
                 use super::rsass;
                 #[test]
-                #[ignore] // wrong result
                 fn empty() {
                     assert_eq!(
                         rsass(
@@ -888,7 +836,6 @@ mod universal {
                     );
                 }
                 #[test]
-                #[ignore] // wrong result
                 fn explicit() {
                     assert_eq!(
                         rsass(
@@ -903,7 +850,6 @@ mod universal {
                     );
                 }
                 #[test]
-                #[ignore] // wrong result
                 fn implicit() {
                     assert_eq!(
                         rsass(
@@ -918,7 +864,6 @@ mod universal {
                     );
                 }
                 #[test]
-                #[ignore] // wrong result
                 fn universal() {
                     assert_eq!(
                         rsass(
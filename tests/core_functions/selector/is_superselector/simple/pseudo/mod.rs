@@ -10,7 +10,6 @@ mod arg {
         #[allow(unused)]
         use super::rsass;
         #[test]
-        #[ignore] // wrong result
         fn equal() {
             assert_eq!(
                 rsass(
@@ -28,7 +27,6 @@ mod arg {
             #[allow(unused)]
             use super::rsass;
             #[test]
-            #[ignore] // wrong result
             fn argument() {
                 assert_eq!(
                     rsass(
@@ -43,7 +41,6 @@ mod arg {
                 );
             }
             #[test]
-            #[ignore] // wrong result
             fn has_argument() {
                 assert_eq!(
                     rsass(
@@ -58,7 +55,6 @@ mod arg {
                 );
             }
             #[test]
-            #[ignore] // wrong result
             fn name() {
                 assert_eq!(
                     rsass(
@@ -78,7 +74,6 @@ mod arg {
         #[allow(unused)]
         use super::rsass;
         #[test]
-        #[ignore] // wrong result
         fn equal() {
             assert_eq!(
                 rsass(
@@ -96,7 +91,6 @@ mod arg {
             #[allow(unused)]
             use super::rsass;
             #[test]
-            #[ignore] // wrong result
             fn argument() {
                 assert_eq!(
                     rsass(
@@ -111,7 +105,6 @@ mod arg {
                 );
             }
             #[test]
-            #[ignore] // wrong result
             fn has_argument() {
                 assert_eq!(
                     rsass(
@@ -126,7 +119,6 @@ mod arg {
                 );
             }
             #[test]
-            #[ignore] // wrong result
             fn name() {
                 assert_eq!(
                     rsass(
@@ -152,7 +144,6 @@ mod no_arg {
         #[allow(unused)]
         use super::rsass;
         #[test]
-        #[ignore] // wrong result
         fn and_element() {
             assert_eq!(
                 rsass(
@@ -167,7 +158,6 @@ mod no_arg {
             );
         }
         #[test]
-        #[ignore] // wrong result
         fn equal() {
             assert_eq!(
                 rsass(
@@ -182,7 +172,6 @@ mod no_arg {
             );
         }
         #[test]
-        #[ignore] // wrong result
         fn unequal() {
             assert_eq!(
                 rsass(
@@ -201,7 +190,6 @@ mod no_arg {
         #[allow(unused)]
         use super::rsass;
         #[test]
-        #[ignore] // wrong result
         fn and_class() {
             assert_eq!(
                 rsass(
@@ -216,7 +204,6 @@ mod no_arg {
             );
         }
         #[test]
-        #[ignore] // wrong result
         fn equal() {
             assert_eq!(
                 rsass(
@@ -231,7 +218,6 @@ mod no_arg {
             );
         }
         #[test]
-        #[ignore] // wrong result
         fn unequal() {
             assert_eq!(
                 rsass(
@@ -10,7 +10,6 @@ mod any {
         #[allow(unused)]
         use super::rsass;
         #[test]
-        #[ignore] // wrong result
         fn subset() {
             assert_eq!(
         rsass(
@@ -25,7 +24,6 @@ mod any {
     );
         }
         #[test]
-        #[ignore] // wrong result
         fn superset() {
             assert_eq!(
         rsass(
@@ -41,7 +39,6 @@ mod any {
         }
     }
     #[test]
-    #[ignore] // wrong result
     fn subset() {
         assert_eq!(
         rsass(
@@ -56,7 +53,6 @@ mod any {
     );
     }
     #[test]
-    #[ignore] // wrong result
     fn superset() {
         assert_eq!(
         rsass(
@@ -77,7 +73,6 @@ mod current {
     #[allow(unused)]
     use super::rsass;
     #[test]
-    #[ignore] // wrong result
     fn bare_sub() {
         assert_eq!(
         rsass(
@@ -92,7 +87,6 @@ mod current {
     );
     }
     #[test]
-    #[ignore] // wrong result
     fn equal() {
         assert_eq!(
         rsass(
@@ -110,7 +104,6 @@ mod current {
         #[allow(unused)]
         use super::rsass;
         #[test]
-        #[ignore] // wrong result
         fn equal() {
             assert_eq!(
         rsass(
@@ -125,7 +118,6 @@ mod current {
     );
         }
         #[test]
-        #[ignore] // wrong result
         fn subset() {
             assert_eq!(
                 rsass(
@@ -144,7 +136,6 @@ mod current {
             );
         }
         #[test]
-        #[ignore] // wrong result
         fn superset() {
             assert_eq!(
                 rsass(
@@ -164,7 +155,6 @@ mod current {
         }
     }
     #[test]
-    #[ignore] // wrong result
     fn subset() {
         assert_eq!(
         rsass(
@@ -179,7 +169,6 @@ mod current {
     );
     }
     #[test]
-    #[ignore] // wrong result
     fn superset() {
         assert_eq!(
         rsass(
@@ -200,7 +189,6 @@ mod has {
     #[allow(unused)]
     use super::rsass;
     #[test]
-    #[ignore] // wrong result
     fn bare_sub() {
         assert_eq!(
         rsass(
@@ -218,7 +206,6 @@ mod has {
         #[allow(unused)]
         use super::rsass;
         #[test]
-        #[ignore] // wrong result
         fn subset() {
             assert_eq!(
         rsass(
@@ -233,7 +220,6 @@ mod has {
     );
         }
         #[test]
-        #[ignore] // wrong result
         fn superset() {
             assert_eq!(
         rsass(
@@ -249,7 +235,6 @@ mod has {
         }
     }
     #[test]
-    #[ignore] // wrong result
     fn subset() {
         assert_eq!(
         rsass(
@@ -264,7 +249,6 @@ mod has {
     );
     }
     #[test]
-    #[ignore] // wrong result
     fn superset() {
         assert_eq!(
         rsass(
@@ -285,7 +269,6 @@ mod host {
     #[allow(unused)]
     use super::rsass;
     #[test]
-    #[ignore] // wrong result
     fn bare_sub() {
         assert_eq!(
         rsass(
@@ -303,7 +286,6 @@ mod host {
         #[allow(unused)]
         use super::rsass;
         #[test]
-        #[ignore] // wrong result
         fn subset() {
             assert_eq!(
         rsass(
@@ -318,7 +300,6 @@ mod host {
     );
         }
         #[test]
-        #[ignore] // wrong result
         fn superset() {
             assert_eq!(
         rsass(
@@ -334,7 +315,6 @@ mod host {
         }
     }
     #[test]
-    #[ignore] // wrong result
     fn subset() {
         assert_eq!(
         rsass(
@@ -349,7 +329,6 @@ mod host {
     );
     }
     #[test]
-    #[ignore] // wrong result
     fn superset() {
         assert_eq!(
         rsass(
@@ -370,7 +349,6 @@ mod host_context {
     #[allow(unused)]
     use super::rsass;
     #[test]
-    #[ignore] // wrong result
     fn bare_sub() {
         assert_eq!(
         rsass(
@@ -388,7 +366,6 @@ mod host_context {
         #[allow(unused)]
         use super::rsass;
         #[test]
-        #[ignore] // wrong result
         fn subset() {
             assert_eq!(
         rsass(
@@ -403,7 +380,6 @@ mod host_context {
     );
         }
         #[test]
-        #[ignore] // wrong result
         fn superset() {
             assert_eq!(
         rsass(
@@ -419,7 +395,6 @@ mod host_context {
         }
     }
     #[test]
-    #[ignore] // wrong result
     fn subset() {
         assert_eq!(
         rsass(
@@ -434,7 +409,6 @@ mod host_context {
     );
     }
     #[test]
-    #[ignore] // wrong result
     fn superset() {
         assert_eq!(
         rsass(
@@ -458,7 +432,6 @@ mod matches {
         #[allow(unused)]
         use super::rsass;
         #[test]
-        #[ignore] // wrong result
         fn subset() {
             assert_eq!(
         rsass(
@@ -473,7 +446,6 @@ mod matches {
     );
         }
         #[test]
-        #[ignore] // wrong result
         fn superset() {
             assert_eq!(
         rsass(
@@ -492,7 +464,6 @@ mod matches {
         #[allow(unused)]
         use super::rsass;
         #[test]
-        #[ignore] // wrong result
         fn subset() {
             assert_eq!(
                 rsass(
@@ -507,7 +478,6 @@ mod matches {
             );
         }
         #[test]
-        #[ignore] // wrong result
         fn superset() {
             assert_eq!(
                 rsass(
@@ -526,7 +496,6 @@ mod matches {
         #[allow(unused)]
         use super::rsass;
         #[test]
-        #[ignore] // wrong result
         fn subset() {
             assert_eq!(
                 rsass(
@@ -541,7 +510,6 @@ mod matches {
             );
         }
         #[test]
-        #[ignore] // wrong result
         fn superset() {
             assert_eq!(
                 rsass(
@@ -560,7 +528,6 @@ mod matches {
         #[allow(unused)]
         use super::rsass;
         #[test]
-        #[ignore] // wrong result
         fn subset() {
             assert_eq!(
         rsass(
@@ -575,7 +542,6 @@ mod matches {
     );
         }
         #[test]
-        #[ignore] // wrong result
         fn superset() {
             assert_eq!(
         rsass(
@@ -594,7 +560,6 @@ mod matches {
         #[allow(unused)]
         use super::rsass;
         #[test]
-        #[ignore] // wrong result
         fn any() {
             assert_eq!(
         rsass(
@@ -609,7 +574,6 @@ mod matches {
     );
         }
         #[test]
-        #[ignore] // wrong result
         fn prefixed() {
             assert_eq!(
         rsass(
@@ -628,7 +592,6 @@ mod matches {
         #[allow(unused)]
         use super::rsass;
         #[test]
-        #[ignore] // wrong result
         fn subset() {
             assert_eq!(
         rsass(
@@ -643,7 +606,6 @@ mod matches {
     );
         }
         #[test]
-        #[ignore] // wrong result
         fn superset() {
             assert_eq!(
         rsass(
@@ -662,7 +624,6 @@ mod matches {
         #[allow(unused)]
         use super::rsass;
         #[test]
-        #[ignore] // wrong result
         fn equal() {
             assert_eq!(
                 rsass(
@@ -677,7 +638,6 @@ mod matches {
             );
         }
         #[test]
-        #[ignore] // wrong result
         fn unequal() {
             assert_eq!(
                 rsass(
@@ -699,7 +659,6 @@ mod not {
     #[allow(unused)]
     use super::rsass;
     #[test]
-    #[ignore] // wrong result
     fn bare_sub() {
         assert_eq!(
         rsass(
@@ -720,7 +679,6 @@ mod not {
             #[allow(unused)]
             use super::rsass;
             #[test]
-            #[ignore] // wrong result
             fn subset() {
                 assert_eq!(
         rsass(
@@ -735,7 +693,6 @@ mod not {
     );
             }
             #[test]
-            #[ignore] // wrong result
             fn superset() {
                 assert_eq!(
         rsass(
@@ -754,7 +711,6 @@ mod not {
             #[allow(unused)]
             use super::rsass;
             #[test]
-            #[ignore] // wrong result
             fn subset() {
                 assert_eq!(
         rsass(
@@ -769,7 +725,6 @@ mod not {
     );
             }
             #[test]
-            #[ignore] // wrong result
             fn superset() {
                 assert_eq!(
         rsass(
@@ -785,7 +740,6 @@ mod not {
         }
     }
     #[test]
-    #[ignore] // wrong result
     fn id() {
         assert_eq!(
             rsass(
@@ -803,7 +757,6 @@ mod not {
         #[allow(unused)]
         use super::rsass;
         #[test]
-        #[ignore] // wrong result
         fn subset() {
             assert_eq!(
         rsass(
@@ -818,7 +771,6 @@ mod not {
     );
         }
         #[test]
-        #[ignore] // wrong result
         fn superset() {
             assert_eq!(
         rsass(
@@ -834,7 +786,6 @@ mod not {
         }
     }
     #[test]
-    #[ignore] // wrong result
     fn subset() {
         assert_eq!(
         rsass(
@@ -849,7 +800,6 @@ mod not {
     );
     }
     #[test]
-    #[ignore] // wrong result
     fn superset() {
         assert_eq!(
         rsass(
@@ -864,7 +814,6 @@ mod not {
     );
     }
     #[test]
-    #[ignore] // wrong result
     fn test_type() {
         assert_eq!(
             rsass(
@@ -885,7 +834,6 @@ mod nth_child {
     #[allow(unused)]
     use super::rsass;
     #[test]
-    #[ignore] // wrong result
     fn bare_sub() {
         assert_eq!(
         rsass(
@@ -900,7 +848,6 @@ mod nth_child {
     );
     }
     #[test]
-    #[ignore] // wrong result
     fn bare_super() {
         assert_eq!(
             rsass(
@@ -915,7 +862,6 @@ mod nth_child {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn different_arg() {
         assert_eq!(
         rsass(
@@ -933,7 +879,6 @@ mod nth_child {
         #[allow(unused)]
         use super::rsass;
         #[test]
-        #[ignore] // wrong result
         fn subset() {
             assert_eq!(
                 rsass(
@@ -952,7 +897,6 @@ mod nth_child {
             );
         }
         #[test]
-        #[ignore] // wrong result
         fn superset() {
             assert_eq!(
                 rsass(
@@ -972,7 +916,6 @@ mod nth_child {
         }
     }
     #[test]
-    #[ignore] // wrong result
     fn subset() {
         assert_eq!(
             rsass(
@@ -991,7 +934,6 @@ mod nth_child {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn superset() {
         assert_eq!(
             rsass(
@@ -1016,7 +958,6 @@ mod nth_last_child {
     #[allow(unused)]
     use super::rsass;
     #[test]
-    #[ignore] // wrong result
     fn bare_sub() {
         assert_eq!(
         rsass(
@@ -1031,7 +972,6 @@ mod nth_last_child {
     );
     }
     #[test]
-    #[ignore] // wrong result
     fn bare_super() {
         assert_eq!(
         rsass(
@@ -1046,7 +986,6 @@ mod nth_last_child {
     );
     }
     #[test]
-    #[ignore] // wrong result
     fn different_arg() {
         assert_eq!(
             rsass(
@@ -1068,7 +1007,6 @@ mod nth_last_child {
         #[allow(unused)]
         use super::rsass;
         #[test]
-        #[ignore] // wrong result
         fn subset() {
             assert_eq!(
                 rsass(
@@ -1087,7 +1025,6 @@ mod nth_last_child {
             );
         }
         #[test]
-        #[ignore] // wrong result
         fn superset() {
             assert_eq!(
                 rsass(
@@ -1107,7 +1044,6 @@ mod nth_last_child {
         }
     }
     #[test]
-    #[ignore] // wrong result
     fn subset() {
         assert_eq!(
             rsass(
@@ -1126,7 +1062,6 @@ mod nth_last_child {
         );
     }
     #[test]
-    #[ignore] // wrong result
     fn superset() {
         assert_eq!(
             rsass(
@@ -1151,7 +1086,6 @@ mod slotted {
     #[allow(unused)]
     use super::rsass;
     #[test]
-    #[ignore] // wrong result
     fn bare_sub() {
         assert_eq!(
         rsass(
@@ -1169,7 +1103,6 @@ mod slotted {
         #[allow(unused)]
         use super::rsass;
         #[test]
-        #[ignore] // wrong result
         fn subset() {
             assert_eq!(
         rsass(
@@ -1184,7 +1117,6 @@ mod slotted {
     );
         }
         #[test]
-        #[ignore] // wrong result
         fn superset() {
             assert_eq!(
         rsass(
@@ -1200,7 +1132,6 @@ mod slotted {
         }
     }
     #[test]
-    #[ignore] // wrong result
     fn subset() {
         assert_eq!(
         rsass(
@@ -1215,7 +1146,6 @@ mod slotted {
     );
     }
     #[test]
-    #[ignore] // wrong result
     fn superset() {
         assert_eq!(
         rsass(
@@ -239,7 +239,6 @@ mod selector {
                 #[allow(unused)]
                 use super::rsass;
                 #[test]
-                #[ignore] // unexepected error
                 fn arg() {
                     assert_eq!(
                         rsass(
@@ -312,7 +311,6 @@ mod selector {
                 #[allow(unused)]
                 use super::rsass;
                 #[test]
-                #[ignore] // unexepected error
                 fn arg() {
                     assert_eq!(
                         rsass(
@@ -255,7 +255,6 @@ mod keyframes {
         #[allow(unused)]
         use super::rsass;
         #[test]
-        #[ignore] // wrong result
         fn empty() {
             assert_eq!(
                 rsass(
@@ -447,7 +447,6 @@ fn nested() {
 
 // From "sass-spec/spec/libsass/at-root/with_without.hrx"
 #[test]
-#[ignore] // unexepected error
 fn with_without() {
     assert_eq!(
         rsass(
@@ -2035,7 +2035,6 @@ mod warn_directive_nested;
 
 // From "sass-spec/spec/libsass/wrapped-selector-whitespace.hrx"
 #[test]
-#[ignore] // wrong result
 fn wrapped_selector_whitespace() {
     assert_eq!(
         rsass(
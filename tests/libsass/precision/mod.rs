@@ -29,10 +29,8 @@ fn default() {
 // From "sass-spec/spec/libsass/precision/higher.hrx"
 #[test]
 fn higher() {
-    let format = rsass::output::Format {
-        style: rsass::output::Style::Expanded,
-        precision: 6,
-    };
+    let format =
+        rsass::output::Format::new(rsass::output::Style::Expanded, 6);
     assert_eq!(
         crate::rsass_fmt(
             format,
@@ -56,10 +54,8 @@ fn higher() {
 #[test]
 #[ignore] // wrong result
 fn lower() {
-    let format = rsass::output::Format {
-        style: rsass::output::Style::Expanded,
-        precision: 4,
-    };
+    let format =
+        rsass::output::Format::new(rsass::output::Style::Expanded, 4);
     assert_eq!(
         crate::rsass_fmt(
             format,
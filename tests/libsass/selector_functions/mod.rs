@@ -25,7 +25,6 @@ fn selector_length() {
 
 // From "sass-spec/spec/libsass/selector-functions/simple-selector.hrx"
 #[test]
-#[ignore] // wrong result
 fn simple_selector() {
     assert_eq!(
         rsass(
@@ -0,0 +1,42 @@
+//! Tests for `meta.load-css`, that needs actual files to load.
+use rsass::{compile_scss, compile_scss_file};
+
+#[test]
+fn load_css_at_root_and_nested() {
+    assert_eq!(
+        compile("tests/load_css/input.scss"),
+        ".button {\n  color: blue;\n}\n\n\
+         .nested .button {\n  color: red;\n}\n"
+    )
+}
+
+#[test]
+fn load_css_has_own_scope() {
+    assert_eq!(
+        compile("tests/load_css/isolated.scss"),
+        ".button {\n  color: blue;\n}\n\na {\n  color: green;\n}\n"
+    )
+}
+
+#[test]
+fn load_css_by_namespace() {
+    assert_eq!(
+        compile("tests/load_css/namespace.scss"),
+        ".button {\n  color: red;\n}\n"
+    )
+}
+
+#[test]
+fn load_css_requires_use() {
+    assert!(compile_scss(
+        b"@include meta.load-css(\"tests/load_css/theme\");",
+        Default::default()
+    )
+    .is_err())
+}
+
+fn compile(path: &str) -> String {
+    compile_scss_file(path.as_ref(), Default::default())
+        .and_then(|s| Ok(String::from_utf8(s)?))
+        .unwrap()
+}
@@ -6,7 +6,6 @@ mod error;
 
 // From "sass-spec/spec/mixin/content/arguments/none.hrx"
 #[test]
-#[ignore] // unexepected error
 fn none() {
     assert_eq!(
         rsass(
@@ -82,7 +81,6 @@ fn none() {
 
 // From "sass-spec/spec/mixin/content/arguments/passing.hrx"
 #[test]
-#[ignore] // unexepected error
 fn passing() {
     assert_eq!(
         rsass(
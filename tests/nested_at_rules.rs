@@ -0,0 +1,119 @@
+//! Tests for `@keyframes` and at-rules with declarations of their own,
+//! like `@font-face` and `@page`, nested in style rules.
+use rsass::compile_scss;
+
+#[test]
+fn keyframes_in_rule() {
+    check(
+        ".a {\n  @keyframes b {\n    from {c: d}\n    50.5% {c: e}\n  }\n}\n",
+        "@keyframes b {\n  from {\n    c: d;\n  }\n  50.5% {\n    c: e;\n  \
+         }\n}\n",
+    )
+}
+
+#[test]
+fn vendor_keyframes_in_rule() {
+    check(
+        ".a {\n  @-webkit-keyframes b {0%, to {c: d}}\n}\n",
+        "@-webkit-keyframes b {\n  0%, to {\n    c: d;\n  }\n}\n",
+    )
+}
+
+#[test]
+fn keyframe_interpolation() {
+    check(
+        "@keyframes a {\n  @for $i from 1 through 2 {\n    \
+         #{$i * 10}% {b: $i}\n  }\n}\n",
+        "@keyframes a {\n  10% {\n    b: 1;\n  }\n  20% {\n    b: 2;\n  \
+         }\n}\n",
+    )
+}
+
+#[test]
+fn font_face_in_rule() {
+    check(
+        ".a {\n  b: c;\n  @font-face {font-family: d}\n}\n",
+        ".a {\n  b: c;\n}\n@font-face {\n  font-family: d;\n}\n",
+    )
+}
+
+#[test]
+fn page_in_rule() {
+    check(
+        ".a {\n  @page :first {margin: 1in}\n}\n",
+        "@page :first {\n  margin: 1in;\n}\n",
+    )
+}
+
+#[test]
+fn counter_style_in_rule() {
+    check(
+        ".a {\n  @counter-style b {system: cyclic}\n}\n",
+        "@counter-style b {\n  system: cyclic;\n}\n",
+    )
+}
+
+#[test]
+fn property_in_rule() {
+    check(
+        ".a {\n  @property --b {syntax: \"*\"}\n}\n",
+        "@property --b {\n  syntax: \"*\";\n}\n",
+    )
+}
+
+#[test]
+fn font_feature_values_in_rule() {
+    check(
+        ".a {\n  @font-feature-values b {font-display: swap}\n}\n",
+        "@font-feature-values b {\n  font-display: swap;\n}\n",
+    )
+}
+
+#[test]
+fn font_palette_values_in_rule() {
+    check(
+        ".a {\n  @font-palette-values --b {font-family: c}\n}\n",
+        "@font-palette-values --b {\n  font-family: c;\n}\n",
+    )
+}
+
+#[test]
+fn viewport_in_rule() {
+    check(
+        ".a {\n  @viewport {width: device-width}\n}\n",
+        "@viewport {\n  width: device-width;\n}\n",
+    )
+}
+
+#[test]
+fn vendor_viewport_in_rule() {
+    check(
+        ".a {\n  @-ms-viewport {width: device-width}\n}\n",
+        "@-ms-viewport {\n  width: device-width;\n}\n",
+    )
+}
+
+#[test]
+fn font_face_in_media_in_rule() {
+    check(
+        ".a {\n  @media print {\n    @font-face {b: c}\n  }\n}\n",
+        "@media print {\n  @font-face {\n    b: c;\n  }\n}\n",
+    )
+}
+
+#[test]
+fn other_at_rule_keeps_rule() {
+    check(
+        ".a {\n  @foo {b: c}\n}\n",
+        "@foo {\n  .a {\n    b: c;\n  }\n}\n",
+    )
+}
+
+fn check(input: &str, expected: &str) {
+    assert_eq!(
+        compile_scss(input.as_bytes(), Default::default())
+            .and_then(|s| Ok(String::from_utf8(s)?))
+            .unwrap(),
+        expected
+    );
+}
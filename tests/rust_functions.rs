@@ -4,10 +4,7 @@ use std::sync::Arc;
 #[test]
 fn simple_value() {
     let parsed = parse_scss_data(b"p { color: $color }").unwrap();
-    let format = output::Format {
-        style: output::Style::Compressed,
-        precision: 5,
-    };
+    let format = output::Format::new(output::Style::Compressed, 5);
     let mut scope = GlobalScope::new(format);
     scope.define("color", &css::Value::black());
     let file_context = FileContext::new();
@@ -22,10 +19,7 @@ fn simple_value() {
 
 #[test]
 fn simple_function() {
-    let format = output::Format {
-        style: output::Style::Compressed,
-        precision: 5,
-    };
+    let format = output::Format::new(output::Style::Compressed, 5);
     let mut scope = GlobalScope::new(format);
     scope.define_function(
         "get_answer",
@@ -82,10 +76,7 @@ fn function_with_args() {
         ),
     );
     let parsed = parse_scss_data(b"p { x: halfway(10, 18); }").unwrap();
-    let format = output::Format {
-        style: output::Style::Compressed,
-        precision: 5,
-    };
+    let format = output::Format::new(output::Style::Compressed, 5);
     let file_context = FileContext::new();
     assert_eq!(
         format
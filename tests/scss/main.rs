@@ -440,7 +440,6 @@ fn comment_after_if_directive() {
 
 // From "sass-spec/spec/scss/composed-args.hrx"
 #[test]
-#[ignore] // wrong result
 fn composed_args() {
     assert_eq!(
         rsass(
@@ -4,7 +4,6 @@ use super::rsass;
 
 // From "sass-spec/spec/scss/media/nesting/merged.hrx"
 #[test]
-#[ignore] // wrong result
 fn merged() {
     assert_eq!(
         rsass(
@@ -276,7 +275,6 @@ fn merged() {
 
 // From "sass-spec/spec/scss/media/nesting/merged_and_retained.hrx"
 #[test]
-#[ignore] // wrong result
 fn merged_and_retained() {
     assert_eq!(
         rsass(
@@ -323,7 +321,6 @@ fn merged_and_retained() {
 
 // From "sass-spec/spec/scss/media/nesting/removed.hrx"
 #[test]
-#[ignore] // wrong result
 fn removed() {
     assert_eq!(
         rsass(
@@ -446,7 +443,6 @@ fn removed() {
 
 // From "sass-spec/spec/scss/media/nesting/retained.hrx"
 #[test]
-#[ignore] // wrong result
 fn retained() {
     assert_eq!(
         rsass(
@@ -1252,7 +1252,6 @@ fn t066_test_mixin_var_args_act_like_list() {
 
 // From "sass-spec/spec/scss-tests/067_test_mixin_splat_args.hrx"
 #[test]
-#[ignore] // wrong result
 fn t067_test_mixin_splat_args() {
     assert_eq!(
         rsass(
@@ -1280,7 +1279,6 @@ fn t067_test_mixin_splat_args() {
 
 // From "sass-spec/spec/scss-tests/068_test_mixin_splat_expression.hrx"
 #[test]
-#[ignore] // wrong result
 fn t068_test_mixin_splat_expression() {
     assert_eq!(
         rsass(
@@ -1307,7 +1305,6 @@ fn t068_test_mixin_splat_expression() {
 
 // From "sass-spec/spec/scss-tests/069_test_mixin_splat_args_with_var_args.hrx"
 #[test]
-#[ignore] // wrong result
 fn t069_test_mixin_splat_args_with_var_args() {
     assert_eq!(
         rsass(
@@ -1331,7 +1328,6 @@ fn t069_test_mixin_splat_args_with_var_args() {
 
 // From "sass-spec/spec/scss-tests/070_test_mixin_splat_args_with_var_args_and_normal_args.hrx"
 #[test]
-#[ignore] // wrong result
 fn t070_test_mixin_splat_args_with_var_args_and_normal_args() {
     assert_eq!(
         rsass(
@@ -1381,7 +1377,6 @@ fn t071_test_mixin_splat_args_with_var_args_preserves_separator() {
 
 // From "sass-spec/spec/scss-tests/072_test_mixin_var_and_splat_args_pass_through_keywords.hrx"
 #[test]
-#[ignore] // wrong result
 fn t072_test_mixin_var_and_splat_args_pass_through_keywords() {
     assert_eq!(
         rsass(
@@ -1410,7 +1405,6 @@ fn t072_test_mixin_var_and_splat_args_pass_through_keywords() {
 
 // From "sass-spec/spec/scss-tests/078_test_mixin_list_of_pairs_splat_treated_as_list.hrx"
 #[test]
-#[ignore] // wrong result
 fn t078_test_mixin_list_of_pairs_splat_treated_as_list() {
     assert_eq!(
         rsass(
@@ -1497,7 +1491,6 @@ fn t085_test_function_var_args_act_like_list() {
 
 // From "sass-spec/spec/scss-tests/086_test_function_splat_args.hrx"
 #[test]
-#[ignore] // wrong result
 fn t086_test_function_splat_args() {
     assert_eq!(
         rsass(
@@ -1519,7 +1512,6 @@ fn t086_test_function_splat_args() {
 
 // From "sass-spec/spec/scss-tests/087_test_function_splat_expression.hrx"
 #[test]
-#[ignore] // wrong result
 fn t087_test_function_splat_expression() {
     assert_eq!(
         rsass(
@@ -1540,7 +1532,6 @@ fn t087_test_function_splat_expression() {
 
 // From "sass-spec/spec/scss-tests/088_test_function_splat_args_with_var_args.hrx"
 #[test]
-#[ignore] // wrong result
 fn t088_test_function_splat_args_with_var_args() {
     assert_eq!(
         rsass(
@@ -1562,7 +1553,6 @@ fn t088_test_function_splat_args_with_var_args() {
 
 // From "sass-spec/spec/scss-tests/089_test_function_splat_args_with_var_args_and_normal_args.hrx"
 #[test]
-#[ignore] // wrong result
 fn t089_test_function_splat_args_with_var_args_and_normal_args() {
     assert_eq!(
         rsass(
@@ -1606,7 +1596,6 @@ fn t090_test_function_splat_args_with_var_args_preserves_separator() {
 
 // From "sass-spec/spec/scss-tests/091_test_function_var_and_splat_args_pass_through_keywords.hrx"
 #[test]
-#[ignore] // wrong result
 fn t091_test_function_var_and_splat_args_pass_through_keywords() {
     assert_eq!(
         rsass(
@@ -1631,7 +1620,6 @@ fn t091_test_function_var_and_splat_args_pass_through_keywords() {
 
 // From "sass-spec/spec/scss-tests/098_test_function_list_of_pairs_splat_treated_as_list.hrx"
 #[test]
-#[ignore] // wrong result
 fn t098_test_function_list_of_pairs_splat_treated_as_list() {
     assert_eq!(
         rsass(
@@ -1654,7 +1642,6 @@ fn t098_test_function_list_of_pairs_splat_treated_as_list() {
 
 // From "sass-spec/spec/scss-tests/103_test_function_var_args_passed_to_native.hrx"
 #[test]
-#[ignore] // unexepected error
 fn t103_test_function_var_args_passed_to_native() {
     assert_eq!(
         rsass(
@@ -1997,7 +1984,6 @@ fn t128_test_supports_with_expressions() {
 
 // From "sass-spec/spec/scss-tests/129_test_supports_bubbling.hrx"
 #[test]
-#[ignore] // wrong result
 fn t129_test_supports_bubbling() {
     assert_eq!(
         rsass(
@@ -0,0 +1,56 @@
+//! Tests for slash-separated lists, like `font: 12px/1.5 serif`.
+use rsass::compile_scss;
+
+#[test]
+fn font_shorthand_with_variables() {
+    check(
+        "$size: 12px;\n$height: 1.5;\n.a {font: $size/$height serif}\n",
+        ".a {\n  font: 12px/1.5 serif;\n}\n",
+    )
+}
+
+#[test]
+fn grid_area() {
+    check(
+        ".a {grid-area: 1 / 2 / 3}\n",
+        ".a {\n  grid-area: 1/2/3;\n}\n",
+    )
+}
+
+#[test]
+fn variable_assignment_divides() {
+    check("$x: 10px/2;\n.a {w: $x}\n", ".a {\n  w: 5px;\n}\n")
+}
+
+#[test]
+fn variables_mixed_with_numbers() {
+    check(
+        "$w: 10px;\n$lh: 1.5;\n.a {b: $w/2; c: 12px/$lh}\n",
+        ".a {\n  b: 10px/2;\n  c: 12px/1.5;\n}\n",
+    )
+}
+
+#[test]
+fn arithmetic_divides() {
+    check(
+        "$f: 12px;\n.a {b: $f/2 + 1; c: percentage(1/2)}\n",
+        ".a {\n  b: 7px;\n  c: 50%;\n}\n",
+    )
+}
+
+#[test]
+fn calc_divides() {
+    check(
+        "$a: 12px;\n$b: 1.5;\n.a {b: calc($a/$b); c: math.div($a, $b)}\n",
+        ".a {\n  b: 8px;\n  c: 8px;\n}\n",
+    )
+}
+
+fn check(input: &str, expected: &str) {
+    assert_eq!(
+        compile_scss(input.as_bytes(), Default::default())
+            .and_then(|s| Ok(String::from_utf8(s)?))
+            .unwrap(),
+        expected
+    );
+}
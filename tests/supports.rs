@@ -0,0 +1,54 @@
+//! Tests for `@supports` conditions.
+use rsass::compile_scss;
+
+#[test]
+fn not_with_interpolation() {
+    check(
+        "$prop: display;\n$val: grid;\n\
+         @supports not (#{$prop}: #{$val}) {.a {b: c}}\n",
+        "@supports not (display: grid) {\n  .a {\n    b: c;\n  }\n}\n",
+    )
+}
+
+#[test]
+fn declaration_expressions() {
+    check(
+        "$val: 1;\n@supports ($val + 1: $val * 3) {.a {b: c}}\n",
+        "@supports (2: 3) {\n  .a {\n    b: c;\n  }\n}\n",
+    )
+}
+
+#[test]
+fn bubbles_through_rule() {
+    check(
+        ".a {\n  @supports (a: b) or selector(c > d) {e: f}\n}\n",
+        "@supports (a: b) or selector(c > d) {\n  .a {\n    e: f;\n  }\n}\n",
+    )
+}
+
+#[test]
+fn nested_in_media() {
+    check(
+        "@media print {\n  .a {\n    @supports (b: c) {d: e}\n  }\n}\n",
+        "@media print {\n  @supports (b: c) {\n    .a {\n      d: e;\n    }\n  \
+         }\n}\n",
+    )
+}
+
+#[test]
+fn media_nested_in_supports() {
+    check(
+        "@supports (a: b) {\n  .c {\n    @media print {d: e}\n  }\n}\n",
+        "@supports (a: b) {\n  @media print {\n    .c {\n      d: e;\n    }\n  \
+         }\n}\n",
+    )
+}
+
+fn check(input: &str, expected: &str) {
+    assert_eq!(
+        compile_scss(input.as_bytes(), Default::default())
+            .and_then(|s| Ok(String::from_utf8(s)?))
+            .unwrap(),
+        expected
+    );
+}
@@ -15,10 +15,7 @@ fn unicode_in_expanded() {
 
 #[test]
 fn unicode_in_compressed() {
-    let format = output::Format {
-        style: output::Style::Compressed,
-        precision: 5,
-    };
+    let format = output::Format::new(output::Style::Compressed, 5);
     // Blåbärsöl is a proper swedish word.  Translates to blueberry beer.
     // The charset declaration is replaced with a byte order mark.
     check(